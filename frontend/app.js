@@ -50,6 +50,34 @@ function showLoading(show) {
     }
 }
 
+// 转义客户端上报或第三方返回的文本，避免写入 innerHTML 时被当作 HTML 执行
+function escapeHtml(value) {
+    return String(value ?? '')
+        .replace(/&/g, '&amp;')
+        .replace(/</g, '&lt;')
+        .replace(/>/g, '&gt;')
+        .replace(/"/g, '&quot;')
+        .replace(/'/g, '&#39;');
+}
+
+// 仅放行 http/https 链接，其余协议（如 javascript:）一律视为不安全
+function safeUrl(value) {
+    try {
+        const url = new URL(value, window.location.href);
+        return url.protocol === 'http:' || url.protocol === 'https:' ? url.href : null;
+    } catch {
+        return null;
+    }
+}
+
+// 链接不安全时只显示文字
+function renderLink(href, text) {
+    const url = safeUrl(href);
+    return url
+        ? `<a href="${escapeHtml(url)}" target="_blank" rel="noopener noreferrer">${escapeHtml(text)}</a>`
+        : `<span>${escapeHtml(text)}</span>`;
+}
+
 function formatTimeAgo(dateString) {
     const now = new Date();
     const then = new Date(dateString);
@@ -120,11 +148,24 @@ function renderStatuses(data) {
 
     // 渲染供应商状态卡片
     const providerOrder = ['OpenAI', 'Anthropic', 'DeepSeek', 'Google'];
+    // 自定义供应商的链接与图标
+    const providerMeta = data.providers || {};
+    // 主动探测结果
+    const probeResults = data.probes || {};
+    // 网络连通性检查结果
+    const networkResults = data.network || {};
+    // 官方状态与实测差异
+    const discrepancies = {};
+    (data.discrepancies || []).forEach(d => { discrepancies[d.provider] = d; });
+    // 客户端上报的实测健康度
+    const observed = data.observed || {};
+    // 模型列表与最近变更
+    const modelListings = data.models || {};
     
     providerOrder.forEach(providerName => {
         const providerData = data.data[providerName];
         if (providerData) {
-            const card = createProviderCard(providerName, providerData);
+            const card = createProviderCard(providerName, providerData, providerMeta[providerName], probeResults[providerName], networkResults[providerName], discrepancies[providerName], observed[providerName], modelListings[providerName]);
             cardsContainer.appendChild(card);
         }
     });
@@ -132,28 +173,171 @@ function renderStatuses(data) {
     // 其他供应商
     for (const providerName in data.data) {
         if (!providerOrder.includes(providerName)) {
-            const card = createProviderCard(providerName, data.data[providerName]);
+            const card = createProviderCard(providerName, data.data[providerName], providerMeta[providerName], probeResults[providerName], networkResults[providerName], discrepancies[providerName], observed[providerName], modelListings[providerName]);
             cardsContainer.appendChild(card);
         }
     }
 }
 
-function createProviderCard(providerName, providerData) {
+// 主动探测状态映射
+const probeStatusMap = {
+    "up": { class: "status-operational", text: "正常" },
+    "degraded": { class: "status-degraded", text: "响应缓慢" },
+    "down": { class: "status-major-outage", text: "不可用" },
+    "misconfigured": { class: "status-unknown", text: "配置错误" },
+};
+
+function createProbeSection(probes) {
+    const items = probes.map(probe => {
+        const info = probeStatusMap[probe.status] || statusMap.unknown;
+        const code = probe.http_status ? ` · HTTP ${probe.http_status}` : '';
+        const error = probe.error ? `<p class="probe-error">${escapeHtml(probe.error)}</p>` : '';
+        const stream = probe.stream ? `
+                <p class="probe-stream">
+                    首token ${probe.stream.ttft_ms}ms · ${probe.stream.tokens_per_sec} token/s
+                    ${probe.baseline ? `<span class="probe-baseline">(基线 ${probe.baseline.ttft_ms}ms · ${probe.baseline.tokens_per_sec} token/s)</span>` : ''}
+                    ${probe.slow ? '<span class="probe-slow">🐢 变慢</span>' : ''}
+                </p>` : '';
+        return `
+            <li class="probe-item">
+                <span class="probe-badge ${info.class}">${info.text}</span>
+                <span class="probe-name">${escapeHtml(probe.model || probe.id)}</span>
+                <span class="probe-meta">${probe.latency_ms}ms${code} · ${formatTimeAgo(probe.checked_at)}</span>
+                ${stream}
+                ${error}
+            </li>`;
+    }).join('');
+    return `
+        <div class="probe-section">
+            <h3>📡 探测状态</h3>
+            <ul class="probe-list">${items}</ul>
+        </div>`;
+}
+
+// 实测错误率对应的状态样式
+function errorRateClass(rate) {
+    if (rate >= 0.2) return 'status-major-outage';
+    if (rate >= 0.05) return 'status-degraded';
+    return 'status-operational';
+}
+
+function formatWindow(summary) {
+    const latency = summary.latency_p95_ms != null ? ` · P95 ${summary.latency_p95_ms}ms` : '';
+    return `${(summary.error_rate * 100).toFixed(1)}% 错误 · ${summary.requests} 次请求${latency}`;
+}
+
+function createObservedSection(health) {
+    const windows = ['5m', '15m', '60m']
+        .filter(name => health.windows[name] && health.windows[name].requests > 0)
+        .map(name => `
+            <li class="probe-item">
+                <span class="probe-badge ${errorRateClass(health.windows[name].error_rate)}">${name}</span>
+                <span class="probe-meta">${formatWindow(health.windows[name])}</span>
+            </li>`)
+        .join('');
+    const models = health.models.map(model => `
+            <li class="probe-item">
+                <span class="probe-badge ${errorRateClass(model.error_rate)}">15m</span>
+                <span class="probe-name">${escapeHtml(model.model)}</span>
+                <span class="probe-meta">${formatWindow(model)}</span>
+            </li>`).join('');
+    return `
+        <div class="probe-section">
+            <h3>👥 实测健康度</h3>
+            <ul class="probe-list">${windows}${models}</ul>
+        </div>`;
+}
+
+function createModelChangesSection(listing) {
+    const changes = listing.recent_changes.map(change => `
+            <li class="probe-item">
+                <span class="probe-badge ${change.kind === 'added' ? 'status-operational' : 'status-major-outage'}">
+                    ${change.kind === 'added' ? '新增' : '移除'}
+                </span>
+                <span class="probe-name">${escapeHtml(change.model)}</span>
+                <span class="probe-meta">${formatTimeAgo(change.at)}</span>
+            </li>`).join('');
+    return `
+        <div class="probe-section">
+            <h3>🧩 模型变更（当前 ${listing.model_count} 个）</h3>
+            <ul class="probe-list">${changes}</ul>
+        </div>`;
+}
+
+// 事件类别显示名称
+const incidentCategoryMap = {
+    'elevated_errors': '错误率升高',
+    'latency': '延迟',
+    'auth': '登录/认证',
+    'billing': '计费',
+    'api': 'API',
+    'consumer_app': '客户端应用',
+    'region': '区域'
+};
+
+function renderIncidentTags(classification) {
+    if (!classification) return '';
+    const tags = [
+        ...classification.categories.filter(c => c !== 'region').map(c => incidentCategoryMap[c] || c),
+        ...classification.regions.map(r => `🌐 ${r}`),
+        ...classification.models.map(m => `🧠 ${m}`)
+    ];
+    if (tags.length === 0) return '';
+    return `<span class="incident-tags">${tags.map(t => `<span class="incident-tag">${escapeHtml(t)}</span>`).join('')}</span>`;
+}
+
+// 网络检查结论映射
+const networkVerdictMap = {
+    "reachable": { class: "status-operational", text: "连通" },
+    "dns_failed": { class: "status-major-outage", text: "DNS 解析失败" },
+    "tcp_failed": { class: "status-major-outage", text: "TCP 连接失败" },
+    "tls_failed": { class: "status-major-outage", text: "TLS 握手失败" },
+    "http_failed": { class: "status-partial-outage", text: "HTTP 无响应" },
+};
+
+function createNetworkSection(checks) {
+    const items = checks.map(check => {
+        const info = networkVerdictMap[check.verdict] || statusMap.unknown;
+        const steps = [['DNS', check.dns], ['TCP', check.tcp], ['TLS', check.tls], ['HTTP', check.http]]
+            .filter(([, step]) => step)
+            .map(([name, step]) => `<span class="network-step ${step.ok ? 'step-ok' : 'step-failed'}" title="${escapeHtml(step.detail)}">${name} ${step.ms}ms</span>`)
+            .join('');
+        const cert = check.certificate ?
+            `<p class="probe-error">证书 ${check.certificate.days_left} 天后过期 · ${escapeHtml(check.certificate.issuer)}</p>` : '';
+        return `
+            <li class="probe-item">
+                <span class="probe-badge ${info.class}">${info.text}</span>
+                <span class="probe-name">${escapeHtml(check.host)}</span>
+                <span class="probe-meta">${formatTimeAgo(check.checked_at)}</span>
+                <p class="probe-stream">${steps}</p>
+                ${cert}
+            </li>`;
+    }).join('');
+    return `
+        <div class="probe-section">
+            <h3>🌐 本地网络连通性</h3>
+            <ul class="probe-list">${items}</ul>
+        </div>`;
+}
+
+function createProviderCard(providerName, providerData, meta = {}, probes = [], network = [], discrepancy = null, observedHealth = null, modelListing = null) {
     const card = document.createElement('div');
     card.classList.add('status-card');
     
     // 官方状态页面URL
-    const officialStatusUrl = providerStatusUrls[providerName] || '#';
+    const officialStatusUrl = providerStatusUrls[providerName] || safeUrl(meta.link) || '#';
     
     // 获取图标
-    const icon = providerIcons[providerName] || '📊';
+    const iconUrl = safeUrl(meta.icon);
+    const icon = providerIcons[providerName] ||
+        (iconUrl ? `<img src="${escapeHtml(iconUrl)}" class="provider-logo">` : '📊');
     
     // 卡片头部
     card.innerHTML = `
         <div class="card-header">
             <h2 class="provider-name-link">
-                <a href="${officialStatusUrl}" target="_blank" rel="noopener noreferrer">
-                    ${icon} ${providerName}
+                <a href="${escapeHtml(officialStatusUrl)}" target="_blank" rel="noopener noreferrer">
+                    ${icon} ${escapeHtml(providerName)}
                 </a>
             </h2>
         </div>
@@ -162,12 +346,42 @@ function createProviderCard(providerName, providerData) {
     
     const scrollableContent = card.querySelector('.card-content-scroll');
     
+    // 官方状态正常但实测错误率持续偏高
+    if (discrepancy) {
+        scrollableContent.innerHTML += `
+            <div class="discrepancy-note">
+                <p>🚨 ${escapeHtml(discrepancy.summary)}</p>
+            </div>`;
+    }
+    
+    // 最近 7 天的模型上下线
+    if (modelListing && modelListing.recent_changes.length > 0) {
+        scrollableContent.innerHTML += createModelChangesSection(modelListing);
+    }
+    
+    // 客户端上报的实测健康度
+    if (observedHealth) {
+        scrollableContent.innerHTML += createObservedSection(observedHealth);
+    }
+    
+    // 主动探测结果与官方状态并列展示，官方状态获取失败时同样显示
+    if (probes && probes.length > 0) {
+        scrollableContent.innerHTML += createProbeSection(probes);
+    }
+    
+    // 网络连通性单独展示，用于区分本地网络问题与供应商故障
+    if (network && network.length > 0) {
+        scrollableContent.innerHTML += createNetworkSection(network);
+    }
+    
     // 错误处理
     if (providerData.error) {
         scrollableContent.innerHTML += `
             <div class="error-section">
-                <p class="error-message">获取失败: ${providerData.error}</p>
-                ${providerName === 'Google' ? 
+                <p class="error-message">获取失败: ${escapeHtml(providerData.error)}</p>
+                ${providerData.status === 'unsupported' ?
+                    '<p class="error-hint">💡 提示：服务端构建时未启用 browser 特性，该供应商需要无头浏览器爬虫。</p>' :
+                    providerName === 'Google' ? 
                     '<p class="error-hint">💡 提示：可能是本地爬虫出现了问题，请等待自动更新或联系我。</p>' : 
                     ''}
             </div>`;
@@ -182,7 +396,7 @@ function createProviderCard(providerName, providerData) {
                 <p class="overall-status ${overallStatusInfo.class}">
                     总体状态: <span>${overallStatusInfo.text}</span>
                 </p>
-                <p class="status-description">${providerData.status.description}</p>
+                <p class="status-description">${escapeHtml(providerData.status.description)}</p>
             </div>`;
         
         // 组件状态
@@ -196,7 +410,7 @@ function createProviderCard(providerName, providerData) {
                 const statusInfo = getStatusInfo(component.status);
                 componentsHTML += `
                     <li>
-                        <span class="component-name">${component.name}</span>
+                        <span class="component-name">${escapeHtml(component.name)}</span>
                         <span class="component-status ${statusInfo.class}">${statusInfo.text}</span>
                     </li>`;
             });
@@ -215,8 +429,9 @@ function createProviderCard(providerName, providerData) {
             providerData.incidents.slice(0, 3).forEach(incident => {
                 incidentsHTML += `
                     <li>
-                        <a href="${incident.shortlink}" target="_blank">${incident.name}</a>
-                        <span class="incident-status">(${incident.status})</span>
+                        ${renderLink(incident.shortlink, incident.name)}
+                        <span class="incident-status">(${escapeHtml(incident.status)})</span>
+                        ${renderIncidentTags(incident.classification)}
                     </li>`;
             });
             
@@ -234,8 +449,8 @@ function createProviderCard(providerName, providerData) {
             providerData.scheduled_maintenances.slice(0, 3).forEach(maintenance => {
                 maintenancesHTML += `
                     <li>
-                        <a href="${maintenance.shortlink}" target="_blank">${maintenance.name}</a>
-                        <span class="maintenance-status">(${maintenance.status})</span>
+                        ${renderLink(maintenance.shortlink, maintenance.name)}
+                        <span class="maintenance-status">(${escapeHtml(maintenance.status)})</span>
                     </li>`;
             });
             
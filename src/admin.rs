@@ -0,0 +1,66 @@
+use crate::auth;
+use crate::config::CONFIG;
+use crate::{diagnostics, selectors};
+use axum::{
+    extract::Path,
+    http::{HeaderMap, StatusCode, header},
+    response::{IntoResponse, Json, Response},
+    routing::{get, post},
+    Router,
+};
+use std::fs;
+
+/// 管理接口路由
+pub fn routes() -> Router {
+    Router::new()
+        .route("/api/admin/diagnostics", get(serve_diagnostics))
+        .route("/api/admin/diagnostics/:id/:file", get(serve_diagnostics_file))
+        .route("/api/admin/selectors", get(serve_selectors))
+        .route("/api/admin/selectors/reload", post(reload_selectors))
+}
+
+/// 校验管理接口令牌，未配置令牌时管理接口关闭
+fn check_admin(headers: &HeaderMap) -> Result<(), StatusCode> {
+    auth::require_bearer(headers, CONFIG.admin_token())
+}
+
+/// 提供最新的爬虫自检报告与快照列表
+async fn serve_diagnostics(headers: HeaderMap) -> Result<Json<serde_json::Value>, StatusCode> {
+    check_admin(&headers)?;
+    Ok(Json(serde_json::json!({
+        "latest": diagnostics::latest_reports(),
+        "snapshots": diagnostics::list_snapshots(),
+        "files": diagnostics::SNAPSHOT_FILES,
+    })))
+}
+
+/// 提供快照中的单个文件（页面源码以纯文本返回，避免在管理端执行脚本）
+async fn serve_diagnostics_file(
+    headers: HeaderMap,
+    Path((id, file)): Path<(String, String)>,
+) -> Result<Response, StatusCode> {
+    check_admin(&headers)?;
+    let path = diagnostics::snapshot_file(&id, &file).ok_or(StatusCode::NOT_FOUND)?;
+    let content = fs::read(path).map_err(|_| StatusCode::NOT_FOUND)?;
+    let content_type = match file.rsplit('.').next() {
+        Some("png") => "image/png",
+        Some("json") => "application/json",
+        _ => "text/plain; charset=utf-8",
+    };
+    Ok(([(header::CONTENT_TYPE, content_type)], content).into_response())
+}
+
+/// 提供当前生效的选择器
+async fn serve_selectors(headers: HeaderMap) -> Result<Json<serde_json::Value>, StatusCode> {
+    check_admin(&headers)?;
+    Ok(Json(serde_json::json!(*selectors::current())))
+}
+
+/// 立即重新加载选择器文件
+async fn reload_selectors(headers: HeaderMap) -> Result<Json<serde_json::Value>, (StatusCode, String)> {
+    check_admin(&headers).map_err(|code| (code, String::new()))?;
+    match selectors::reload() {
+        Ok(selectors) => Ok(Json(serde_json::json!({ "version": selectors.version }))),
+        Err(e) => Err((StatusCode::UNPROCESSABLE_ENTITY, e)),
+    }
+}
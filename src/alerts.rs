@@ -0,0 +1,40 @@
+//! 告警 webhook
+//!
+//! 向配置的 webhook 地址发送 JSON 告警。负载中的 `text` 字段兼容 Slack 等常见的 incoming webhook，
+//! 其余字段供自定义接收端使用。
+
+use crate::sources::expand_env;
+use reqwest::{Client, ClientBuilder};
+use std::sync::LazyLock;
+use std::time::Duration;
+use tracing::warn;
+
+/// 发送告警使用的 HTTP 客户端
+static CLIENT: LazyLock<Option<Client>> = LazyLock::new(|| {
+    ClientBuilder::new()
+        .timeout(Duration::from_secs(10))
+        .use_rustls_tls()
+        .build()
+        .map_err(|e| warn!("⚠️ 创建告警客户端失败: {}", e))
+        .ok()
+});
+
+/// 向每个 webhook 发送告警，地址支持 `${VAR}` 引用环境变量
+pub async fn send(webhooks: &[String], event: &str, text: &str, details: serde_json::Value) {
+    let Some(client) = CLIENT.as_ref() else {
+        return;
+    };
+    let mut payload = serde_json::json!({ "event": event, "text": text });
+    if let (Some(payload), serde_json::Value::Object(details)) = (payload.as_object_mut(), details) {
+        payload.extend(details);
+    }
+
+    for webhook in webhooks {
+        let url = expand_env(webhook);
+        match client.post(&url).json(&payload).send().await {
+            Ok(response) if response.status().is_success() => {}
+            Ok(response) => warn!("⚠️ 告警 webhook 返回 HTTP {}", response.status().as_u16()),
+            Err(e) => warn!("⚠️ 发送告警 webhook 失败: {}", e),
+        }
+    }
+}
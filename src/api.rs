@@ -0,0 +1,128 @@
+use crate::auth;
+use crate::classification;
+use crate::config::CONFIG;
+use crate::discrepancy;
+use crate::discovery;
+use crate::model_catalog;
+use crate::model_listing;
+use crate::netcheck;
+use crate::observations;
+use crate::probes;
+use axum::{
+    extract::{Path, Query},
+    http::{HeaderMap, StatusCode},
+    response::Json,
+    routing::get,
+    Router,
+};
+use chrono::Utc;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+
+/// `/api/v1` 接口路由
+pub fn routes() -> Router {
+    Router::new()
+        .route("/api/v1/discover", get(discover))
+        .route("/api/v1/probes", get(probe_results))
+        .route("/api/v1/probes/:id/history", get(probe_history))
+        .route("/api/v1/network", get(network_results))
+        .route("/api/v1/discrepancies", get(discrepancies))
+        .route("/api/v1/observations", get(observed_health).post(ingest_observations))
+        .route("/api/v1/model-listings", get(model_listings))
+        .route("/api/v1/model-listings/events", get(model_events))
+        .route("/api/v1/models", get(model_statuses))
+        .route("/api/v1/models/:id/status", get(model_status))
+        .route("/api/v1/incidents", get(incidents))
+}
+
+/// 主动探测的最近结果
+async fn probe_results() -> Json<Vec<probes::ProbeResult>> {
+    Json(probes::latest_results())
+}
+
+/// 流式探测的历史样本
+async fn probe_history(Path(id): Path<String>) -> Result<Json<Vec<probes::StreamSample>>, StatusCode> {
+    probes::stream_history(&id).map(Json).ok_or(StatusCode::NOT_FOUND)
+}
+
+#[derive(Deserialize)]
+struct DiscoverQuery {
+    url: String,
+}
+
+/// 识别状态页面平台并返回供应商配置
+///
+/// 该接口会向任意地址发起请求，因此需要管理接口令牌。
+async fn discover(
+    headers: HeaderMap,
+    Query(query): Query<DiscoverQuery>,
+) -> Result<Json<discovery::Discovery>, (StatusCode, String)> {
+    auth::require_bearer(&headers, CONFIG.admin_token()).map_err(|code| (code, String::new()))?;
+    discovery::discover(&query.url)
+        .await
+        .map(Json)
+        .map_err(|e| (StatusCode::BAD_REQUEST, e.to_string()))
+}
+
+/// 网络连通性检查的最近结果
+async fn network_results() -> Json<Vec<netcheck::NetCheck>> {
+    Json(netcheck::latest_results())
+}
+
+/// 官方状态正常但实测错误率持续偏高的供应商
+async fn discrepancies() -> Json<Vec<discrepancy::Discrepancy>> {
+    Json(discrepancy::active())
+}
+
+/// 接收客户端批量上报的请求结果
+///
+/// 使用独立的上报令牌，网关无需持有管理接口令牌。
+async fn ingest_observations(
+    headers: HeaderMap,
+    Json(batch): Json<observations::ObservationBatch>,
+) -> Result<Json<observations::IngestResult>, (StatusCode, String)> {
+    auth::require_bearer(&headers, CONFIG.ingest_token()).map_err(|code| (code, String::new()))?;
+    if batch.observations.len() > observations::MAX_BATCH {
+        return Err((
+            StatusCode::PAYLOAD_TOO_LARGE,
+            format!("单次最多上报 {} 条", observations::MAX_BATCH),
+        ));
+    }
+    Ok(Json(observations::ingest(batch, Utc::now())))
+}
+
+/// 各供应商的实测健康度
+async fn observed_health() -> Json<Vec<observations::ObservedHealth>> {
+    Json(observations::observed_health(Utc::now()))
+}
+
+/// 各供应商当前的模型列表
+async fn model_listings() -> Json<BTreeMap<String, model_listing::ProviderModels>> {
+    Json(model_listing::current())
+}
+
+#[derive(Deserialize)]
+struct EventsQuery {
+    provider: Option<String>,
+    limit: Option<usize>,
+}
+
+/// 最近的模型上下线事件
+async fn model_events(Query(query): Query<EventsQuery>) -> Json<Vec<model_listing::ModelEvent>> {
+    Json(model_listing::events(query.provider.as_deref(), query.limit.unwrap_or(100).min(500)))
+}
+
+/// 模型目录中全部模型的有效状态
+async fn model_statuses() -> Json<Vec<model_catalog::ModelStatus>> {
+    Json(model_catalog::all_statuses())
+}
+
+/// 单个模型的有效状态与相关事件
+async fn model_status(Path(id): Path<String>) -> Result<Json<model_catalog::ModelStatus>, StatusCode> {
+    model_catalog::status(&id).map(Json).ok_or(StatusCode::NOT_FOUND)
+}
+
+/// 按供应商、类别、区域、模型筛选事件
+async fn incidents(Query(filter): Query<classification::IncidentFilter>) -> Json<Vec<classification::ProviderIncident>> {
+    Json(classification::incidents(&filter))
+}
@@ -0,0 +1,47 @@
+use axum::http::{header, HeaderMap, StatusCode};
+
+/// 校验 `Authorization: Bearer <令牌>`
+///
+/// 未配置令牌时返回 404，使对应接口看起来不存在；令牌错误时返回 401。
+pub fn require_bearer(headers: &HeaderMap, expected: Option<String>) -> Result<(), StatusCode> {
+    let token = expected.ok_or(StatusCode::NOT_FOUND)?;
+    let provided = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "));
+    if provided.is_some_and(|v| constant_time_eq(v.as_bytes(), token.as_bytes())) {
+        Ok(())
+    } else {
+        Err(StatusCode::UNAUTHORIZED)
+    }
+}
+
+/// 逐字节异或后再汇总，比较耗时与令牌在哪一位出现差异无关
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::HeaderValue;
+
+    fn headers(value: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(header::AUTHORIZATION, HeaderValue::from_str(value).unwrap());
+        headers
+    }
+
+    #[test]
+    fn bearer_token_must_match_exactly() {
+        let token = Some("secret".to_string());
+        assert_eq!(require_bearer(&headers("Bearer secret"), token.clone()), Ok(()));
+        assert_eq!(require_bearer(&headers("Bearer secreT"), token.clone()), Err(StatusCode::UNAUTHORIZED));
+        assert_eq!(require_bearer(&headers("Bearer secret2"), token.clone()), Err(StatusCode::UNAUTHORIZED));
+        assert_eq!(require_bearer(&headers("secret"), token), Err(StatusCode::UNAUTHORIZED));
+        assert_eq!(require_bearer(&headers("Bearer secret"), None), Err(StatusCode::NOT_FOUND));
+    }
+}
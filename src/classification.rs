@@ -0,0 +1,371 @@
+//! 事件分类：按可配置的关键字/正则词典为事件打上类别、区域与模型标签
+//!
+//! 状态页面的事件更新是自由文本，这里在每轮刷新时对事件标题、更新内容与受影响组件名称
+//! 做规则匹配，结果保存在事件的 `classification` 中，接口可以按类别、区域与模型过滤。
+//! 模型目录中的模型会自动加入模型词典。
+
+use crate::config::CONFIG;
+use crate::fetcher;
+use crate::model_catalog::{self, ModelEntry};
+use crate::sources::mentions;
+use crate::models::{Incident, IncidentClassification, IncidentStatus, StatusPageSummary};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use tracing::{error, warn};
+
+/// 识别到区域时额外附加的类别
+const REGION_CATEGORY: &str = "region";
+
+/// 规则文件结构
+#[derive(Debug, Default, Deserialize)]
+pub struct RulesFile {
+    #[serde(default)]
+    pub categories: Vec<Rule>,
+    #[serde(default)]
+    pub regions: Vec<Rule>,
+    #[serde(default)]
+    pub models: Vec<Rule>,
+    /// 匹配到的文本本身作为模型名称的正则，如 `(?i)\bgpt-[0-9][\w.-]*`
+    #[serde(default)]
+    pub model_patterns: Vec<String>,
+}
+
+/// 单条规则，任一关键字或正则命中即打上 `tag`
+#[derive(Debug, Clone, Deserialize)]
+pub struct Rule {
+    pub tag: String,
+    /// 不区分大小写的关键字；类别与区域按子串匹配，模型按词匹配
+    #[serde(default)]
+    pub keywords: Vec<String>,
+    /// 正则表达式，区分大小写（可用 `(?i)` 关闭）
+    #[serde(default)]
+    pub patterns: Vec<String>,
+}
+
+/// 编译后的规则
+struct CompiledRule {
+    tag: String,
+    keywords: Vec<String>,
+    patterns: Vec<Regex>,
+}
+
+impl CompiledRule {
+    fn new(rule: &Rule) -> Self {
+        Self {
+            tag: rule.tag.clone(),
+            keywords: rule.keywords.iter().map(|k| k.to_lowercase()).filter(|k| !k.is_empty()).collect(),
+            patterns: rule.patterns.iter().filter_map(|p| compile(p)).collect(),
+        }
+    }
+
+    /// ASCII 关键词按整词匹配（`terror` 不会命中 `error`）；含中文等非 ASCII 字符的关键词没有词边界，
+    /// 类别与区域仍按子串匹配，模型始终按整词匹配
+    fn matches(&self, text: &str, lower: &str, whole_word: bool) -> bool {
+        self.keywords
+            .iter()
+            .any(|k| if whole_word || k.is_ascii() { mentions(lower, k) } else { lower.contains(k.as_str()) })
+            || self.patterns.iter().any(|p| p.is_match(text))
+    }
+}
+
+fn compile(pattern: &str) -> Option<Regex> {
+    Regex::new(pattern)
+        .map_err(|e| warn!("⚠️ 事件分类规则中的正则无效，已忽略 {}: {}", pattern, e))
+        .ok()
+}
+
+/// 事件分类器
+pub struct Classifier {
+    categories: Vec<CompiledRule>,
+    regions: Vec<CompiledRule>,
+    models: Vec<CompiledRule>,
+    model_patterns: Vec<Regex>,
+}
+
+impl Classifier {
+    /// 由规则与模型目录构建分类器
+    pub fn new(rules: &RulesFile, catalog: &[ModelEntry]) -> Self {
+        let catalog_rules = catalog.iter().map(|entry| Rule {
+            tag: entry.id.clone(),
+            keywords: entry.terms().map(str::to_string).collect(),
+            patterns: Vec::new(),
+        });
+        Self {
+            categories: rules.categories.iter().map(CompiledRule::new).collect(),
+            regions: rules.regions.iter().map(CompiledRule::new).collect(),
+            models: rules.models.iter().cloned().chain(catalog_rules).map(|r| CompiledRule::new(&r)).collect(),
+            model_patterns: rules.model_patterns.iter().filter_map(|p| compile(p)).collect(),
+        }
+    }
+
+    /// 读取 `config/incident_rules.json` 与模型目录，每轮刷新时调用
+    pub fn load() -> Self {
+        let rules = match fs::read_to_string(CONFIG.incident_rules_file) {
+            Ok(content) => serde_json::from_str(&content).unwrap_or_else(|e| {
+                error!("❌ 解析事件分类规则 {} 失败: {}", CONFIG.incident_rules_file, e);
+                RulesFile::default()
+            }),
+            Err(_) => RulesFile::default(),
+        };
+        Self::new(&rules, &model_catalog::load_catalog())
+    }
+
+    fn is_empty(&self) -> bool {
+        self.categories.is_empty() && self.regions.is_empty() && self.models.is_empty() && self.model_patterns.is_empty()
+    }
+
+    /// 对单个事件分类
+    pub fn classify(&self, incident: &Incident) -> IncidentClassification {
+        let text = incident_text(incident);
+        let lower = text.to_lowercase();
+
+        let tags = |rules: &[CompiledRule], whole_word: bool| -> Vec<String> {
+            let mut tags: Vec<String> = Vec::new();
+            for rule in rules.iter().filter(|r| r.matches(&text, &lower, whole_word)) {
+                push_unique(&mut tags, rule.tag.clone());
+            }
+            tags
+        };
+
+        let mut categories = tags(&self.categories, false);
+        let regions = tags(&self.regions, false);
+        if !regions.is_empty() {
+            push_unique(&mut categories, REGION_CATEGORY.to_string());
+        }
+
+        let mut models = tags(&self.models, true);
+        for pattern in &self.model_patterns {
+            for found in pattern.find_iter(&text) {
+                let name = model_name(found.as_str());
+                if !name.is_empty() {
+                    push_unique(&mut models, name);
+                }
+            }
+        }
+
+        IncidentClassification { categories, regions, models }
+    }
+
+    /// 为摘要中的全部事件分类，未配置任何规则时不做处理
+    pub fn apply(&self, summary: &mut StatusPageSummary) {
+        if self.is_empty() {
+            return;
+        }
+        for incident in &mut summary.incidents {
+            incident.classification = Some(self.classify(incident));
+        }
+    }
+}
+
+/// 参与匹配的文本：标题、各次更新内容与受影响组件名称
+fn incident_text(incident: &Incident) -> String {
+    let mut parts = vec![incident.name.as_str()];
+    for update in &incident.incident_updates {
+        parts.push(update.body.as_str());
+        let components = update.affected_components.iter().flatten().filter_map(|c| c.get("name"));
+        parts.extend(components.map(String::as_str));
+    }
+    parts.join("\n")
+}
+
+/// 规范化正则提取到的模型名称：小写，空白折叠为 `-`，去掉末尾标点
+fn model_name(raw: &str) -> String {
+    let name = raw.split_whitespace().collect::<Vec<_>>().join("-").to_lowercase();
+    name.trim_end_matches(['.', '-', '_']).to_string()
+}
+
+fn push_unique(values: &mut Vec<String>, value: String) {
+    if !values.contains(&value) {
+        values.push(value);
+    }
+}
+
+/// 判断分类结果是否包含指定标签（不区分大小写）
+pub fn has_tag(tags: &[String], wanted: &str) -> bool {
+    tags.iter().any(|t| t.eq_ignore_ascii_case(wanted))
+}
+
+/// 事件查询条件，均不区分大小写
+#[derive(Debug, Default, Deserialize)]
+pub struct IncidentFilter {
+    pub provider: Option<String>,
+    pub category: Option<String>,
+    pub region: Option<String>,
+    pub model: Option<String>,
+    /// 只返回未结束的事件
+    #[serde(default)]
+    pub active: bool,
+}
+
+impl IncidentFilter {
+    fn matches(&self, provider: &str, incident: &Incident) -> bool {
+        if self.provider.as_deref().is_some_and(|p| !p.eq_ignore_ascii_case(provider)) {
+            return false;
+        }
+        if self.active && incident.status == IncidentStatus::Resolved {
+            return false;
+        }
+        let empty = IncidentClassification::default();
+        let classification = incident.classification.as_ref().unwrap_or(&empty);
+        [
+            (&self.category, &classification.categories),
+            (&self.region, &classification.regions),
+            (&self.model, &classification.models),
+        ]
+        .iter()
+        .all(|(wanted, tags)| wanted.as_deref().is_none_or(|wanted| has_tag(tags, wanted)))
+    }
+}
+
+/// 带供应商名称的事件
+#[derive(Debug, Clone, Serialize)]
+pub struct ProviderIncident {
+    pub provider: String,
+    #[serde(flatten)]
+    pub incident: Incident,
+}
+
+/// 按条件筛选最近一轮刷新中的事件，按更新时间倒序
+pub fn incidents(filter: &IncidentFilter) -> Vec<ProviderIncident> {
+    let mut incidents: Vec<ProviderIncident> = fetcher::latest_statuses()
+        .into_iter()
+        .filter_map(|(provider, value)| Some((provider, serde_json::from_value::<StatusPageSummary>(value).ok()?)))
+        .flat_map(|(provider, summary)| {
+            summary.incidents.into_iter().map(move |incident| ProviderIncident { provider: provider.clone(), incident })
+        })
+        .filter(|item| filter.matches(&item.provider, &item.incident))
+        .collect();
+    incidents.sort_by(|a, b| b.incident.updated_at.cmp(&a.incident.updated_at));
+    incidents
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rules() -> RulesFile {
+        serde_json::from_value(serde_json::json!({
+            "categories": [
+                { "tag": "elevated_errors", "keywords": ["error", "errors", "报错"] },
+                { "tag": "latency", "keywords": ["latency", "slow", "延迟"] },
+                { "tag": "auth", "keywords": ["login", "登录"], "patterns": ["(?i)\\bauth(entication)?\\b"] },
+                { "tag": "api", "patterns": ["(?i)\\bapi\\b"] },
+                { "tag": "consumer_app", "keywords": ["chatgpt", "claude.ai"] }
+            ],
+            "regions": [
+                { "tag": "eu", "keywords": ["europe"], "patterns": ["\\bEU\\b", "\\beu-(west|central)"] }
+            ],
+            "models": [
+                { "tag": "o3", "keywords": ["o3"] }
+            ],
+            "model_patterns": ["(?i)\\bgpt-[0-9][\\w.-]*", "(?i)\\bclaude[- ](?:opus|sonnet|haiku)[- ][0-9.]+"]
+        }))
+        .unwrap()
+    }
+
+    fn incident(name: &str, body: &str, component: Option<&str>) -> Incident {
+        serde_json::from_value(serde_json::json!({
+            "id": "inc", "name": name, "status": "investigating", "created_at": "", "updated_at": "",
+            "impact": "minor", "incident_updates": [{
+                "id": "u1", "status": "investigating", "body": body,
+                "affected_components": component.map(|c| serde_json::json!([{ "name": c }]))
+            }]
+        }))
+        .unwrap()
+    }
+
+    fn catalog() -> Vec<ModelEntry> {
+        serde_json::from_value(serde_json::json!([
+            { "id": "deepseek-v3", "name": "DeepSeek-V3", "provider": "DeepSeek", "aliases": ["deepseek-chat"] }
+        ]))
+        .unwrap()
+    }
+
+    #[test]
+    fn tags_categories_regions_and_models() {
+        let classifier = Classifier::new(&rules(), &catalog());
+        let result = classifier.classify(&incident(
+            "Elevated errors for GPT-4o and Claude Sonnet 4.",
+            "Customers in eu-west may see slow responses from the API.",
+            Some("ChatGPT"),
+        ));
+        assert_eq!(result.categories, ["elevated_errors", "latency", "api", "consumer_app", "region"]);
+        assert_eq!(result.regions, ["eu"]);
+        assert_eq!(result.models, ["gpt-4o", "claude-sonnet-4"]);
+    }
+
+    #[test]
+    fn keywords_and_catalog_models_respect_boundaries() {
+        let classifier = Classifier::new(&rules(), &catalog());
+        let result = classifier.classify(&incident("deepseek-chat 接口报错", "Authors of o3-mini reports: issue with o3.", None));
+        assert_eq!(result.categories, ["elevated_errors"]);
+        assert_eq!(result.models, ["o3", "deepseek-v3"]);
+        assert!(result.regions.is_empty());
+
+        let result = classifier.classify(&incident("Login failures", "Authentication is failing", None));
+        assert_eq!(result.categories, ["auth"]);
+        assert!(result.models.is_empty());
+    }
+
+    #[test]
+    fn shipped_ascii_keywords_match_whole_words() {
+        let rules: RulesFile = serde_json::from_str(include_str!("../config/incident_rules.json")).unwrap();
+        let classifier = Classifier::new(&rules, &[]);
+        let result = classifier.classify(&incident("New design in the settings page", "A terror-themed banner was removed.", None));
+        assert!(result.categories.is_empty());
+        assert!(result.regions.is_empty());
+
+        let result = classifier.classify(&incident("Users unable to sign in", "Elevated errors and timeouts 登录失败", None));
+        assert_eq!(result.categories, ["elevated_errors", "latency", "auth"]);
+    }
+
+    #[test]
+    fn apply_skips_when_no_rules() {
+        let mut summary: StatusPageSummary = serde_json::from_value(serde_json::json!({
+            "page": { "id": "p", "name": "P", "url": "", "updated_at": "" },
+            "components": [],
+            "incidents": [serde_json::to_value(incident("Elevated errors", "", None)).unwrap()],
+            "status": { "indicator": "minor", "description": "" }
+        }))
+        .unwrap();
+        Classifier::new(&RulesFile::default(), &[]).apply(&mut summary);
+        assert!(summary.incidents[0].classification.is_none());
+
+        Classifier::new(&rules(), &[]).apply(&mut summary);
+        let classification = summary.incidents[0].classification.as_ref().unwrap();
+        assert!(has_tag(&classification.categories, "ELEVATED_ERRORS"));
+    }
+
+    #[test]
+    fn filter_by_tags_and_status() {
+        let mut classified = incident("Elevated errors", "", None);
+        classified.classification = Some(IncidentClassification {
+            categories: vec!["elevated_errors".to_string()],
+            regions: Vec::new(),
+            models: vec!["gpt-4o".to_string()],
+        });
+        let filter = |value: serde_json::Value| serde_json::from_value::<IncidentFilter>(value).unwrap();
+
+        assert!(filter(serde_json::json!({ "model": "GPT-4o", "provider": "openai" })).matches("OpenAI", &classified));
+        assert!(!filter(serde_json::json!({ "provider": "Anthropic" })).matches("OpenAI", &classified));
+        assert!(!filter(serde_json::json!({ "category": "latency" })).matches("OpenAI", &classified));
+        assert!(!filter(serde_json::json!({ "region": "eu" })).matches("OpenAI", &classified));
+        assert!(!filter(serde_json::json!({ "category": "latency" })).matches("OpenAI", &incident("x", "", None)));
+
+        classified.status = IncidentStatus::Resolved;
+        assert!(filter(serde_json::json!({})).matches("OpenAI", &classified));
+        assert!(!filter(serde_json::json!({ "active": true })).matches("OpenAI", &classified));
+    }
+
+    #[test]
+    fn invalid_patterns_are_ignored() {
+        let rules: RulesFile = serde_json::from_value(serde_json::json!({
+            "categories": [{ "tag": "billing", "keywords": ["invoice"], "patterns": ["(unclosed"] }],
+            "model_patterns": ["[bad"]
+        }))
+        .unwrap();
+        let result = Classifier::new(&rules, &[]).classify(&incident("Invoice delays", "", None));
+        assert_eq!(result.categories, ["billing"]);
+    }
+}
@@ -0,0 +1,175 @@
+use crate::config::CONFIG;
+use std::collections::HashMap;
+use std::fs;
+use std::sync::{Arc, LazyLock, RwLock};
+use std::time::SystemTime;
+use tracing::{info, warn};
+
+/// 组件别名表：供应商ID -> (页面上的当前名称 -> 生成ID所用的名称)
+///
+/// 服务改名后把新名称映射回旧名称，即可保持组件ID不变。
+type Aliases = HashMap<String, HashMap<String, String>>;
+
+/// 当前别名表及别名文件的修改时间，文件修改后自动重新加载
+static ALIASES: LazyLock<RwLock<(Option<SystemTime>, Arc<Aliases>)>> =
+    LazyLock::new(|| RwLock::new((file_modified(), Arc::new(load_aliases()))));
+
+/// 规范化名称：小写，字母数字以外的字符折叠为单个 `-`
+pub fn normalize(name: &str) -> String {
+    let mut slug = String::with_capacity(name.len());
+    for c in name.trim().chars().flat_map(char::to_lowercase) {
+        if c.is_alphanumeric() {
+            slug.push(c);
+        } else if !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+    while slug.ends_with('-') {
+        slug.pop();
+    }
+    slug
+}
+
+/// 为一组组件名称生成确定性的ID，名称不重复时ID只取决于名称而不受排列顺序影响
+///
+/// 重名组件无法仅凭名称区分，按出现顺序依次追加 `-2`、`-3` 后缀，
+/// 因此页面调整重名组件的先后顺序时它们的ID会互换（别名按名称生效，同样无法区分它们）。
+pub fn assign_ids<'a>(provider_id: &str, names: impl IntoIterator<Item = &'a str>) -> Vec<String> {
+    let aliases = current_aliases();
+    assign_ids_with(provider_id, aliases.get(provider_id), names)
+}
+
+fn assign_ids_with<'a>(
+    provider_id: &str,
+    aliases: Option<&HashMap<String, String>>,
+    names: impl IntoIterator<Item = &'a str>,
+) -> Vec<String> {
+    let mut seen: HashMap<String, u32> = HashMap::new();
+
+    names
+        .into_iter()
+        .map(|name| {
+            let normalized = normalize(name);
+            let canonical = aliases
+                .and_then(|table| table.get(&normalized))
+                .cloned()
+                .unwrap_or(normalized);
+            let base = if canonical.is_empty() {
+                format!("{}-unnamed", provider_id)
+            } else {
+                format!("{}-{}", provider_id, canonical)
+            };
+
+            let count = seen.entry(base.clone()).or_insert(0);
+            *count += 1;
+            if *count == 1 { base } else { format!("{}-{}", base, count) }
+        })
+        .collect()
+}
+
+/// 获取当前别名表，文件被修改时重新加载
+fn current_aliases() -> Arc<Aliases> {
+    let modified = file_modified();
+    {
+        let loaded = ALIASES.read().unwrap();
+        if loaded.0 == modified {
+            return loaded.1.clone();
+        }
+    }
+    let aliases = Arc::new(load_aliases());
+    *ALIASES.write().unwrap() = (modified, aliases.clone());
+    aliases
+}
+
+fn file_modified() -> Option<SystemTime> {
+    fs::metadata(CONFIG.component_aliases_file).and_then(|m| m.modified()).ok()
+}
+
+/// 由若干文本派生稳定的64位哈希（FNV-1a），用于需要跨运行保持一致的事件ID
+///
+/// 标准库的 `DefaultHasher` 不保证不同 Rust 版本间输出一致，不能用于持久化或跨次比较的ID。
+pub fn stable_hash<'a>(parts: impl IntoIterator<Item = &'a str>) -> u64 {
+    const OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0100_0000_01b3;
+    let mut hash = OFFSET;
+    for part in parts {
+        // 0xff 不会出现在 UTF-8 文本中，作为分隔符避免 ["ab", ""] 与 ["a", "b"] 冲突
+        for byte in part.bytes().chain(std::iter::once(0xff)) {
+            hash ^= u64::from(byte);
+            hash = hash.wrapping_mul(PRIME);
+        }
+    }
+    hash
+}
+
+fn load_aliases() -> Aliases {
+    let content = match fs::read_to_string(CONFIG.component_aliases_file) {
+        Ok(content) => content,
+        Err(_) => {
+            info!("ℹ️ 未找到组件别名文件 {}，不使用别名", CONFIG.component_aliases_file);
+            return HashMap::new();
+        }
+    };
+
+    match serde_json::from_str::<HashMap<String, HashMap<String, String>>>(&content) {
+        Ok(raw) => raw
+            .into_iter()
+            .map(|(provider, table)| {
+                let table = table
+                    .into_iter()
+                    .map(|(from, to)| (normalize(&from), normalize(&to)))
+                    .collect();
+                (provider, table)
+            })
+            .collect(),
+        Err(e) => {
+            warn!("⚠️ 解析组件别名文件 {} 失败: {}", CONFIG.component_aliases_file, e);
+            HashMap::new()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalize_collapses_punctuation() {
+        assert_eq!(normalize("  Chat Completions "), "chat-completions");
+        assert_eq!(normalize("Claude API (api.anthropic.com)"), "claude-api-api-anthropic-com");
+        assert_eq!(normalize("--Gemini 2.5 Pro!!"), "gemini-2-5-pro");
+        assert_eq!(normalize("API 服务"), "api-服务");
+        assert_eq!(normalize("***"), "");
+    }
+
+    #[test]
+    fn ids_do_not_depend_on_order_of_distinct_names() {
+        let forward = assign_ids_with("openai", None, ["Chat Completions", "Responses"]);
+        let reversed = assign_ids_with("openai", None, ["Responses", "Chat Completions"]);
+        assert_eq!(forward, ["openai-chat-completions", "openai-responses"]);
+        assert_eq!(reversed, ["openai-responses", "openai-chat-completions"]);
+    }
+
+    #[test]
+    fn duplicates_and_empty_names() {
+        let ids = assign_ids_with("p", None, ["API", "api", "", "!!"]);
+        assert_eq!(ids, ["p-api", "p-api-2", "p-unnamed", "p-unnamed-2"]);
+    }
+
+    #[test]
+    fn aliases_keep_old_ids() {
+        let aliases = HashMap::from([("gemini-api".to_string(), "generative-language-api".to_string())]);
+        let ids = assign_ids_with("google", Some(&aliases), ["Gemini API", "AI Studio"]);
+        assert_eq!(ids, ["google-generative-language-api", "google-ai-studio"]);
+    }
+
+    #[test]
+    fn stable_hash_is_fixed() {
+        assert_eq!(stable_hash(["a"]), 0x089b_c907_b544_c769);
+        assert_eq!(
+            stable_hash(["Gemini API", "Elevated error rates", "Jun 10, 2025, 9:15 AM – 11:20 AM PDT"]),
+            0x5a42_bc82_5c48_b3ef
+        );
+        assert_ne!(stable_hash(["ab", ""]), stable_hash(["a", "b"]));
+    }
+}
@@ -12,17 +12,65 @@ pub struct Config {
     pub refresh_interval_secs: u64,
     /// Web服务器绑定地址
     pub server_bind_addr: &'static str,
+    /// 爬虫诊断快照目录
+    #[cfg(feature = "browser")]
+    pub diagnostics_dir: &'static str,
+    /// 保留的诊断快照数量
+    #[cfg(feature = "browser")]
+    pub diagnostics_keep: usize,
+    /// Google 状态页面选择器文件
+    #[cfg(feature = "browser")]
+    pub selectors_file: &'static str,
+    /// 组件别名文件（服务改名后保持组件ID不变）
+    pub component_aliases_file: &'static str,
+    /// 自定义供应商配置文件
+    pub providers_file: &'static str,
+    /// 管理接口令牌所在的环境变量（未设置时管理接口关闭）
+    pub admin_token_env: &'static str,
+    /// 实测数据上报令牌所在的环境变量（未设置时上报接口关闭）
+    pub ingest_token_env: &'static str,
+    /// 主动探测配置文件
+    pub probes_file: &'static str,
+    /// 网络连通性检查配置文件
+    pub netcheck_file: &'static str,
+    /// 官方状态与实测差异检测配置文件
+    pub discrepancy_file: &'static str,
+    /// 模型列表追踪配置文件
+    pub model_listings_file: &'static str,
+    /// 模型目录（模型到供应商组件的映射）
+    pub model_catalog_file: &'static str,
+    /// 事件分类规则文件
+    pub incident_rules_file: &'static str,
+    /// 运行数据目录（模型列表等需要跨重启保存的数据）
+    pub data_dir: &'static str,
 }
 
 impl Config {
     /// 获取默认配置
-    pub fn default() -> Self {
+    pub const fn default() -> Self {
         Self {
             status_file: "frontend/status.json",
             frontend_port: 5959,
             frontend_dir: "frontend",
             refresh_interval_secs: 300,
             server_bind_addr: "0.0.0.0",
+            #[cfg(feature = "browser")]
+            diagnostics_dir: "diagnostics",
+            #[cfg(feature = "browser")]
+            diagnostics_keep: 20,
+            #[cfg(feature = "browser")]
+            selectors_file: "config/google_selectors.json",
+            component_aliases_file: "config/component_aliases.json",
+            providers_file: "config/providers.json",
+            admin_token_env: "LMSD_ADMIN_TOKEN",
+            ingest_token_env: "LMSD_INGEST_TOKEN",
+            probes_file: "config/probes.json",
+            netcheck_file: "config/netcheck.json",
+            discrepancy_file: "config/discrepancy.json",
+            model_listings_file: "config/model_listings.json",
+            model_catalog_file: "config/model_catalog.json",
+            incident_rules_file: "config/incident_rules.json",
+            data_dir: "data",
         }
     }
     
@@ -36,6 +84,16 @@ impl Config {
         format!("http://localhost:{}", self.frontend_port)
     }
     
+    /// 获取管理接口令牌，未配置时返回None
+    pub fn admin_token(&self) -> Option<String> {
+        std::env::var(self.admin_token_env).ok().filter(|t| !t.is_empty())
+    }
+    
+    /// 获取实测数据上报令牌，未配置时返回None
+    pub fn ingest_token(&self) -> Option<String> {
+        std::env::var(self.ingest_token_env).ok().filter(|t| !t.is_empty())
+    }
+    
     /// 获取刷新间隔Duration
     pub fn refresh_interval(&self) -> Duration {
         Duration::from_secs(self.refresh_interval_secs)
@@ -43,10 +101,4 @@ impl Config {
 }
 
 /// 全局配置实例
-pub static CONFIG: Config = Config {
-    status_file: "frontend/status.json",
-    frontend_port: 5959,
-    frontend_dir: "frontend",
-    refresh_interval_secs: 300,
-    server_bind_addr: "0.0.0.0",
-};
\ No newline at end of file
+pub static CONFIG: Config = Config::default();
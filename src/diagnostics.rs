@@ -0,0 +1,269 @@
+use crate::config::CONFIG;
+use chrono::Utc;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::{LazyLock, Mutex};
+use tracing::{info, warn};
+
+/// 快照目录中允许通过管理接口读取的文件
+pub const SNAPSHOT_FILES: [&str; 3] = ["report.json", "page.html", "screenshot.png"];
+
+/// 各供应商最近一次爬取的自检报告
+static LATEST_REPORTS: LazyLock<Mutex<HashMap<String, ScrapeReport>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// 单个选择器的匹配记录
+#[derive(Debug, Clone, Serialize)]
+pub struct SelectorCheck {
+    /// 选择器用途，如 `overall_status`
+    pub purpose: String,
+    pub selector: String,
+    /// 是否为该用途的首选选择器（首选未命中即视为布局漂移）
+    pub expected: bool,
+    pub matched: bool,
+}
+
+/// 一次爬取的选择器健康报告
+#[derive(Debug, Clone, Serialize)]
+pub struct ScrapeReport {
+    pub provider: String,
+    pub url: String,
+    pub started_at: String,
+    /// 使用的选择器版本
+    pub selectors_version: Option<String>,
+    pub checks: Vec<SelectorCheck>,
+    pub layout_drift: bool,
+    pub drift_reasons: Vec<String>,
+    pub error: Option<String>,
+    /// 失败快照目录名
+    pub snapshot: Option<String>,
+}
+
+impl ScrapeReport {
+    pub fn new(provider: &str, url: &str) -> Self {
+        Self {
+            provider: provider.to_string(),
+            url: url.to_string(),
+            started_at: Utc::now().to_rfc3339(),
+            selectors_version: None,
+            checks: Vec::new(),
+            layout_drift: false,
+            drift_reasons: Vec::new(),
+            error: None,
+            snapshot: None,
+        }
+    }
+
+    /// 记录选择器匹配结果，同一用途的同一选择器只要命中过一次即视为命中
+    pub fn record(&mut self, purpose: &str, selector: &str, expected: bool, matched: bool) {
+        match self
+            .checks
+            .iter_mut()
+            .find(|c| c.purpose == purpose && c.selector == selector)
+        {
+            Some(check) => check.matched |= matched,
+            None => self.checks.push(SelectorCheck {
+                purpose: purpose.to_string(),
+                selector: selector.to_string(),
+                expected,
+                matched,
+            }),
+        }
+    }
+
+    /// 记录无法由单个选择器表达的漂移迹象
+    pub fn flag_drift(&mut self, reason: impl Into<String>) {
+        self.drift_reasons.push(reason.into());
+    }
+
+    /// 汇总漂移结果
+    pub fn finalize(&mut self, error: Option<String>) {
+        for check in &self.checks {
+            if check.expected && !check.matched {
+                self.drift_reasons.push(format!("首选选择器未命中 [{}]: {}", check.purpose, check.selector));
+            }
+        }
+        self.layout_drift = !self.drift_reasons.is_empty();
+        self.error = error;
+    }
+
+    /// 是否需要保存失败快照
+    pub fn is_failure(&self) -> bool {
+        self.layout_drift || self.error.is_some()
+    }
+}
+
+/// 保存失败快照（页面源码、截图、报告），返回快照目录名
+pub fn save_snapshot(
+    report: &mut ScrapeReport,
+    page_source: Option<String>,
+    screenshot: Option<Vec<u8>>,
+) -> io::Result<String> {
+    let id = format!("{}-{}", Utc::now().format("%Y%m%d-%H%M%S"), slug(&report.provider));
+    let dir = Path::new(CONFIG.diagnostics_dir).join(&id);
+    fs::create_dir_all(&dir)?;
+
+    if let Some(source) = page_source {
+        fs::write(dir.join("page.html"), source)?;
+    }
+    if let Some(png) = screenshot {
+        fs::write(dir.join("screenshot.png"), png)?;
+    }
+
+    report.snapshot = Some(id.clone());
+    let json = serde_json::to_string_pretty(report).map_err(io::Error::other)?;
+    fs::write(dir.join("report.json"), json)?;
+
+    prune_snapshots();
+    info!("📸 已保存诊断快照: {}", dir.display());
+    Ok(id)
+}
+
+/// 发布最新报告
+pub fn publish(report: ScrapeReport) {
+    if report.layout_drift {
+        warn!("⚠️ {} 页面布局可能已变化: {}", report.provider, report.drift_reasons.join("; "));
+    } else {
+        info!(
+            "🩺 {} 选择器自检通过 ({}/{} 命中)",
+            report.provider,
+            report.checks.iter().filter(|c| c.matched).count(),
+            report.checks.len()
+        );
+    }
+    LATEST_REPORTS
+        .lock()
+        .unwrap()
+        .insert(report.provider.clone(), report);
+}
+
+/// 最新报告列表
+pub fn latest_reports() -> HashMap<String, ScrapeReport> {
+    LATEST_REPORTS.lock().unwrap().clone()
+}
+
+/// 已保存的快照目录名（新的在前）
+pub fn list_snapshots() -> Vec<String> {
+    list_snapshots_in(Path::new(CONFIG.diagnostics_dir))
+}
+
+fn list_snapshots_in(root: &Path) -> Vec<String> {
+    let mut ids: Vec<String> = fs::read_dir(root)
+        .map(|entries| {
+            entries
+                .filter_map(Result::ok)
+                .filter(|e| e.path().is_dir())
+                .filter_map(|e| e.file_name().into_string().ok())
+                .collect()
+        })
+        .unwrap_or_default();
+    ids.sort_unstable_by(|a, b| b.cmp(a));
+    ids
+}
+
+/// 解析快照文件路径，拒绝任何目录穿越
+pub fn snapshot_file(id: &str, file: &str) -> Option<PathBuf> {
+    snapshot_file_in(Path::new(CONFIG.diagnostics_dir), id, file)
+}
+
+fn snapshot_file_in(root: &Path, id: &str, file: &str) -> Option<PathBuf> {
+    let valid_id = !id.is_empty()
+        && id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    if !valid_id || !SNAPSHOT_FILES.contains(&file) {
+        return None;
+    }
+    let path = root.join(id).join(file);
+    path.is_file().then_some(path)
+}
+
+/// 只保留最近的若干个快照
+fn prune_snapshots() {
+    prune_snapshots_in(Path::new(CONFIG.diagnostics_dir), CONFIG.diagnostics_keep);
+}
+
+fn prune_snapshots_in(root: &Path, keep: usize) {
+    for id in list_snapshots_in(root).into_iter().skip(keep) {
+        let dir = root.join(&id);
+        if let Err(e) = fs::remove_dir_all(&dir) {
+            warn!("⚠️ 删除旧诊断快照 {} 失败: {}", dir.display(), e);
+        }
+    }
+}
+
+fn slug(name: &str) -> String {
+    name.chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c.to_ascii_lowercase() } else { '-' })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 每个测试使用独立的临时目录
+    fn temp_root(name: &str) -> PathBuf {
+        let root = std::env::temp_dir().join(format!("diagnostics-test-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(&root).unwrap();
+        root
+    }
+
+    #[test]
+    fn finalize_flags_missed_expected_selectors() {
+        let mut report = ScrapeReport::new("Google", "https://aistudio.google.com/status");
+        report.record("overall_status", "h1.status", true, false);
+        report.record("overall_status", "h1.status", true, true);
+        report.record("service_row", ".row", true, false);
+        report.record("service_row", ".legacy-row", false, false);
+        report.finalize(None);
+
+        assert_eq!(report.checks.len(), 3);
+        assert!(report.checks[0].matched);
+        assert!(report.layout_drift);
+        assert_eq!(report.drift_reasons, ["首选选择器未命中 [service_row]: .row"]);
+        assert!(report.is_failure());
+
+        let mut healthy = ScrapeReport::new("Google", "https://aistudio.google.com/status");
+        healthy.record("overall_status", "h1.status", true, true);
+        healthy.finalize(None);
+        assert!(!healthy.is_failure());
+        healthy.finalize(Some("超时".to_string()));
+        assert!(healthy.is_failure());
+    }
+
+    #[test]
+    fn snapshot_file_rejects_traversal() {
+        let root = temp_root("traversal");
+        fs::create_dir_all(root.join("20250601-120000-google")).unwrap();
+        fs::write(root.join("20250601-120000-google/report.json"), "{}").unwrap();
+        fs::write(root.join("secret.txt"), "secret").unwrap();
+
+        assert_eq!(
+            snapshot_file_in(&root, "20250601-120000-google", "report.json"),
+            Some(root.join("20250601-120000-google/report.json"))
+        );
+        assert!(snapshot_file_in(&root, "20250601-120000-google", "page.html").is_none());
+        assert!(snapshot_file_in(&root, "../20250601-120000-google", "report.json").is_none());
+        assert!(snapshot_file_in(&root, "..", "report.json").is_none());
+        assert!(snapshot_file_in(&root, "20250601-120000-google", "../secret.txt").is_none());
+        assert!(snapshot_file_in(&root, "", "report.json").is_none());
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn prune_keeps_the_newest_snapshots() {
+        let root = temp_root("prune");
+        for id in ["20250601-120000-google", "20250603-120000-google", "20250602-120000-google"] {
+            fs::create_dir_all(root.join(id)).unwrap();
+        }
+        fs::write(root.join("notes.txt"), "").unwrap();
+
+        prune_snapshots_in(&root, 2);
+        assert_eq!(list_snapshots_in(&root), ["20250603-120000-google", "20250602-120000-google"]);
+        assert!(root.join("notes.txt").exists());
+        let _ = fs::remove_dir_all(&root);
+    }
+}
@@ -0,0 +1,255 @@
+//! 状态页面平台自动识别
+//!
+//! 依次探测各托管平台的公开接口，能被对应数据源成功解析即视为识别成功，
+//! 并生成可以直接写入 `config/providers.json` 的供应商配置。
+
+use crate::component_ids;
+use crate::fetcher::StatusFetcher;
+use crate::sources::betterstack::{self, BetterStackConfig};
+use crate::sources::incident_io::{self, IncidentIoConfig};
+use crate::sources::instatus::{self, InstatusConfig};
+use crate::sources::statuspage::{self, StatuspageConfig};
+use crate::sources::{ProviderConfig, SourceConfig};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::error::Error;
+use tracing::{info, warn};
+
+/// 页面源码中可以说明托管平台的特征文本
+const PLATFORM_HINTS: [(&str, &str); 5] = [
+    ("statuspage.io", "statuspage"),
+    ("incident.io", "incident_io"),
+    ("instatus", "instatus"),
+    ("betterstack", "better_stack"),
+    ("betteruptime", "better_stack"),
+];
+
+/// 识别结果
+#[derive(Debug, Serialize)]
+pub struct Discovery {
+    pub url: String,
+    /// 识别出的平台（即数据源类型），未识别时为 None
+    pub platform: Option<String>,
+    /// 识别成功时解析出的组件数
+    pub components: usize,
+    /// 每个探测的结果，便于排查
+    pub probes: Vec<ProbeResult>,
+    /// 可直接使用的供应商配置
+    pub provider: Option<ProviderConfig>,
+}
+
+/// 单个平台探测结果
+#[derive(Debug, Serialize)]
+pub struct ProbeResult {
+    pub platform: String,
+    pub endpoint: String,
+    pub ok: bool,
+    pub detail: String,
+}
+
+/// 识别状态页面所用的平台
+pub async fn discover(url: &str) -> Result<Discovery, Box<dyn Error + Send + Sync>> {
+    let (host, base) = base_url(url)?;
+    let fetcher = StatusFetcher::new()?;
+
+    let (id, name) = provider_identity(&host);
+
+    let mut probes = Vec::new();
+    for source in candidates(&base) {
+        let provider = ProviderConfig {
+            id: id.clone(),
+            name: name.clone(),
+            link: Some(base.clone()),
+            icon: None,
+            source,
+            component_filter: Vec::new(),
+        };
+        let (endpoint, result) = probe(&fetcher, &provider).await;
+        let platform = platform_name(&provider.source).to_string();
+        match result {
+            Ok(components) => {
+                info!("🔎 {} 识别为 {}（{} 个组件）", base, platform, components);
+                probes.push(ProbeResult {
+                    platform: platform.clone(),
+                    endpoint,
+                    ok: true,
+                    detail: format!("解析出 {} 个组件", components),
+                });
+                return Ok(Discovery {
+                    url: base,
+                    platform: Some(platform),
+                    components,
+                    probes,
+                    provider: Some(provider),
+                });
+            }
+            Err(e) => probes.push(ProbeResult {
+                platform,
+                endpoint,
+                ok: false,
+                detail: e.to_string(),
+            }),
+        }
+    }
+
+    // 均未识别时，从页面源码中寻找平台特征供人工判断
+    let hint = match fetcher.fetch_text_once(&base, &HashMap::new()).await {
+        Ok(html) => {
+            let html = html.to_lowercase();
+            PLATFORM_HINTS
+                .iter()
+                .find(|(needle, _)| html.contains(needle))
+                .map(|(needle, platform)| format!("页面中包含 {}，可能是 {}，但接口未能解析", needle, platform))
+                .unwrap_or_else(|| "未发现已知平台特征，可尝试 static_html 或 browser_scrape".to_string())
+        }
+        Err(e) => format!("页面获取失败: {}", e),
+    };
+    warn!("⚠️ 未能识别 {} 的状态页面平台: {}", base, hint);
+    probes.push(ProbeResult {
+        platform: "html".to_string(),
+        endpoint: base.clone(),
+        ok: false,
+        detail: hint,
+    });
+
+    Ok(Discovery {
+        url: base,
+        platform: None,
+        components: 0,
+        probes,
+        provider: None,
+    })
+}
+
+/// 校验地址并只保留协议与域名，`https://x/history` 的接口仍在 `https://x/api/...` 下
+fn base_url(url: &str) -> Result<(String, String), Box<dyn Error + Send + Sync>> {
+    let parsed = reqwest::Url::parse(url.trim()).map_err(|e| format!("无效的URL {}: {}", url, e))?;
+    if parsed.scheme() != "https" {
+        return Err("只支持 https 地址".into());
+    }
+    let host = parsed.host_str().ok_or("URL 缺少域名")?.to_string();
+    Ok((host, parsed.origin().ascii_serialization()))
+}
+
+/// 按顺序探测的平台。incident.io 页面同时提供兼容 Statuspage 的 `/api/v2/summary.json`，
+/// 必须先于 Statuspage 探测，否则会被识别为 Statuspage
+fn candidates(base: &str) -> [SourceConfig; 4] {
+    [
+        SourceConfig::IncidentIo(IncidentIoConfig { url: base.to_string() }),
+        SourceConfig::Statuspage(StatuspageConfig { url: base.to_string() }),
+        SourceConfig::Instatus(InstatusConfig { url: base.to_string() }),
+        SourceConfig::BetterStack(BetterStackConfig { url: base.to_string() }),
+    ]
+}
+
+/// 请求平台接口并用对应数据源解析，返回组件数
+async fn probe(
+    fetcher: &StatusFetcher,
+    provider: &ProviderConfig,
+) -> (String, Result<usize, Box<dyn Error + Send + Sync>>) {
+    let headers = HashMap::from([("Accept".to_string(), "application/json".to_string())]);
+    let fetch = |url: String| {
+        let headers = &headers;
+        async move { fetcher.fetch_text_once(&url, headers).await }
+    };
+
+    match &provider.source {
+        SourceConfig::Statuspage(config) => {
+            let url = config.api_url();
+            let result = match fetch(url.clone()).await {
+                Ok(body) => statuspage::parse(provider, config, &body).map(|s| s.components.len()),
+                Err(e) => Err(e),
+            };
+            (url, result)
+        }
+        SourceConfig::IncidentIo(config) => {
+            let url = config.api_url();
+            let result = match fetch(url.clone()).await {
+                Ok(body) => incident_io::parse(provider, config, &body).map(|s| s.components.len()),
+                Err(e) => Err(e),
+            };
+            (url, result)
+        }
+        SourceConfig::Instatus(config) => {
+            let url = config.summary_url();
+            let result = async {
+                let summary = fetch(config.summary_url()).await?;
+                let components = fetch(config.components_url()).await?;
+                instatus::parse(provider, config, &summary, &components).map(|s| s.components.len())
+            }
+            .await;
+            (url, result)
+        }
+        SourceConfig::BetterStack(config) => {
+            let url = config.api_url();
+            let result = match fetch(url.clone()).await {
+                Ok(body) => betterstack::parse(provider, config, &body, chrono::Utc::now()).map(|s| s.components.len()),
+                Err(e) => Err(e),
+            };
+            (url, result)
+        }
+        _ => (String::new(), Err("该数据源不支持自动识别".into())),
+    }
+}
+
+fn platform_name(source: &SourceConfig) -> &'static str {
+    match source {
+        SourceConfig::Statuspage(_) => "statuspage",
+        SourceConfig::IncidentIo(_) => "incident_io",
+        SourceConfig::Instatus(_) => "instatus",
+        SourceConfig::BetterStack(_) => "better_stack",
+        _ => "unknown",
+    }
+}
+
+/// 由域名推导供应商ID与名称：`status.example.com` -> (`example`, `Example`)
+fn provider_identity(host: &str) -> (String, String) {
+    let labels: Vec<&str> = host
+        .split('.')
+        .filter(|label| !matches!(*label, "www" | "status" | "health"))
+        .collect();
+    let label = match labels.len() {
+        0 => host,
+        1 => labels[0],
+        n => labels[n - 2],
+    };
+    let label = label.trim_end_matches("status");
+    let id = component_ids::normalize(if label.is_empty() { host } else { label });
+    let mut chars = id.chars();
+    let name = match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => id.clone(),
+    };
+    (id, name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn base_keeps_only_scheme_and_host() {
+        let (host, base) = base_url(" https://status.openai.com/history?page=2 ").unwrap();
+        assert_eq!(host, "status.openai.com");
+        assert_eq!(base, "https://status.openai.com");
+        assert_eq!(base_url("https://status.example.com:8443/").unwrap().1, "https://status.example.com:8443");
+        assert_eq!(base_url("http://status.example.com").unwrap_err().to_string(), "只支持 https 地址");
+        assert!(base_url("status.example.com").is_err());
+    }
+
+    #[test]
+    fn incident_io_is_probed_before_statuspage() {
+        let order: Vec<&str> = candidates("https://status.openai.com").iter().map(platform_name).collect();
+        assert_eq!(order, vec!["incident_io", "statuspage", "instatus", "better_stack"]);
+    }
+
+    #[test]
+    fn identity_from_host() {
+        let identity = |host: &str| provider_identity(host);
+        assert_eq!(identity("status.openai.com"), ("openai".to_string(), "Openai".to_string()));
+        assert_eq!(identity("www.githubstatus.com"), ("github".to_string(), "Github".to_string()));
+        assert_eq!(identity("status.cloud.google.com"), ("google".to_string(), "Google".to_string()));
+        assert_eq!(identity("status.x.ai"), ("x".to_string(), "X".to_string()));
+        assert_eq!(identity("Status.Together.AI"), ("together".to_string(), "Together".to_string()));
+    }
+}
@@ -0,0 +1,442 @@
+//! 官方状态与实测健康度的差异检测
+//!
+//! 供应商经常在请求大量失败时仍显示“All Systems Operational”。这里汇总主动探测与客户端上报的
+//! 请求结果，按滑动窗口计算错误率，官方状态正常而错误率持续超过阈值时标记为差异，
+//! 并通过 webhook 发送告警。
+
+use crate::alerts;
+use crate::config::CONFIG;
+use crate::models::{ComponentStatus, StatusIndicator};
+use crate::probes::{ProbeResult, ProbeStatus};
+use chrono::{DateTime, Duration as ChronoDuration, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::fs;
+use std::sync::{LazyLock, Mutex};
+use std::time::Duration;
+use tracing::{error, info, warn};
+
+/// 差异检测状态
+static DETECTOR: LazyLock<Mutex<Detector>> = LazyLock::new(|| Mutex::new(Detector::default()));
+
+/// 差异检测配置
+#[derive(Debug, Clone, Deserialize)]
+pub struct DiscrepancyConfig {
+    /// 计算错误率的滑动窗口（分钟）
+    #[serde(default = "default_window_mins")]
+    pub window_mins: i64,
+    /// 错误率持续超过阈值多久后标记为差异（分钟）
+    #[serde(default = "default_sustain_mins")]
+    pub sustain_mins: i64,
+    /// 错误率阈值（0~1）
+    #[serde(default = "default_error_rate_threshold")]
+    pub error_rate_threshold: f64,
+    /// 窗口内请求数少于该值时不判断
+    #[serde(default = "default_min_requests")]
+    pub min_requests: u64,
+    /// 告警 webhook 地址，支持 `${VAR}` 引用环境变量
+    #[serde(default)]
+    pub webhooks: Vec<String>,
+}
+
+impl Default for DiscrepancyConfig {
+    fn default() -> Self {
+        Self {
+            window_mins: default_window_mins(),
+            sustain_mins: default_sustain_mins(),
+            error_rate_threshold: default_error_rate_threshold(),
+            min_requests: default_min_requests(),
+            webhooks: Vec::new(),
+        }
+    }
+}
+
+fn default_window_mins() -> i64 {
+    15
+}
+
+fn default_sustain_mins() -> i64 {
+    10
+}
+
+fn default_error_rate_threshold() -> f64 {
+    0.2
+}
+
+fn default_min_requests() -> u64 {
+    3
+}
+
+/// 实测数据来源
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ObservationSource {
+    /// 主动探测
+    Probe,
+    /// 客户端上报
+    Client,
+}
+
+/// 一段时间内的请求结果
+#[derive(Debug, Clone)]
+struct Sample {
+    at: DateTime<Utc>,
+    source: ObservationSource,
+    requests: u64,
+    errors: u64,
+}
+
+/// 差异
+#[derive(Debug, Clone, Serialize)]
+pub struct Discrepancy {
+    pub provider: String,
+    pub official: StatusIndicator,
+    /// 窗口内的错误率（0~1）
+    pub error_rate: f64,
+    pub requests: u64,
+    pub errors: u64,
+    pub sources: Vec<ObservationSource>,
+    /// 错误率首次超过阈值的时间
+    pub since: String,
+    pub duration_mins: i64,
+    pub summary: String,
+}
+
+/// 告警事件
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AlertEvent {
+    DiscrepancyOpened,
+    DiscrepancyResolved,
+}
+
+/// 单个供应商的检测状态
+#[derive(Debug, Default)]
+struct ProviderState {
+    official: Option<StatusIndicator>,
+    samples: VecDeque<Sample>,
+    /// 错误率首次超过阈值的时间
+    exceeded_since: Option<DateTime<Utc>>,
+    /// 已告警的差异
+    open: Option<Discrepancy>,
+}
+
+#[derive(Debug, Default)]
+struct Detector {
+    providers: HashMap<String, ProviderState>,
+}
+
+impl Detector {
+    fn record(&mut self, provider: &str, sample: Sample) {
+        self.providers.entry(provider.to_string()).or_default().samples.push_back(sample);
+    }
+
+    fn set_official(&mut self, provider: &str, indicator: Option<StatusIndicator>) {
+        self.providers.entry(provider.to_string()).or_default().official = indicator;
+    }
+
+    /// 重新计算各供应商的差异，返回需要发送的告警
+    fn evaluate(&mut self, config: &DiscrepancyConfig, now: DateTime<Utc>) -> Vec<(AlertEvent, Discrepancy)> {
+        let window_start = now - ChronoDuration::minutes(config.window_mins);
+        let mut alerts = Vec::new();
+
+        for (provider, state) in self.providers.iter_mut() {
+            // 客户端上报的时间不一定有序
+            state.samples.retain(|s| s.at >= window_start);
+
+            let requests: u64 = state.samples.iter().map(|s| s.requests).sum();
+            let errors: u64 = state.samples.iter().map(|s| s.errors).sum();
+            let error_rate = if requests == 0 { 0.0 } else { errors as f64 / requests as f64 };
+            // 只有官方明确显示正常时才算差异，官方已报告故障或状态未知时不告警
+            let official_ok = state.official == Some(StatusIndicator::None);
+            let exceeded = official_ok && requests >= config.min_requests && error_rate >= config.error_rate_threshold;
+
+            if !exceeded {
+                state.exceeded_since = None;
+                if let Some(mut resolved) = state.open.take() {
+                    resolved.error_rate = error_rate;
+                    resolved.duration_mins = (now - parse_time(&resolved.since).unwrap_or(now)).num_minutes();
+                    resolved.official = state.official.clone().unwrap_or(StatusIndicator::Unknown);
+                    alerts.push((AlertEvent::DiscrepancyResolved, resolved));
+                }
+                continue;
+            }
+
+            let since = *state.exceeded_since.get_or_insert(now);
+            let duration_mins = (now - since).num_minutes();
+            if duration_mins < config.sustain_mins {
+                continue;
+            }
+
+            let mut sources: Vec<ObservationSource> = Vec::new();
+            for sample in &state.samples {
+                if !sources.contains(&sample.source) {
+                    sources.push(sample.source);
+                }
+            }
+            let discrepancy = Discrepancy {
+                provider: provider.clone(),
+                official: StatusIndicator::None,
+                error_rate: (error_rate * 1000.0).round() / 1000.0,
+                requests,
+                errors,
+                sources,
+                since: since.to_rfc3339(),
+                duration_mins,
+                summary: format!(
+                    "官方状态: 正常，实测: {:.0}% 请求失败（{}/{}），已持续 {} 分钟",
+                    error_rate * 100.0,
+                    errors,
+                    requests,
+                    duration_mins
+                ),
+            };
+            if state.open.is_none() {
+                alerts.push((AlertEvent::DiscrepancyOpened, discrepancy.clone()));
+            }
+            state.open = Some(discrepancy);
+        }
+        alerts
+    }
+
+    fn active(&self) -> Vec<Discrepancy> {
+        let mut active: Vec<Discrepancy> = self.providers.values().filter_map(|s| s.open.clone()).collect();
+        active.sort_by(|a, b| a.provider.cmp(&b.provider));
+        active
+    }
+}
+
+fn parse_time(value: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(value).ok().map(|t| t.with_timezone(&Utc))
+}
+
+/// 读取差异检测配置，文件不存在时使用默认值
+pub fn load_config() -> DiscrepancyConfig {
+    match fs::read_to_string(CONFIG.discrepancy_file) {
+        Ok(content) => serde_json::from_str(&content).unwrap_or_else(|e| {
+            error!("❌ 解析差异检测配置 {} 失败: {}", CONFIG.discrepancy_file, e);
+            DiscrepancyConfig::default()
+        }),
+        Err(_) => DiscrepancyConfig::default(),
+    }
+}
+
+/// 记录主动探测结果，配置错误的探测不计入
+pub fn record_probe(result: &ProbeResult) {
+    let errors = match result.status {
+        ProbeStatus::Up | ProbeStatus::Degraded => 0,
+        ProbeStatus::Down => 1,
+        ProbeStatus::Misconfigured => return,
+    };
+    let at = parse_time(&result.checked_at).unwrap_or_else(Utc::now);
+    DETECTOR.lock().unwrap().record(
+        &result.provider,
+        Sample { at, source: ObservationSource::Probe, requests: 1, errors },
+    );
+}
+
+/// 记录客户端上报的一段时间内的请求数与供应商侧失败数
+pub fn record_requests(provider: &str, source: ObservationSource, at: DateTime<Utc>, requests: u64, errors: u64) {
+    DETECTOR
+        .lock()
+        .unwrap()
+        .record(provider, Sample { at, source, requests, errors: errors.min(requests) });
+}
+
+/// 根据最新一轮获取结果更新各供应商的官方状态
+pub fn record_official(results: &HashMap<String, serde_json::Value>) {
+    let mut detector = DETECTOR.lock().unwrap();
+    for (provider, value) in results {
+        detector.set_official(provider, official_indicator(value));
+    }
+}
+
+/// 官方状态：整体指示器正常但有组件异常时，按最严重的组件状态计算，
+/// 组件级别的故障同样视为官方已承认
+fn official_indicator(summary: &serde_json::Value) -> Option<StatusIndicator> {
+    let indicator = summary
+        .pointer("/status/indicator")
+        .and_then(|v| serde_json::from_value::<StatusIndicator>(v.clone()).ok());
+    if indicator != Some(StatusIndicator::None) {
+        return indicator;
+    }
+
+    let worst = summary
+        .get("components")
+        .and_then(|v| v.as_array())
+        .into_iter()
+        .flatten()
+        .filter_map(|c| c.get("status").and_then(|s| serde_json::from_value::<ComponentStatus>(s.clone()).ok()))
+        .max_by_key(ComponentStatus::severity);
+    Some(match worst {
+        Some(ComponentStatus::UnderMaintenance) => StatusIndicator::Maintenance,
+        Some(ComponentStatus::DegradedPerformance | ComponentStatus::PartialOutage) => StatusIndicator::Minor,
+        Some(ComponentStatus::MajorOutage) => StatusIndicator::Major,
+        _ => StatusIndicator::None,
+    })
+}
+
+/// 当前存在的差异
+pub fn active() -> Vec<Discrepancy> {
+    DETECTOR.lock().unwrap().active()
+}
+
+/// 每分钟重新检测一次，并发送告警
+pub async fn run_forever() {
+    let mut interval = tokio::time::interval(Duration::from_secs(60));
+    loop {
+        interval.tick().await;
+        let config = load_config();
+        let alerts = DETECTOR.lock().unwrap().evaluate(&config, Utc::now());
+
+        for (event, discrepancy) in alerts {
+            let text = match event {
+                AlertEvent::DiscrepancyOpened => {
+                    warn!("🚨 {} 状态差异: {}", discrepancy.provider, discrepancy.summary);
+                    format!("🚨 {} {}", discrepancy.provider, discrepancy.summary)
+                }
+                AlertEvent::DiscrepancyResolved => {
+                    info!("✅ {} 状态差异已恢复", discrepancy.provider);
+                    format!(
+                        "✅ {} 实测错误率已恢复（当前 {:.0}%），差异持续了 {} 分钟",
+                        discrepancy.provider,
+                        discrepancy.error_rate * 100.0,
+                        discrepancy.duration_mins
+                    )
+                }
+            };
+            let event = serde_json::to_value(event).unwrap();
+            alerts::send(
+                &config.webhooks,
+                event.as_str().unwrap_or_default(),
+                &text,
+                serde_json::json!({ "discrepancy": discrepancy }),
+            )
+            .await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(minute: i64) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339("2025-06-01T12:00:00Z").unwrap().with_timezone(&Utc) + ChronoDuration::minutes(minute)
+    }
+
+    fn probe(detector: &mut Detector, minute: i64, failed: bool) {
+        detector.record(
+            "OpenAI",
+            Sample { at: at(minute), source: ObservationSource::Probe, requests: 1, errors: failed as u64 },
+        );
+    }
+
+    #[test]
+    fn sustained_errors_open_then_resolve() {
+        let config = DiscrepancyConfig { min_requests: 1, ..DiscrepancyConfig::default() };
+        let mut detector = Detector::default();
+        detector.set_official("OpenAI", Some(StatusIndicator::None));
+
+        for minute in 0..10 {
+            probe(&mut detector, minute, minute % 2 == 0);
+            assert!(detector.evaluate(&config, at(minute)).is_empty(), "未持续足够时间前不应告警");
+        }
+
+        probe(&mut detector, 10, true);
+        let alerts = detector.evaluate(&config, at(10));
+        assert_eq!(alerts.len(), 1);
+        assert_eq!(alerts[0].0, AlertEvent::DiscrepancyOpened);
+        assert_eq!(alerts[0].1.duration_mins, 10);
+        assert_eq!(detector.active().len(), 1);
+
+        // 已告警的差异不重复告警
+        probe(&mut detector, 11, true);
+        assert!(detector.evaluate(&config, at(11)).is_empty());
+
+        // 窗口内的失败样本过期后恢复
+        for minute in 12..40 {
+            probe(&mut detector, minute, false);
+        }
+        let alerts = detector.evaluate(&config, at(40));
+        assert_eq!(alerts.len(), 1);
+        assert_eq!(alerts[0].0, AlertEvent::DiscrepancyResolved);
+        assert!(detector.active().is_empty());
+    }
+
+    #[test]
+    fn no_discrepancy_when_official_reports_outage() {
+        let config = DiscrepancyConfig::default();
+        let mut detector = Detector::default();
+        detector.set_official("OpenAI", Some(StatusIndicator::Major));
+        for minute in 0..30 {
+            probe(&mut detector, minute, true);
+            assert!(detector.evaluate(&config, at(minute)).is_empty());
+        }
+    }
+
+    #[test]
+    fn too_few_requests_are_ignored() {
+        let config = DiscrepancyConfig { sustain_mins: 0, ..DiscrepancyConfig::default() };
+        let mut detector = Detector::default();
+        detector.set_official("OpenAI", Some(StatusIndicator::None));
+        probe(&mut detector, 0, true);
+        probe(&mut detector, 1, true);
+        assert!(detector.evaluate(&config, at(1)).is_empty());
+
+        probe(&mut detector, 2, false);
+        let alerts = detector.evaluate(&config, at(2));
+        assert_eq!(alerts.len(), 1);
+        assert_eq!(alerts[0].1.errors, 2);
+        assert_eq!(alerts[0].1.sources, vec![ObservationSource::Probe]);
+    }
+
+    #[test]
+    fn client_reports_are_weighted_by_request_count() {
+        let config = DiscrepancyConfig { sustain_mins: 0, ..DiscrepancyConfig::default() };
+        let mut detector = Detector::default();
+        detector.set_official("OpenAI", Some(StatusIndicator::None));
+        detector.record("OpenAI", Sample { at: at(0), source: ObservationSource::Client, requests: 100, errors: 40 });
+        probe(&mut detector, 1, false);
+
+        let alerts = detector.evaluate(&config, at(1));
+        assert_eq!(alerts.len(), 1);
+        let discrepancy = &alerts[0].1;
+        assert_eq!(discrepancy.requests, 101);
+        assert_eq!(discrepancy.errors, 40);
+        assert_eq!(discrepancy.sources, vec![ObservationSource::Client, ObservationSource::Probe]);
+    }
+
+    #[test]
+    fn component_incident_counts_as_acknowledged() {
+        let summary = |components: serde_json::Value| {
+            serde_json::json!({ "status": { "indicator": "none" }, "components": components })
+        };
+        let operational = summary(serde_json::json!([
+            { "name": "API", "status": "operational" },
+            { "name": "Sora", "status": "unknown_value" }
+        ]));
+        assert_eq!(official_indicator(&operational), Some(StatusIndicator::None));
+
+        let degraded = summary(serde_json::json!([
+            { "name": "API", "status": "operational" },
+            { "name": "Chat Completions", "status": "partial_outage" }
+        ]));
+        assert_eq!(official_indicator(&degraded), Some(StatusIndicator::Minor));
+        let outage = summary(serde_json::json!([{ "name": "API", "status": "major_outage" }]));
+        assert_eq!(official_indicator(&outage), Some(StatusIndicator::Major));
+        let reported = serde_json::json!({ "status": { "indicator": "critical" }, "components": [] });
+        assert_eq!(official_indicator(&reported), Some(StatusIndicator::Critical));
+        assert_eq!(official_indicator(&serde_json::json!({})), None);
+
+        // 组件已报告故障时，实测错误率高不算差异
+        let config = DiscrepancyConfig { sustain_mins: 0, min_requests: 1, ..DiscrepancyConfig::default() };
+        let mut detector = Detector::default();
+        detector.set_official("OpenAI", official_indicator(&degraded));
+        probe(&mut detector, 0, true);
+        assert!(detector.evaluate(&config, at(0)).is_empty());
+
+        detector.set_official("OpenAI", official_indicator(&operational));
+        assert_eq!(detector.evaluate(&config, at(1)).len(), 1);
+    }
+}
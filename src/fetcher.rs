@@ -1,13 +1,25 @@
+use crate::classification::Classifier;
 use crate::models::StatusPageSummary;
+use crate::sources::incident_io::IncidentIoConfig;
+use crate::sources::inference::{InferenceConfig, ServerKind};
+use crate::sources::http_check::{self, CheckResult, HttpCheckConfig};
+use crate::sources::{ProviderConfig, SourceConfig};
+#[cfg(feature = "browser")]
 use crate::google::GoogleAIStatusCrawler;
 use reqwest::{Client, ClientBuilder};
 use std::collections::HashMap;
 use std::time::Duration;
-use std::sync::Arc;
+use std::sync::{Arc, LazyLock, Mutex};
 use std::error::Error;
+use std::future::Future;
 use tracing::{info, warn, error};
+use tokio::task::JoinSet;
 use tokio::time::sleep;
 
+/// 最近一轮刷新的结果，供按模型、按事件查询的接口使用
+static LATEST_STATUSES: LazyLock<Mutex<HashMap<String, serde_json::Value>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
 /// 定义支持的LLM来源
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum LlmProvider {
@@ -21,7 +33,7 @@ impl LlmProvider {
     /// 获取供应商的API状态页面URL
     pub fn api_url(&self) -> &'static str {
         match self {
-            LlmProvider::OpenAI => "https://status.openai.com/api/v2/summary.json",
+            LlmProvider::OpenAI => "https://status.openai.com", // incident.io 页面，接口地址由数据源推导
             LlmProvider::Anthropic => "https://status.anthropic.com/api/v2/summary.json",
             LlmProvider::DeepSeek => "https://status.deepseek.com/api/v2/summary.json",
             LlmProvider::Google => "https://aistudio.google.com/status", // 爬虫获取
@@ -29,6 +41,7 @@ impl LlmProvider {
     }
 
     /// 从字符串解析为LlmProvider枚举
+    #[allow(dead_code)]
     pub fn from_str(s: &str) -> Option<Self> {
         match s.to_lowercase().as_str() {
             "openai" => Some(LlmProvider::OpenAI),
@@ -49,18 +62,39 @@ impl LlmProvider {
         }
     }
 
-    /// 检查是否需要爬虫获取状态
-    pub fn requires_scraping(&self) -> bool {
+    /// 不使用 statuspage 接口的内置供应商对应的数据源
+    pub fn builtin_source(&self) -> Option<ProviderConfig> {
         match self {
-            LlmProvider::Google => true,
-            _ => false,
+            LlmProvider::OpenAI => Some(ProviderConfig {
+                id: "openai".to_string(),
+                name: self.name().to_string(),
+                link: Some(self.api_url().to_string()),
+                icon: None,
+                source: SourceConfig::IncidentIo(IncidentIoConfig {
+                    url: self.api_url().to_string(),
+                }),
+                component_filter: Vec::new(),
+            }),
+            _ => None,
         }
     }
+
+    /// 检查是否需要爬虫获取状态
+    pub fn requires_scraping(&self) -> bool {
+        matches!(self, LlmProvider::Google)
+    }
+
+    /// 检查当前构建是否支持该供应商（爬虫需要启用 browser 特性）
+    pub fn is_supported(&self) -> bool {
+        !self.requires_scraping() || cfg!(feature = "browser")
+    }
 }
 
 /// 状态获取器
 pub struct StatusFetcher {
     client: Arc<Client>,
+    /// 通用 HTTP 检查与自建推理服务使用的客户端，内网服务通常是 http，因此不限制 https
+    monitor_client: Arc<Client>,
 }
 
 impl StatusFetcher {
@@ -68,20 +102,21 @@ impl StatusFetcher {
 
     /// 创建一个配置好的状态获取器
     pub fn new() -> Result<Self, Box<dyn Error + Send + Sync>> {
-        let client = ClientBuilder::new()
-            .connect_timeout(Duration::from_secs(10))
-            .timeout(Duration::from_secs(30))
-            .user_agent("LLM-Status-Monitor/1.0 (Rust/1.80.0)")
-            .gzip(true)
-            .pool_max_idle_per_host(10)
-            .pool_idle_timeout(Duration::from_secs(90))
-            .use_rustls_tls()
-            .tls_built_in_root_certs(true)
-            .https_only(true)
-            .build()?;
+        let builder = || {
+            ClientBuilder::new()
+                .connect_timeout(Duration::from_secs(10))
+                .timeout(Duration::from_secs(30))
+                .user_agent("LLM-Status-Monitor/1.0 (Rust/1.80.0)")
+                .gzip(true)
+                .pool_max_idle_per_host(10)
+                .pool_idle_timeout(Duration::from_secs(90))
+                .use_rustls_tls()
+                .tls_built_in_root_certs(true)
+        };
 
         Ok(Self {
-            client: Arc::new(client),
+            client: Arc::new(builder().https_only(true).build()?),
+            monitor_client: Arc::new(builder().build()?),
         })
     }
 
@@ -92,6 +127,8 @@ impl StatusFetcher {
     ) -> Result<StatusPageSummary, Box<dyn Error + Send + Sync>> {
         if provider.requires_scraping() {
             self.get_scraped_status(provider).await
+        } else if let Some(source) = provider.builtin_source() {
+            self.get_custom_provider_status(&source).await
         } else {
             let url = provider.api_url();
             info!("📊 从 {} 获取 {} 状态", url, provider.name());
@@ -105,6 +142,7 @@ impl StatusFetcher {
         provider: LlmProvider,
     ) -> Result<StatusPageSummary, Box<dyn Error + Send + Sync>> {
         match provider {
+            #[cfg(feature = "browser")]
             LlmProvider::Google => {
                 info!("📊 使用爬虫获取 Google 状态");
                 let crawler = GoogleAIStatusCrawler::new().await?;
@@ -120,19 +158,191 @@ impl StatusFetcher {
         }
     }
 
+    /// 获取配置文件中自定义供应商的状态
+    pub async fn get_custom_provider_status(
+        &self,
+        provider: &ProviderConfig,
+    ) -> Result<StatusPageSummary, Box<dyn Error + Send + Sync>> {
+        let mut summary = self.fetch_custom_source(provider).await?;
+        crate::sources::apply_component_filter(&mut summary, &provider.component_filter);
+        Ok(summary)
+    }
+
+    /// 按数据源类型获取并解析
+    async fn fetch_custom_source(
+        &self,
+        provider: &ProviderConfig,
+    ) -> Result<StatusPageSummary, Box<dyn Error + Send + Sync>> {
+        match &provider.source {
+            SourceConfig::Statuspage(config) => {
+                let url = config.api_url();
+                info!("📊 从 {} 获取 {} 状态", url, provider.name);
+                let headers = HashMap::from([("Accept".to_string(), "application/json".to_string())]);
+                let body = self.fetch_text_with_retry(&url, &headers, &provider.name).await?;
+                crate::sources::statuspage::parse(provider, config, &body)
+            }
+            #[cfg(feature = "browser")]
+            SourceConfig::BrowserScrape(config) => {
+                info!("📊 使用浏览器爬虫获取 {} 状态", provider.name);
+                crate::sources::browser::fetch(provider, config).await
+            }
+            #[cfg(not(feature = "browser"))]
+            SourceConfig::BrowserScrape(_) => Err("当前构建未启用 browser 特性，不支持爬虫获取".into()),
+            SourceConfig::StaticHtml(config) => {
+                info!("📊 从 {} 获取 {} 页面", config.url, provider.name);
+                let html = self.fetch_text_with_retry(&config.url, &HashMap::new(), &provider.name).await?;
+                crate::sources::html::parse(provider, config, &html)
+            }
+            SourceConfig::JsonEndpoint(config) => {
+                info!("📊 从 {} 获取 {} 状态", config.url, provider.name);
+                let mut headers = crate::sources::expand_headers(&config.headers);
+                headers.entry("Accept".to_string()).or_insert_with(|| "application/json".to_string());
+                let body = self.fetch_text_with_retry(&config.url, &headers, &provider.name).await?;
+                crate::sources::json::parse(provider, config, &body)
+            }
+            SourceConfig::IncidentIo(config) => {
+                let url = config.api_url();
+                info!("📊 从 {} 获取 {} 状态", url, provider.name);
+                let headers = HashMap::from([("Accept".to_string(), "application/json".to_string())]);
+                let body = self.fetch_text_with_retry(&url, &headers, &provider.name).await?;
+                crate::sources::incident_io::parse(provider, config, &body)
+            }
+            SourceConfig::Instatus(config) => {
+                info!("📊 从 {} 获取 {} 状态", config.url, provider.name);
+                let headers = HashMap::from([("Accept".to_string(), "application/json".to_string())]);
+                let summary = self.fetch_text_with_retry(&config.summary_url(), &headers, &provider.name).await?;
+                let components = self.fetch_text_with_retry(&config.components_url(), &headers, &provider.name).await?;
+                crate::sources::instatus::parse(provider, config, &summary, &components)
+            }
+            SourceConfig::BetterStack(config) => {
+                let url = config.api_url();
+                info!("📊 从 {} 获取 {} 状态", url, provider.name);
+                let headers = HashMap::from([("Accept".to_string(), "application/json".to_string())]);
+                let body = self.fetch_text_with_retry(&url, &headers, &provider.name).await?;
+                crate::sources::betterstack::parse(provider, config, &body, chrono::Utc::now())
+            }
+            SourceConfig::GoogleCloud(config) => {
+                info!("📊 从 {} 获取 {} 状态", config.url, provider.name);
+                let headers = HashMap::from([("Accept".to_string(), "application/json".to_string())]);
+                let body = self.fetch_text_with_retry(&config.url, &headers, &provider.name).await?;
+                crate::sources::google_cloud::parse(provider, config, &body)
+            }
+            SourceConfig::AzureStatus(config) => {
+                info!("📊 从 {} 获取 {} 状态", config.url, provider.name);
+                let body = self.fetch_text_with_retry(&config.url, &HashMap::new(), &provider.name).await?;
+                crate::sources::azure::parse(provider, config, &body)
+            }
+            SourceConfig::AwsHealth(config) => {
+                info!("📊 从 {} 获取 {} 状态", config.url, provider.name);
+                let body = self.fetch_bytes_with_retry(&config.url, &HashMap::new(), &provider.name).await?;
+                crate::sources::aws::parse(provider, config, &body)
+            }
+            SourceConfig::Announcements(config) => {
+                info!("📊 从 {} 获取 {} 公告", config.url, provider.name);
+                let headers = crate::sources::expand_headers(&config.headers);
+                let body = self.fetch_text_with_retry(&config.url, &headers, &provider.name).await?;
+                crate::sources::announcements::parse(provider, config, &body, chrono::Utc::now())
+            }
+            SourceConfig::Vllm(config) => self.fetch_inference(provider, config, ServerKind::Vllm).await,
+            SourceConfig::Tgi(config) => self.fetch_inference(provider, config, ServerKind::Tgi).await,
+            SourceConfig::Ollama(config) => self.fetch_inference(provider, config, ServerKind::Ollama).await,
+            SourceConfig::LlamaCpp(config) => self.fetch_inference(provider, config, ServerKind::LlamaCpp).await,
+            SourceConfig::HttpCheck(config) => self.fetch_http_checks(provider, config).await,
+        }
+    }
+
+    /// 执行通用 HTTP 检查，每个检查各自重试，未到检查间隔时复用上一次结果
+    async fn fetch_http_checks(
+        &self,
+        provider: &ProviderConfig,
+        config: &HttpCheckConfig,
+    ) -> Result<StatusPageSummary, Box<dyn Error + Send + Sync>> {
+        if config.checks.is_empty() {
+            return Err("未配置任何检查".into());
+        }
+        // 各检查并发执行，个别接口不可达时的重试等待不会拖慢其他检查
+        let mut results: Vec<Option<CheckResult>> = vec![None; config.checks.len()];
+        let mut tasks = JoinSet::new();
+        for (index, check) in config.checks.iter().enumerate() {
+            if let Some(result) = http_check::cached(provider, check) {
+                info!("📊 {}/{} 未到检查间隔，沿用上一次结果", provider.name, check.name);
+                results[index] = Some(result);
+                continue;
+            }
+            let (client, check) = (self.monitor_client.clone(), check.clone());
+            let label = format!("{}/{}", provider.name, check.name);
+            tasks.spawn(async move {
+                let result = Self::with_retry(&label, || http_check::check_once(&client, &check)).await;
+                (index, result.map_err(|e| e.to_string()))
+            });
+        }
+
+        info!("📊 执行 {} 的 {} 个 HTTP 检查", provider.name, tasks.len());
+        while let Some(joined) = tasks.join_next().await {
+            let (index, result) = joined?;
+            http_check::remember(provider, &config.checks[index], &result);
+            results[index] = Some(result);
+        }
+        let results: Vec<CheckResult> = results.into_iter().flatten().collect();
+        Ok(http_check::parse(provider, config, &results))
+    }
+
+    /// 检查自建推理服务的各个实例
+    async fn fetch_inference(
+        &self,
+        provider: &ProviderConfig,
+        config: &InferenceConfig,
+        kind: ServerKind,
+    ) -> Result<StatusPageSummary, Box<dyn Error + Send + Sync>> {
+        info!("📊 检查 {} 的 {} 个推理服务实例", provider.name, config.instances.len());
+        crate::sources::inference::fetch(&self.monitor_client, provider, config, kind).await
+    }
+
     /// 带重试机制的获取函数（用于API调用）
     async fn fetch_with_retry(
         &self,
         url: &str,
         provider_name: &str,
     ) -> Result<StatusPageSummary, Box<dyn Error + Send + Sync>> {
+        Self::with_retry(provider_name, || self.fetch_once(url)).await
+    }
+
+    /// 带重试机制的页面/接口文本获取，供自定义数据源使用
+    pub async fn fetch_text_with_retry(
+        &self,
+        url: &str,
+        headers: &HashMap<String, String>,
+        provider_name: &str,
+    ) -> Result<String, Box<dyn Error + Send + Sync>> {
+        Self::with_retry(provider_name, || self.fetch_text_once(url, headers)).await
+    }
+
+    /// 带重试机制的原始字节获取，供需要自行处理编码的数据源使用
+    pub async fn fetch_bytes_with_retry(
+        &self,
+        url: &str,
+        headers: &HashMap<String, String>,
+        provider_name: &str,
+    ) -> Result<Vec<u8>, Box<dyn Error + Send + Sync>> {
+        Self::with_retry(provider_name, || self.fetch_bytes_once(url, headers)).await
+    }
+
+    /// 按指数退避重试任意获取操作
+    async fn with_retry<T, F, Fut>(
+        provider_name: &str,
+        mut operation: F,
+    ) -> Result<T, Box<dyn Error + Send + Sync>>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T, Box<dyn Error + Send + Sync>>>,
+    {
         let mut last_error: Option<Box<dyn Error + Send + Sync>> = None;
 
         for attempt in 1..=Self::MAX_RETRIES {
-            match self.fetch_once(url).await {
-                Ok(summary) => {
+            match operation().await {
+                Ok(value) => {
                     info!("✅ 成功获取 {} 状态 (尝试 {}/{})", provider_name, attempt, Self::MAX_RETRIES);
-                    return Ok(summary);
+                    return Ok(value);
                 }
                 Err(e) => {
                     warn!("⚠️ {} 获取失败 (尝试 {}/{}): {}", provider_name, attempt, Self::MAX_RETRIES, e);
@@ -175,8 +385,48 @@ impl StatusFetcher {
         Ok(summary)
     }
 
-    /// 获取所有供应商的状态
-    pub async fn get_all_llm_statuses(&self) -> HashMap<String, serde_json::Value> {
+    /// 单次文本获取尝试
+    pub async fn fetch_text_once(
+        &self,
+        url: &str,
+        headers: &HashMap<String, String>,
+    ) -> Result<String, Box<dyn Error + Send + Sync>> {
+        Ok(self.send_once(url, headers).await?.text().await?)
+    }
+
+    /// 单次原始字节获取尝试
+    async fn fetch_bytes_once(
+        &self,
+        url: &str,
+        headers: &HashMap<String, String>,
+    ) -> Result<Vec<u8>, Box<dyn Error + Send + Sync>> {
+        Ok(self.send_once(url, headers).await?.bytes().await?.to_vec())
+    }
+
+    /// 发送请求并检查响应状态
+    async fn send_once(
+        &self,
+        url: &str,
+        headers: &HashMap<String, String>,
+    ) -> Result<reqwest::Response, Box<dyn Error + Send + Sync>> {
+        let mut request = self.client.get(url);
+        for (name, value) in headers {
+            request = request.header(name.as_str(), value.as_str());
+        }
+        let response = request.send().await?;
+
+        if !response.status().is_success() {
+            return Err(format!("HTTP 错误: {} - {}", 
+                response.status(), 
+                response.status().canonical_reason().unwrap_or("Unknown")
+            ).into());
+        }
+
+        Ok(response)
+    }
+
+    /// 获取所有供应商（内置与自定义）的状态
+    pub async fn get_all_llm_statuses(&self, custom: &[ProviderConfig]) -> HashMap<String, serde_json::Value> {
         let providers = [
             LlmProvider::OpenAI,
             LlmProvider::Anthropic,
@@ -185,11 +435,22 @@ impl StatusFetcher {
         ];
 
         let mut results = HashMap::new();
+        let classifier = Classifier::load();
         
         for provider in providers {
             let provider_name = provider.name().to_string();
+            if !provider.is_supported() {
+                warn!("⚠️ 当前构建未启用 browser 特性，跳过 {}", provider_name);
+                results.insert(provider_name, serde_json::json!({
+                    "error": "当前构建未启用 browser 特性，不支持爬虫获取",
+                    "status": "unsupported",
+                    "timestamp": chrono::Utc::now().to_rfc3339()
+                }));
+                continue;
+            }
             match self.get_llm_provider_status(provider).await {
-                Ok(summary) => {
+                Ok(mut summary) => {
+                    classifier.apply(&mut summary);
                     info!("✅ 成功获取 {} 状态", provider_name);
                     results.insert(provider_name, serde_json::to_value(summary).unwrap());
                 }
@@ -204,8 +465,39 @@ impl StatusFetcher {
             }
         }
 
+        for provider in custom {
+            if results.contains_key(&provider.name) {
+                warn!("⚠️ 自定义供应商 {} 与已有供应商重名，已跳过", provider.name);
+                continue;
+            }
+            if !provider.source.is_supported() {
+                warn!("⚠️ 当前构建未启用 browser 特性，跳过 {}", provider.name);
+                results.insert(provider.name.clone(), serde_json::json!({
+                    "error": "当前构建未启用 browser 特性，不支持爬虫获取",
+                    "status": "unsupported",
+                    "timestamp": chrono::Utc::now().to_rfc3339()
+                }));
+                continue;
+            }
+            match self.get_custom_provider_status(provider).await {
+                Ok(mut summary) => {
+                    classifier.apply(&mut summary);
+                    info!("✅ 成功获取 {} 状态", provider.name);
+                    results.insert(provider.name.clone(), serde_json::to_value(summary).unwrap());
+                }
+                Err(e) => {
+                    error!("❌ 获取 {} 状态失败: {}", provider.name, e);
+                    results.insert(provider.name.clone(), serde_json::json!({
+                        "error": e.to_string(),
+                        "status": "failed",
+                        "timestamp": chrono::Utc::now().to_rfc3339()
+                    }));
+                }
+            }
+        }
+
         info!("📊 状态获取完成，成功: {}, 失败: {}", 
-            results.values().filter(|v| !v.get("error").is_some()).count(),
+            results.values().filter(|v| v.get("error").is_none()).count(),
             results.values().filter(|v| v.get("error").is_some()).count()
         );
 
@@ -214,6 +506,7 @@ impl StatusFetcher {
 }
 
 // 保持向后兼容的公共接口
+#[allow(dead_code)]
 pub async fn get_llm_provider_status(
     provider: LlmProvider,
 ) -> Result<StatusPageSummary, Box<dyn Error + Send + Sync>> {
@@ -221,9 +514,18 @@ pub async fn get_llm_provider_status(
     fetcher.get_llm_provider_status(provider).await
 }
 
-pub async fn get_all_llm_statuses() -> HashMap<String, serde_json::Value> {
+/// 最近一轮刷新得到的各供应商状态，按供应商名称索引
+pub fn latest_statuses() -> HashMap<String, serde_json::Value> {
+    LATEST_STATUSES.lock().unwrap().clone()
+}
+
+pub async fn get_all_llm_statuses(custom: &[ProviderConfig]) -> HashMap<String, serde_json::Value> {
     match StatusFetcher::new() {
-        Ok(fetcher) => fetcher.get_all_llm_statuses().await,
+        Ok(fetcher) => {
+            let results = fetcher.get_all_llm_statuses(custom).await;
+            *LATEST_STATUSES.lock().unwrap() = results.clone();
+            results
+        }
         Err(e) => {
             error!("❌ 创建状态获取器失败: {}", e);
             let mut error_result = HashMap::new();
@@ -1,8 +1,17 @@
-use crate::models::{StatusPageSummary, Page, Component, ComponentStatus, OverallStatus, StatusIndicator};
+use crate::component_ids;
+use crate::diagnostics::{self, ScrapeReport};
+use crate::selectors::{self, GoogleSelectors, SeverityClasses};
+use crate::webdriver;
+use crate::models::{
+    StatusPageSummary, Page, Component, ComponentStatus, OverallStatus, StatusIndicator,
+    Incident, IncidentStatus, IncidentImpact, IncidentUpdate, IncidentUpdateStatus,
+};
+use chrono::{DateTime, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Utc};
+use std::collections::{HashMap, HashSet};
 use std::error::Error;
+use std::sync::{Arc, Mutex};
 use tracing::{info, warn, error, debug};
 use thirtyfour::prelude::*;
-use thirtyfour::PageLoadStrategy;
 use tokio::time::{timeout, sleep, Duration};
 
 /// Google专用状态枚举
@@ -15,12 +24,13 @@ pub enum GoogleServiceStatus {
     Unknown,
 }
 
-impl From<&str> for GoogleServiceStatus {
-    fn from(class_name: &str) -> Self {
+impl GoogleServiceStatus {
+    /// 根据时间轴元素的class判断服务状态
+    pub fn from_class(class_name: &str, severity: &SeverityClasses) -> Self {
         match class_name {
-            s if s.contains("severity-major") => GoogleServiceStatus::MajorOutage,
-            s if s.contains("severity-moderate") => GoogleServiceStatus::PartialOutage,
-            s if s.contains("severity-minor") => GoogleServiceStatus::Degraded,
+            s if s.contains(severity.major.as_str()) => GoogleServiceStatus::MajorOutage,
+            s if s.contains(severity.moderate.as_str()) => GoogleServiceStatus::PartialOutage,
+            s if s.contains(severity.minor.as_str()) => GoogleServiceStatus::Degraded,
             _ => GoogleServiceStatus::Operational,
         }
     }
@@ -71,10 +81,206 @@ impl From<GoogleOverallStatus> for StatusIndicator {
     }
 }
 
+impl From<&GoogleServiceStatus> for IncidentImpact {
+    fn from(status: &GoogleServiceStatus) -> Self {
+        match status {
+            GoogleServiceStatus::Degraded => IncidentImpact::Minor,
+            GoogleServiceStatus::PartialOutage => IncidentImpact::Major,
+            GoogleServiceStatus::MajorOutage => IncidentImpact::Critical,
+            GoogleServiceStatus::Operational => IncidentImpact::None,
+            GoogleServiceStatus::Unknown => IncidentImpact::Unknown,
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct GoogleServiceInfo {
     pub name: String,
     pub status: GoogleServiceStatus,
+    pub incidents: Vec<GoogleIncidentInfo>,
+}
+
+/// 时间轴对话框中的事件描述
+#[derive(Debug, Clone)]
+pub struct GoogleIncidentInfo {
+    pub service: String,
+    pub title: String,
+    /// 对话框中的原始时间范围文本
+    pub time_range: Option<String>,
+    pub started_at: Option<DateTime<Utc>>,
+    pub ended_at: Option<DateTime<Utc>>,
+    pub description: String,
+    pub severity: GoogleServiceStatus,
+}
+
+impl GoogleIncidentInfo {
+    /// 从对话框文本解析事件信息
+    ///
+    /// 对话框通常依次包含：服务名、事件标题、时间范围、详细描述。
+    pub fn parse_dialog(service: &str, severity: GoogleServiceStatus, text: &str) -> Option<Self> {
+        let lines: Vec<&str> = text
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && *line != service)
+            .collect();
+
+        let time_index = lines.iter().position(|line| parse_time_range(line).is_some());
+        let (started_at, ended_at) = match time_index.and_then(|i| parse_time_range(lines[i])) {
+            Some((start, end)) => (Some(start), end),
+            None => (None, None),
+        };
+
+        let mut rest = lines
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| Some(*i) != time_index)
+            .map(|(_, line)| *line);
+
+        let title = rest.next()?.to_string();
+        let description = rest.collect::<Vec<_>>().join("\n");
+
+        Some(Self {
+            service: service.to_string(),
+            title,
+            time_range: time_index.map(|i| lines[i].to_string()),
+            started_at,
+            ended_at,
+            description,
+            severity,
+        })
+    }
+
+    /// 根据服务名、标题和时间范围生成事件ID，同一事件在多次爬取间保持一致
+    fn id(&self) -> String {
+        let hash = component_ids::stable_hash([
+            self.service.as_str(),
+            self.title.as_str(),
+            self.time_range.as_deref().unwrap_or_default(),
+        ]);
+        format!("google-incident-{:016x}", hash)
+    }
+
+    fn into_incident(self, timestamp: &DateTime<Utc>) -> Incident {
+        let id = self.id();
+        let created_at = self.started_at.unwrap_or(*timestamp).to_rfc3339();
+        let resolved_at = self.ended_at.map(|t| t.to_rfc3339());
+        let updated_at = resolved_at.clone().unwrap_or_else(|| timestamp.to_rfc3339());
+        let (status, update_status) = if resolved_at.is_some() {
+            (IncidentStatus::Resolved, IncidentUpdateStatus::Resolved)
+        } else {
+            (IncidentStatus::Investigating, IncidentUpdateStatus::Investigating)
+        };
+
+        let mut body = self.description;
+        if let Some(range) = &self.time_range {
+            body = if body.is_empty() { range.clone() } else { format!("{}\n{}", range, body) };
+        }
+
+        let mut affected = HashMap::new();
+        affected.insert("name".to_string(), self.service.clone());
+
+        Incident {
+            id: id.clone(),
+            name: format!("{}: {}", self.service, self.title),
+            status,
+            created_at: created_at.clone(),
+            updated_at: updated_at.clone(),
+            monitoring_at: None,
+            resolved_at,
+            impact: IncidentImpact::from(&self.severity),
+            shortlink: Some(GoogleAIStatusCrawler::URL.to_string()),
+            page_id: Some("google-ai-studio".to_string()),
+            incident_updates: vec![IncidentUpdate {
+                id: format!("{}-update", id),
+                status: update_status,
+                body,
+                display_at: Some(updated_at),
+                incident_id: Some(id),
+                affected_components: Some(vec![affected]),
+                delights_resolved: None,
+            }],
+            scheduled_for: None,
+            scheduled_until: None,
+            automated: Some(true),
+            classification: None,
+        }
+    }
+}
+
+/// 解析形如 `Jun 10, 2025, 9:15 AM – 11:20 AM PDT` 的时间范围
+fn parse_time_range(line: &str) -> Option<(DateTime<Utc>, Option<DateTime<Utc>>)> {
+    let (start_text, end_text) = [" – ", " - ", " — ", " to "]
+        .iter()
+        .find_map(|sep| line.split_once(sep))?;
+
+    let (end_text, offset) = split_time_zone(end_text.trim());
+    let (start_text, start_offset) = split_time_zone(start_text.trim());
+    let offset = start_offset.or(offset).unwrap_or_else(|| FixedOffset::east_opt(0).unwrap());
+
+    let start = parse_date_time(start_text)?;
+    let end = parse_date_time(end_text).or_else(|| {
+        parse_time(end_text).map(|time| {
+            let end = start.date().and_time(time);
+            // 只有时刻的结束时间早于开始时间，说明跨过了午夜
+            if end < start { end + chrono::Duration::days(1) } else { end }
+        })
+    });
+
+    let to_utc = |naive: NaiveDateTime| {
+        offset.from_local_datetime(&naive).single().map(|t| t.with_timezone(&Utc))
+    };
+
+    Some((to_utc(start)?, end.and_then(to_utc)))
+}
+
+/// 拆分末尾的时区缩写，返回剩余文本与对应偏移
+///
+/// `CST`、`IST` 等缩写在不同地区含义不同（如美国中部时间与中国标准时间），不做映射，
+/// 这类缩写与其他未知缩写一样被去掉，偏移按未知处理。
+fn split_time_zone(text: &str) -> (&str, Option<FixedOffset>) {
+    let Some((rest, zone)) = text.rsplit_once(' ') else {
+        return (text, None);
+    };
+    let hours = match zone {
+        "UTC" | "GMT" => 0,
+        "PST" => -8,
+        "PDT" => -7,
+        "EST" => -5,
+        "EDT" => -4,
+        "CET" => 1,
+        "CEST" => 2,
+        "AM" | "PM" => return (text, None),
+        _ if (2..=5).contains(&zone.len()) && zone.chars().all(|c| c.is_ascii_uppercase()) => {
+            debug!("⚠️ 无法确定时区缩写 {} 的偏移，按 UTC 处理", zone);
+            return (rest, None);
+        }
+        _ => return (text, None),
+    };
+    (rest, FixedOffset::east_opt(hours * 3600))
+}
+
+fn parse_date_time(text: &str) -> Option<NaiveDateTime> {
+    const FORMATS: [&str; 4] = [
+        "%b %d, %Y, %I:%M %p",
+        "%b %d, %Y %I:%M %p",
+        "%B %d, %Y, %I:%M %p",
+        "%Y-%m-%d %H:%M",
+    ];
+    FORMATS
+        .iter()
+        .find_map(|format| NaiveDateTime::parse_from_str(text, format).ok())
+        .or_else(|| {
+            // 仅有日期时按当天零点处理
+            NaiveDate::parse_from_str(text, "%b %d, %Y")
+                .ok()
+                .and_then(|date| date.and_hms_opt(0, 0, 0))
+        })
+}
+
+fn parse_time(text: &str) -> Option<NaiveTime> {
+    NaiveTime::parse_from_str(text, "%I:%M %p")
+        .or_else(|_| NaiveTime::parse_from_str(text, "%H:%M"))
+        .ok()
 }
 
 #[derive(Debug, Clone)]
@@ -94,6 +300,23 @@ impl GoogleAIStudioStatus {
             GoogleOverallStatus::Unknown => "Status Unknown",
         };
         
+        let timestamp = self.timestamp;
+        // 同一事件可能出现在多个时间格中，排序前按ID去重（没有解析出时间的事件开始时间相同，排序后未必相邻）
+        let mut seen = HashSet::new();
+        let mut incidents: Vec<Incident> = self.services
+            .iter()
+            .flat_map(|service| service.incidents.iter().cloned())
+            .map(|incident| incident.into_incident(&timestamp))
+            .filter(|incident| seen.insert(incident.id.clone()))
+            .collect();
+        // 未解决的事件排在前面，其余按开始时间倒序
+        incidents.sort_by(|a, b| {
+            (a.status == IncidentStatus::Resolved)
+                .cmp(&(b.status == IncidentStatus::Resolved))
+                .then_with(|| b.created_at.cmp(&a.created_at))
+        });
+        let ids = component_ids::assign_ids("google", self.services.iter().map(|s| s.name.as_str()));
+
         StatusPageSummary {
             page: Page {
                 id: "google-ai-studio".to_string(),
@@ -104,9 +327,10 @@ impl GoogleAIStudioStatus {
             },
             components: self.services
                 .into_iter()
+                .zip(ids)
                 .enumerate()
-                .map(|(i, service)| Component {
-                    id: format!("google-service-{}", i),
+                .map(|(i, (service, id))| Component {
+                    id,
                     name: service.name,
                     status: service.status.into(),
                     created_at: self.timestamp.to_rfc3339(),
@@ -118,7 +342,7 @@ impl GoogleAIStudioStatus {
                     only_show_if_degraded: false,
                 })
                 .collect(),
-            incidents: vec![],
+            incidents,
             scheduled_maintenances: vec![],
             status: OverallStatus {
                 indicator: self.overall_status.clone().into(),
@@ -132,105 +356,54 @@ impl GoogleAIStudioStatus {
 #[derive(Debug)]
 pub struct GoogleAIStatusCrawler {
     driver: Option<WebDriver>,
+    /// 本次爬取使用的选择器
+    selectors: Arc<GoogleSelectors>,
+    /// 当前爬取尝试的选择器自检报告
+    report: Mutex<ScrapeReport>,
 }
 
 impl GoogleAIStatusCrawler {
     const URL: &'static str = "https://aistudio.google.com/status";
+    const PROVIDER: &'static str = "Google";
     const MAX_WAIT_SECONDS: u64 = 45;
-    const CHROME_DRIVER_URL: &'static str = "http://localhost:9515";
     const RETRY_ATTEMPTS: u32 = 3;
+    /// 页面加载完成后等待前端应用渲染的时间
+    const SETTLE_DELAY: Duration = Duration::from_secs(2);
+    /// 检查事件对话框的最近天数
+    const INCIDENT_LOOKBACK_DAYS: usize = 7;
 
     /// 创建新的爬虫实例
     pub async fn new() -> Result<Self, Box<dyn Error + Send + Sync>> {
-        let driver = Self::create_webdriver().await?;
-        Ok(Self { driver: Some(driver) })
-    }
-
-    /// 创建 WebDriver 实例
-    async fn create_webdriver() -> Result<WebDriver, Box<dyn Error + Send + Sync>> {
-        let mut caps = DesiredCapabilities::chrome();
-        
-        // Chrome 启动参数
-        let chrome_args = [
-            "--headless=new",
-            "--no-sandbox",
-            "--disable-dev-shm-usage",
-            "--disable-gpu",
-            "--disable-web-security",
-            "--disable-features=VizDisplayCompositor",
-            "--window-size=1920,1080",
-            "--user-agent=Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/137.0.0.0 Safari/537.36",
-        ];
-        
-        for arg in chrome_args {
-            caps.add_arg(arg)?;
-        }
-        
-        caps.set_page_load_strategy(PageLoadStrategy::Normal)?;
-        
-        let driver = WebDriver::new(Self::CHROME_DRIVER_URL, caps).await
-            .map_err(|e| -> Box<dyn Error + Send + Sync> { 
-                format!("WebDriver初始化失败: {}。请确保ChromeDriver在{}运行", e, Self::CHROME_DRIVER_URL).into() 
-            })?;
-        
-        info!("✅ WebDriver 初始化完成");
-        Ok(driver)
-    }
-
-    /// 等待页面完全加载
-    async fn wait_for_page_ready(&self) -> Result<(), Box<dyn Error + Send + Sync>> {
-        let driver = self.driver.as_ref().ok_or("WebDriver未初始化")?;
-        
-        // 等待页面加载完成
-        for i in 0..10 {
-            let ready_state = driver.execute("return document.readyState", vec![]).await?;
-            
-            match ready_state.convert::<String>() {
-                Ok(state) if state == "complete" => {
-                    debug!("✅ 页面加载完成");
-                    break;
-                }
-                Ok(state) => {
-                    debug!("📊 页面状态: {}", state);
-                }
-                Err(_) => {
-                    debug!("⚠️ 无法获取页面状态");
-                }
-            }
-            
-            if i == 9 {
-                warn!("⚠️ 页面可能未完全加载，但继续执行");
-            }
-            
-            sleep(Duration::from_millis(500)).await;
-        }
-        
-        // 额外等待前端应用加载
-        sleep(Duration::from_secs(2)).await;
-        Ok(())
+        let driver = webdriver::create_webdriver().await?;
+        let selectors = selectors::current();
+        Ok(Self {
+            driver: Some(driver),
+            report: Mutex::new(Self::new_report(&selectors)),
+            selectors,
+        })
     }
 
     /// 获取整体状态
     async fn get_overall_status(&self) -> Result<GoogleOverallStatus, Box<dyn Error + Send + Sync>> {
         let driver = self.driver.as_ref().ok_or("WebDriver未初始化")?;
         
-        let selectors = [
-            "div.status.status-large.operational span:not(.material-symbols-outlined)",
-            "div.status.status-large span:not(.material-symbols-outlined)", 
-            ".status-page-container .status span",
-            "[class*='status'] span",
-        ];
-        
-        for selector in &selectors {
-            match driver.find(By::Css(*selector)).await {
+        for (i, selector) in self.selectors.overall_status.iter().enumerate() {
+            match driver.find(By::Css(selector)).await {
                 Ok(element) => {
                     let status_text = element.text().await?;
-                    if !status_text.trim().is_empty() {
+                    let matched = !status_text.trim().is_empty();
+                    self.record_selector("overall_status", selector, i == 0, matched);
+                    if matched {
                         debug!("✅ 使用选择器 {} 获取状态: {}", selector, status_text);
-                        return Ok(GoogleOverallStatus::from(status_text.as_str()));
+                        let status = GoogleOverallStatus::from(status_text.as_str());
+                        if status == GoogleOverallStatus::Unknown {
+                            self.flag_drift(format!("无法识别的整体状态文本: {}", status_text.trim()));
+                        }
+                        return Ok(status);
                     }
                 }
                 Err(_) => {
+                    self.record_selector("overall_status", selector, i == 0, false);
                     debug!("⚠️ 选择器 {} 未找到元素", selector);
                     continue;
                 }
@@ -238,6 +411,7 @@ impl GoogleAIStatusCrawler {
         }
         
         warn!("⚠️ 无法获取整体状态，返回未知状态");
+        self.flag_drift("所有整体状态选择器均未命中");
         Ok(GoogleOverallStatus::Unknown)
     }
 
@@ -247,21 +421,19 @@ impl GoogleAIStatusCrawler {
         let mut services = Vec::new();
         
         // 查找服务容器
-        let container_selectors = [
-            "div.dashboards-container",
-            ".dashboards-container",
-            "[class*='dashboards']",
-        ];
-        
         let mut dashboards_container = None;
-        for selector in &container_selectors {
-            match driver.find(By::Css(*selector)).await {
+        for (i, selector) in self.selectors.dashboards_container.iter().enumerate() {
+            match driver.find(By::Css(selector)).await {
                 Ok(container) => {
+                    self.record_selector("dashboards_container", selector, i == 0, true);
                     dashboards_container = Some(container);
                     debug!("✅ 找到容器: {}", selector);
                     break;
                 }
-                Err(_) => continue,
+                Err(_) => {
+                    self.record_selector("dashboards_container", selector, i == 0, false);
+                    continue;
+                }
             }
         }
         
@@ -270,14 +442,16 @@ impl GoogleAIStatusCrawler {
 
         // 获取服务名称和状态面板
         let service_elements = dashboards_container
-            .find_all(By::Css("div[data-testid='service-name']"))
+            .find_all(By::Css(&self.selectors.service_name))
             .await
             .unwrap_or_default();
+        self.record_selector("service_name", &self.selectors.service_name, true, !service_elements.is_empty());
             
         let status_dashboards = dashboards_container
-            .find_all(By::Css("ms-status-dashboard"))
+            .find_all(By::Css(&self.selectors.status_dashboard))
             .await
             .unwrap_or_default();
+        self.record_selector("status_dashboard", &self.selectors.status_dashboard, true, !status_dashboards.is_empty());
 
         if service_elements.is_empty() {
             warn!("⚠️ 未找到任何服务元素");
@@ -287,6 +461,8 @@ impl GoogleAIStatusCrawler {
         if service_elements.len() != status_dashboards.len() {
             warn!("⚠️ 服务数量({})与状态面板数量({})不匹配", 
                 service_elements.len(), status_dashboards.len());
+            self.flag_drift(format!("服务数量({})与状态面板数量({})不匹配",
+                service_elements.len(), status_dashboards.len()));
         }
 
         let min_length = std::cmp::min(service_elements.len(), status_dashboards.len());
@@ -295,11 +471,13 @@ impl GoogleAIStatusCrawler {
             match service_elements[i].text().await {
                 Ok(service_name) if !service_name.trim().is_empty() => {
                     let latest_status = self.get_service_latest_status(&status_dashboards[i], &service_name).await?;
+                    let incidents = self.get_service_incidents(&status_dashboards[i], &service_name).await;
                     
-                    info!("✅ Google服务 {}: {:?}", service_name, latest_status);
+                    info!("✅ Google服务 {}: {:?}, 事件数: {}", service_name, latest_status, incidents.len());
                     services.push(GoogleServiceInfo {
                         name: service_name,
                         status: latest_status,
+                        incidents,
                     });
                 }
                 _ => {
@@ -320,9 +498,12 @@ impl GoogleAIStatusCrawler {
     ) -> Result<GoogleServiceStatus, Box<dyn Error + Send + Sync>> {
         
         let timeline_days_result = status_dashboard
-            .find_all(By::Css("ms-status-dashboard-day .xap-inline-dialog.timeline-day"))
+            .find_all(By::Css(&self.selectors.timeline_day))
             .await;
 
+        let found = matches!(&timeline_days_result, Ok(days) if !days.is_empty());
+        self.record_selector("timeline_day", &self.selectors.timeline_day, true, found);
+
         match timeline_days_result {
             Ok(timeline_days) if !timeline_days.is_empty() => {
                 // 获取最后一天的状态（最新状态）
@@ -330,7 +511,7 @@ impl GoogleAIStatusCrawler {
                 
                 match last_day.attr("class").await {
                     Ok(Some(class_name)) => {
-                        let status = GoogleServiceStatus::from(class_name.as_str());
+                        let status = GoogleServiceStatus::from_class(&class_name, &self.selectors.severity);
                         debug!("🔍 服务 {} 状态类: {} -> {:?}", service_name, class_name, status);
                         Ok(status)
                     }
@@ -347,6 +528,76 @@ impl GoogleAIStatusCrawler {
         }
     }
 
+    /// 获取单个服务最近几天的事件描述
+    async fn get_service_incidents(
+        &self,
+        status_dashboard: &WebElement,
+        service_name: &str,
+    ) -> Vec<GoogleIncidentInfo> {
+        let timeline_days = status_dashboard
+            .find_all(By::Css(&self.selectors.timeline_day))
+            .await
+            .unwrap_or_default();
+
+        let mut incidents = Vec::new();
+        for day in timeline_days.iter().rev().take(Self::INCIDENT_LOOKBACK_DAYS) {
+            let severity = match day.attr("class").await {
+                Ok(Some(class_name)) => GoogleServiceStatus::from_class(&class_name, &self.selectors.severity),
+                _ => continue,
+            };
+            if severity == GoogleServiceStatus::Operational {
+                continue;
+            }
+
+            match self.read_incident_dialog(day).await {
+                Ok(Some(text)) => {
+                    match GoogleIncidentInfo::parse_dialog(service_name, severity, &text) {
+                        Some(incident) => {
+                            debug!("🔍 服务 {} 事件: {}", service_name, incident.title);
+                            incidents.push(incident);
+                        }
+                        None => debug!("⚠️ 服务 {} 的事件对话框内容无法解析", service_name),
+                    }
+                }
+                Ok(None) => debug!("⚠️ 服务 {} 的事件对话框未找到", service_name),
+                Err(e) => debug!("⚠️ 打开服务 {} 的事件对话框失败: {}", service_name, e),
+            }
+        }
+
+        incidents
+    }
+
+    /// 点击时间轴中的某一天并读取弹出对话框的文本
+    async fn read_incident_dialog(
+        &self,
+        day: &WebElement,
+    ) -> Result<Option<String>, Box<dyn Error + Send + Sync>> {
+        let driver = self.driver.as_ref().ok_or("WebDriver未初始化")?;
+
+        day.scroll_into_view().await?;
+        driver.execute("arguments[0].click();", vec![day.to_json()?]).await?;
+        sleep(Duration::from_millis(500)).await;
+
+        let mut content = None;
+        for (i, selector) in self.selectors.incident_dialog.iter().enumerate() {
+            if let Ok(dialog) = driver.find(By::Css(selector)).await {
+                let text = dialog.text().await?;
+                if !text.trim().is_empty() {
+                    self.record_selector("incident_dialog", selector, i == 0, true);
+                    content = Some(text);
+                    break;
+                }
+            }
+            self.record_selector("incident_dialog", selector, i == 0, false);
+        }
+
+        // 关闭对话框，避免遮挡下一次点击
+        let _ = driver.action_chain().send_keys(Key::Escape).perform().await;
+        sleep(Duration::from_millis(200)).await;
+
+        Ok(content)
+    }
+
     /// 单次获取状态的实现
     async fn fetch_status_with_retry(&self) -> Result<StatusPageSummary, Box<dyn Error + Send + Sync>> {
         let driver = self.driver.as_ref().ok_or("WebDriver未初始化")?;
@@ -356,23 +607,18 @@ impl GoogleAIStatusCrawler {
         driver.goto(Self::URL).await?;
         
         // 等待页面完全加载
-        self.wait_for_page_ready().await?;
+        webdriver::wait_for_page_ready(driver, Self::SETTLE_DELAY).await?;
         
         // 等待主要容器出现
         let container_found = timeout(
             Duration::from_secs(Self::MAX_WAIT_SECONDS),
             async {
-                let selectors = [
-                    "div.status-page-container",
-                    ".status-page-container", 
-                    "[class*='status-page']",
-                    "body > *",
-                ];
-                
-                for selector in &selectors {
-                    if let Ok(element) = driver.find(By::Css(*selector)).await {
+                for (i, selector) in self.selectors.page_container.iter().enumerate() {
+                    let found = driver.find(By::Css(selector)).await.is_ok();
+                    self.record_selector("page_container", selector, i == 0, found);
+                    if found {
                         debug!("✅ 找到容器: {}", selector);
-                        return Ok(element);
+                        return Ok(());
                     }
                 }
                 
@@ -381,7 +627,7 @@ impl GoogleAIStatusCrawler {
         ).await;
 
         match container_found {
-            Ok(Ok(_)) => {
+            Ok(Ok(())) => {
                 info!("✅ 页面容器加载完成");
             }
             _ => {
@@ -392,8 +638,12 @@ impl GoogleAIStatusCrawler {
         // 获取状态信息
         let overall_status = self.get_overall_status().await?;
         let services = self.get_services_status().await?;
+        if services.is_empty() {
+            self.flag_drift("未获取到任何服务");
+        }
         
-        info!("📊 Google状态获取完成 - 整体: {:?}, 服务数: {}", overall_status, services.len());
+        info!("📊 Google状态获取完成 - 整体: {:?}, 服务数: {}, 事件数: {}",
+            overall_status, services.len(), services.iter().map(|s| s.incidents.len()).sum::<usize>());
         
         let google_status = GoogleAIStudioStatus {
             overall_status,
@@ -408,17 +658,22 @@ impl GoogleAIStatusCrawler {
     pub async fn fetch_status(&self) -> Result<StatusPageSummary, Box<dyn Error + Send + Sync>> {
         let driver = self.driver.as_ref().ok_or("WebDriver未初始化")?;
         
-        info!("📊 开始爬取 Google AI Studio 状态");
+        info!("📊 开始爬取 Google AI Studio 状态 (选择器版本 {})", self.selectors.version);
         
         // 实现重试机制
         for attempt in 1..=Self::RETRY_ATTEMPTS {
+            *self.report.lock().unwrap() = Self::new_report(&self.selectors);
             match self.fetch_status_with_retry().await {
                 Ok(status) => {
                     info!("✅ Google状态获取成功 (尝试 {}/{})", attempt, Self::RETRY_ATTEMPTS);
+                    self.finish_report(None).await;
                     return Ok(status);
                 }
                 Err(e) => {
                     error!("❌ 第 {} 次尝试失败: {}", attempt, e);
+                    if attempt == Self::RETRY_ATTEMPTS {
+                        self.finish_report(Some(e.to_string())).await;
+                    }
                     if attempt < Self::RETRY_ATTEMPTS {
                         warn!("🔄 等待 3 秒后重试...");
                         sleep(Duration::from_secs(3)).await;
@@ -435,6 +690,40 @@ impl GoogleAIStatusCrawler {
         unreachable!()
     }
 
+    /// 创建带选择器版本的自检报告
+    fn new_report(selectors: &GoogleSelectors) -> ScrapeReport {
+        let mut report = ScrapeReport::new(Self::PROVIDER, Self::URL);
+        report.selectors_version = Some(selectors.version.clone());
+        report
+    }
+
+    /// 记录选择器匹配结果
+    fn record_selector(&self, purpose: &str, selector: &str, expected: bool, matched: bool) {
+        self.report.lock().unwrap().record(purpose, selector, expected, matched);
+    }
+
+    /// 记录布局漂移迹象
+    fn flag_drift(&self, reason: impl Into<String>) {
+        self.report.lock().unwrap().flag_drift(reason);
+    }
+
+    /// 汇总本次爬取的自检报告，失败时保存页面源码与截图
+    async fn finish_report(&self, error: Option<String>) {
+        let mut report = self.report.lock().unwrap().clone();
+        report.finalize(error);
+
+        if report.is_failure() {
+            if let Some(driver) = self.driver.as_ref() {
+                let (page_source, screenshot) = webdriver::capture_page(driver).await;
+                if let Err(e) = diagnostics::save_snapshot(&mut report, page_source, screenshot) {
+                    error!("❌ 保存诊断快照失败: {}", e);
+                }
+            }
+        }
+
+        diagnostics::publish(report);
+    }
+
     /// 关闭爬虫并清理资源
     pub async fn close(mut self) -> Result<(), Box<dyn Error + Send + Sync>> {
         if let Some(driver) = self.driver.take() {
@@ -447,4 +736,82 @@ impl GoogleAIStatusCrawler {
         }
         Ok(())
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn utc(text: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(text).unwrap().with_timezone(&Utc)
+    }
+
+    #[test]
+    fn time_range_with_zone() {
+        let (start, end) = parse_time_range("Jun 10, 2025, 9:15 AM – 11:20 AM PDT").unwrap();
+        assert_eq!(start, utc("2025-06-10T16:15:00Z"));
+        assert_eq!(end, Some(utc("2025-06-10T18:20:00Z")));
+
+        let (start, end) = parse_time_range("Jun 10, 2025, 9:15 AM - Jun 11, 2025, 2:00 AM UTC").unwrap();
+        assert_eq!(start, utc("2025-06-10T09:15:00Z"));
+        assert_eq!(end, Some(utc("2025-06-11T02:00:00Z")));
+    }
+
+    #[test]
+    fn time_range_crossing_midnight() {
+        let (start, end) = parse_time_range("Jun 10, 2025, 11:30 PM – 1:10 AM PDT").unwrap();
+        assert_eq!(start, utc("2025-06-11T06:30:00Z"));
+        assert_eq!(end, Some(utc("2025-06-11T08:10:00Z")));
+    }
+
+    #[test]
+    fn ambiguous_zone_is_not_mapped() {
+        let (start, end) = parse_time_range("Jun 10, 2025, 9:15 AM – 11:20 AM CST").unwrap();
+        assert_eq!(start, utc("2025-06-10T09:15:00Z"));
+        assert_eq!(end, Some(utc("2025-06-10T11:20:00Z")));
+    }
+
+    #[test]
+    fn time_range_without_end_or_range() {
+        let (start, end) = parse_time_range("Jun 10, 2025, 9:15 AM – Ongoing").unwrap();
+        assert_eq!(start, utc("2025-06-10T09:15:00Z"));
+        assert_eq!(end, None);
+        assert!(parse_time_range("Elevated error rates").is_none());
+        assert!(parse_time_range("Gemini API - elevated errors").is_none());
+    }
+
+    #[test]
+    fn dialog_fields() {
+        let text = "Gemini API\n  Elevated error rates\n\nJun 10, 2025, 9:15 AM – 11:20 AM PDT\nWe are investigating.\nFixed.\n";
+        let incident = GoogleIncidentInfo::parse_dialog("Gemini API", GoogleServiceStatus::PartialOutage, text).unwrap();
+        assert_eq!(incident.title, "Elevated error rates");
+        assert_eq!(incident.time_range.as_deref(), Some("Jun 10, 2025, 9:15 AM – 11:20 AM PDT"));
+        assert_eq!(incident.started_at, Some(utc("2025-06-10T16:15:00Z")));
+        assert_eq!(incident.ended_at, Some(utc("2025-06-10T18:20:00Z")));
+        assert_eq!(incident.description, "We are investigating.\nFixed.");
+
+        let ongoing = GoogleIncidentInfo::parse_dialog("Gemini API", GoogleServiceStatus::Degraded, "Gemini API\nSlow responses").unwrap();
+        assert_eq!(ongoing.title, "Slow responses");
+        assert_eq!(ongoing.started_at, None);
+        assert!(ongoing.into_incident(&utc("2025-06-10T00:00:00Z")).resolved_at.is_none());
+
+        assert!(GoogleIncidentInfo::parse_dialog("Gemini API", GoogleServiceStatus::Unknown, "Gemini API\n\n").is_none());
+    }
+
+    #[test]
+    fn duplicate_incidents_without_time_are_removed() {
+        let dialog = |title: &str| {
+            GoogleIncidentInfo::parse_dialog("Gemini API", GoogleServiceStatus::Degraded, &format!("Gemini API\n{}", title)).unwrap()
+        };
+        let status = GoogleAIStudioStatus {
+            overall_status: GoogleOverallStatus::DegradedPerformance,
+            services: vec![GoogleServiceInfo {
+                name: "Gemini API".to_string(),
+                status: GoogleServiceStatus::Degraded,
+                incidents: vec![dialog("Slow responses"), dialog("Elevated errors"), dialog("Slow responses")],
+            }],
+            timestamp: utc("2025-06-10T00:00:00Z"),
+        };
+        let names: Vec<String> = status.into_status_page_summary().incidents.into_iter().map(|i| i.name).collect();
+        assert_eq!(names, ["Gemini API: Slow responses", "Gemini API: Elevated errors"]);
+    }
+}
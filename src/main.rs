@@ -8,21 +8,54 @@ use axum::{
 };
 use tower_http::{cors::CorsLayer, services::ServeDir};
 
+#[cfg(feature = "browser")]
+mod admin;
+mod alerts;
+mod api;
+mod auth;
+mod classification;
+mod component_ids;
 mod config;
+#[cfg(feature = "browser")]
+mod diagnostics;
+mod discrepancy;
+mod discovery;
 mod fetcher;
+mod model_catalog;
+mod model_listing;
 mod models;
+mod netcheck;
+mod observations;
+mod probes;
+#[cfg(feature = "browser")]
 mod google;
+#[cfg(feature = "browser")]
+mod selectors;
+mod sources;
+#[cfg(feature = "browser")]
+mod webdriver;
 
 use config::CONFIG;
 
 #[tokio::main]
 async fn main() {
     tracing_subscriber::fmt::init();
+
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if let Some(command) = args.first() {
+        run_command(command, &args[1..]).await;
+        return;
+    }
+
     fs::create_dir_all(CONFIG.frontend_dir).expect("创建前端目录失败");
     
     println!("🔄 启动大模型供应商状态监控服务...");
     println!("📂 状态文件将保存到: {}", CONFIG.status_file);
     println!("🌐 前端服务将在 {} 启动", CONFIG.local_url());
+    #[cfg(feature = "browser")]
+    println!("🎯 Google 选择器版本: {}", selectors::current().version);
+    #[cfg(not(feature = "browser"))]
+    println!("ℹ️ 当前构建未启用 browser 特性，Google 等爬虫数据源不可用");
     
     // 初始运行
     fetch_and_save().await;
@@ -36,15 +69,99 @@ async fn main() {
         }
     });
     
+    // 启动主动探测任务
+    tokio::spawn(probes::run_forever());
+    
+    // 启动网络连通性检查任务
+    tokio::spawn(netcheck::run_forever());
+    
+    // 启动官方状态与实测差异检测任务
+    tokio::spawn(discrepancy::run_forever());
+    
+    // 启动模型列表追踪任务
+    tokio::spawn(model_listing::run_forever());
+    
     // 启动Web服务器
     start_web_server().await;
 }
 
+/// 执行命令行子命令
+async fn run_command(command: &str, args: &[String]) {
+    match (command, args) {
+        ("discover", [url]) => match discovery::discover(url).await {
+            Ok(result) => {
+                for probe in &result.probes {
+                    let mark = if probe.ok { "✅" } else { "❌" };
+                    println!("{} {:<12} {} - {}", mark, probe.platform, probe.endpoint, probe.detail);
+                }
+                match &result.provider {
+                    Some(provider) => {
+                        println!("\n🎯 识别为 {}，可将以下配置加入 {}:", result.platform.as_deref().unwrap_or_default(), CONFIG.providers_file);
+                        println!("{}", serde_json::to_string_pretty(provider).unwrap());
+                    }
+                    None => {
+                        println!("\n⚠️ 未能识别状态页面平台");
+                        std::process::exit(1);
+                    }
+                }
+            }
+            Err(e) => {
+                eprintln!("❌ 识别失败: {}", e);
+                std::process::exit(1);
+            }
+        },
+        ("netcheck", urls) => {
+            let targets = if urls.is_empty() {
+                netcheck::targets()
+            } else {
+                urls.iter()
+                    .map(|url| netcheck::NetCheckTarget { provider: String::new(), url: url.clone() })
+                    .collect()
+            };
+            let client = netcheck::client().expect("创建HTTP客户端失败");
+            let mut failed = false;
+            for result in netcheck::check_all(&client, targets).await {
+                failed |= result.verdict != netcheck::Verdict::Reachable;
+                println!("\n{} [{:?}]", format!("{} {}", result.provider, result.url).trim(), result.verdict);
+                let steps = [("DNS", &result.dns), ("TCP", &result.tcp), ("TLS", &result.tls), ("HTTP", &result.http)];
+                for (name, step) in steps {
+                    if let Some(step) = step {
+                        let mark = if step.ok { "✅" } else { "❌" };
+                        println!("  {} {:<4} {:>5}ms  {}", mark, name, step.ms, step.detail);
+                    }
+                }
+                if let Some(cert) = &result.certificate {
+                    println!("  📜 {} · 签发者 {} · {} 天后过期", cert.subject, cert.issuer, cert.days_left);
+                }
+            }
+            if failed {
+                std::process::exit(1);
+            }
+        }
+        _ => {
+            eprintln!("用法:");
+            eprintln!("  LM_service_status_dashboard                  启动监控服务");
+            eprintln!("  LM_service_status_dashboard discover <URL>   识别状态页面平台并生成供应商配置");
+            eprintln!("  LM_service_status_dashboard netcheck [URL]   检查到供应商 API 的网络连通性（默认检查 config/netcheck.json 中的目标）");
+            std::process::exit(2);
+        }
+    }
+}
+
 /// 启动Web服务器
 async fn start_web_server() {
     let app = Router::new()
         // 特殊处理status.json，添加防缓存头
-        .route("/status.json", get(serve_status_json))
+        .route("/status.json", get(serve_status_json));
+
+    // 公共接口
+    let app = app.merge(api::routes());
+
+    // 爬虫诊断与选择器管理接口
+    #[cfg(feature = "browser")]
+    let app = app.merge(admin::routes());
+
+    let app = app
         // 服务整个frontend目录的所有其他文件
         .nest_service("/", get_service(ServeDir::new(CONFIG.frontend_dir)))
         .layer(CorsLayer::permissive());
@@ -80,10 +197,18 @@ async fn serve_status_json() -> Result<([(header::HeaderName, &'static str); 2],
 async fn fetch_and_save() {
     println!("\n🔄 开始获取供应商状态 [{}]", Utc::now().format("%Y-%m-%d %H:%M:%S"));
     
-    let results = fetcher::get_all_llm_statuses().await;
+    let custom = sources::load_providers();
+    let results = fetcher::get_all_llm_statuses(&custom).await;
+    discrepancy::record_official(&results);
     let output = serde_json::json!({
         "timestamp": Utc::now().to_rfc3339(),
-        "data": results
+        "data": results,
+        "providers": sources::provider_links(&custom),
+        "probes": probes::results_by_provider(),
+        "network": netcheck::results_by_provider(),
+        "discrepancies": discrepancy::active(),
+        "observed": observations::health_by_provider(Utc::now()),
+        "models": model_listing::summary_by_provider(Utc::now())
     });
     
     if let Err(e) = fs::write(CONFIG.status_file, serde_json::to_string_pretty(&output).unwrap()) {
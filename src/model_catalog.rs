@@ -0,0 +1,320 @@
+//! 模型目录：把具体模型映射到影响它的供应商组件
+//!
+//! 业务按模型路由（GPT-4o、Claude Sonnet 等），而状态页面按组件发布状态。
+//! 目录在 `config/model_catalog.json` 中维护，每次查询时重新读取；
+//! 模型的有效状态由所映射组件中最严重的状态决定，并附上相关的未结束事件。
+
+use crate::component_ids::normalize;
+use crate::config::CONFIG;
+use crate::fetcher;
+use crate::models::{ComponentStatus, Incident, IncidentImpact, IncidentStatus, StatusPageSummary};
+use crate::sources::mentions;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use tracing::error;
+
+#[derive(Debug, Default, Deserialize)]
+struct CatalogFile {
+    #[serde(default)]
+    models: Vec<ModelEntry>,
+}
+
+/// 目录中的模型
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ModelEntry {
+    /// 接口中使用的模型ID，如 `gpt-4o`
+    pub id: String,
+    /// 显示名称，未配置时使用ID
+    #[serde(default)]
+    pub name: Option<String>,
+    /// 供应商名称，即 status.json 中的键
+    pub provider: String,
+    /// 影响该模型的组件名称或组件ID
+    #[serde(default)]
+    pub components: Vec<String>,
+    /// 事件中可能出现的其他叫法，模型ID与显示名称无需重复列出
+    #[serde(default)]
+    pub aliases: Vec<String>,
+}
+
+impl ModelEntry {
+    fn display_name(&self) -> &str {
+        self.name.as_deref().unwrap_or(&self.id)
+    }
+
+    /// 事件文本中用于识别该模型的词
+    pub fn terms(&self) -> impl Iterator<Item = &str> {
+        std::iter::once(self.id.as_str()).chain(self.name.as_deref()).chain(self.aliases.iter().map(String::as_str))
+    }
+}
+
+/// 模型映射到的组件当前状态
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct ComponentState {
+    pub id: String,
+    pub name: String,
+    pub status: ComponentStatus,
+}
+
+/// 与模型相关的未结束事件
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct ModelIncident {
+    pub id: String,
+    pub name: String,
+    pub status: IncidentStatus,
+    pub impact: IncidentImpact,
+    pub updated_at: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub shortlink: Option<String>,
+}
+
+/// 模型的有效状态
+#[derive(Debug, Clone, Serialize)]
+pub struct ModelStatus {
+    pub id: String,
+    pub name: String,
+    pub provider: String,
+    pub status: ComponentStatus,
+    pub components: Vec<ComponentState>,
+    /// 目录中配置了但供应商当前未提供的组件，通常说明组件已改名
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub missing_components: Vec<String>,
+    pub incidents: Vec<ModelIncident>,
+    /// 供应商状态的更新时间
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub updated_at: Option<String>,
+    /// 供应商状态获取失败或尚未获取时的原因
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// 读取模型目录，文件不存在时为空
+pub fn load_catalog() -> Vec<ModelEntry> {
+    match fs::read_to_string(CONFIG.model_catalog_file) {
+        Ok(content) => serde_json::from_str::<CatalogFile>(&content)
+            .unwrap_or_else(|e| {
+                error!("❌ 解析模型目录 {} 失败: {}", CONFIG.model_catalog_file, e);
+                CatalogFile::default()
+            })
+            .models,
+        Err(_) => Vec::new(),
+    }
+}
+
+/// 目录中全部模型的有效状态
+pub fn all_statuses() -> Vec<ModelStatus> {
+    let statuses = fetcher::latest_statuses();
+    load_catalog().iter().map(|entry| evaluate(entry, lookup(&statuses, &entry.provider))).collect()
+}
+
+/// 指定模型的有效状态，模型ID不区分大小写
+pub fn status(id: &str) -> Option<ModelStatus> {
+    let entry = load_catalog().into_iter().find(|m| m.id.eq_ignore_ascii_case(id))?;
+    let statuses = fetcher::latest_statuses();
+    Some(evaluate(&entry, lookup(&statuses, &entry.provider)))
+}
+
+fn lookup<'a>(statuses: &'a HashMap<String, serde_json::Value>, provider: &str) -> Option<&'a serde_json::Value> {
+    statuses.iter().find(|(name, _)| name.eq_ignore_ascii_case(provider)).map(|(_, value)| value)
+}
+
+/// 根据供应商状态计算模型的有效状态
+pub fn evaluate(entry: &ModelEntry, provider_status: Option<&serde_json::Value>) -> ModelStatus {
+    let mut result = ModelStatus {
+        id: entry.id.clone(),
+        name: entry.display_name().to_string(),
+        provider: entry.provider.clone(),
+        status: ComponentStatus::Unknown,
+        components: Vec::new(),
+        missing_components: Vec::new(),
+        incidents: Vec::new(),
+        updated_at: None,
+        error: None,
+    };
+
+    let summary = match provider_status {
+        None => {
+            result.error = Some("尚未获取该供应商的状态".to_string());
+            return result;
+        }
+        Some(value) => match value.get("error") {
+            Some(error) => {
+                result.error = Some(error.as_str().unwrap_or("获取失败").to_string());
+                return result;
+            }
+            None => match serde_json::from_value::<StatusPageSummary>(value.clone()) {
+                Ok(summary) => summary,
+                Err(e) => {
+                    result.error = Some(format!("无法解析供应商状态: {}", e));
+                    return result;
+                }
+            },
+        },
+    };
+    result.updated_at = Some(summary.page.updated_at.clone());
+
+    let wanted: Vec<(String, &str)> = entry.components.iter().map(|c| (normalize(c), c.as_str())).collect();
+    for (key, configured) in &wanted {
+        let matched: Vec<_> = summary
+            .components
+            .iter()
+            .filter(|c| normalize(&c.name) == *key || c.id == *configured)
+            .collect();
+        if matched.is_empty() {
+            result.missing_components.push(configured.to_string());
+        }
+        for component in matched {
+            if !result.components.iter().any(|c| c.id == component.id) {
+                result.components.push(ComponentState {
+                    id: component.id.clone(),
+                    name: component.name.clone(),
+                    status: component.status.clone(),
+                });
+            }
+        }
+    }
+
+    let component_keys: Vec<&str> = wanted.iter().map(|(key, _)| key.as_str()).collect();
+    result.incidents = summary
+        .incidents
+        .iter()
+        .filter(|incident| incident.status != IncidentStatus::Resolved)
+        .filter(|incident| affects_components(incident, &component_keys) || mentions_model(incident, entry))
+        .map(|incident| ModelIncident {
+            id: incident.id.clone(),
+            name: incident.name.clone(),
+            status: incident.status.clone(),
+            impact: incident.impact.clone(),
+            updated_at: incident.updated_at.clone(),
+            shortlink: incident.shortlink.clone(),
+        })
+        .collect();
+
+    result.status = if let Some(worst) = result.components.iter().map(|c| &c.status).max_by_key(|s| s.severity()) {
+        worst.clone()
+    } else if let Some(worst) = result.incidents.iter().map(|i| impact_status(&i.impact)).max_by_key(|s| s.severity()) {
+        // 映射的组件都不存在（或只按名称匹配事件）时，由相关事件的影响程度推导
+        worst
+    } else if entry.components.is_empty() {
+        ComponentStatus::Operational
+    } else {
+        ComponentStatus::Unknown
+    };
+    result
+}
+
+/// 事件更新中列出的受影响组件是否包含映射的组件
+fn affects_components(incident: &Incident, component_keys: &[&str]) -> bool {
+    incident
+        .incident_updates
+        .iter()
+        .filter_map(|u| u.affected_components.as_ref())
+        .flatten()
+        .filter_map(|c| c.get("name"))
+        .any(|name| component_keys.contains(&normalize(name).as_str()))
+}
+
+/// 事件标题或更新内容是否提到该模型（包括事件分类提取到的模型）
+fn mentions_model(incident: &Incident, entry: &ModelEntry) -> bool {
+    if let Some(classification) = &incident.classification {
+        if entry.terms().any(|term| crate::classification::has_tag(&classification.models, term)) {
+            return true;
+        }
+    }
+    let texts = std::iter::once(incident.name.as_str()).chain(incident.incident_updates.iter().map(|u| u.body.as_str()));
+    let texts: Vec<String> = texts.map(str::to_lowercase).collect();
+    entry.terms().map(str::to_lowercase).any(|term| texts.iter().any(|text| mentions(text, &term)))
+}
+
+/// 事件影响程度对应的组件状态
+fn impact_status(impact: &IncidentImpact) -> ComponentStatus {
+    match impact {
+        IncidentImpact::Critical => ComponentStatus::MajorOutage,
+        IncidentImpact::Major => ComponentStatus::PartialOutage,
+        IncidentImpact::Minor | IncidentImpact::None | IncidentImpact::Unknown => ComponentStatus::DegradedPerformance,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(components: &[&str], aliases: &[&str]) -> ModelEntry {
+        ModelEntry {
+            id: "gpt-4o".to_string(),
+            name: Some("GPT-4o".to_string()),
+            provider: "OpenAI".to_string(),
+            components: components.iter().map(|s| s.to_string()).collect(),
+            aliases: aliases.iter().map(|s| s.to_string()).collect(),
+        }
+    }
+
+    fn incident(id: &str, name: &str, status: &str, affected: Option<&str>, body: &str) -> serde_json::Value {
+        let affected = affected.map(|name| serde_json::json!([{ "code": "x", "name": name }]));
+        serde_json::json!({
+            "id": id, "name": name, "status": status, "created_at": "2025-06-01T00:00:00Z",
+            "updated_at": "2025-06-01T00:10:00Z", "impact": "minor", "incident_updates": [{
+                "id": format!("{}-u1", id), "status": status, "body": body, "affected_components": affected
+            }]
+        })
+    }
+
+    fn provider(incidents: Vec<serde_json::Value>) -> serde_json::Value {
+        let component = |id: &str, name: &str, status: &str| serde_json::json!({
+            "id": id, "name": name, "status": status, "created_at": "", "updated_at": "", "position": 0
+        });
+        serde_json::json!({
+            "page": { "id": "openai", "name": "OpenAI", "url": "https://status.openai.com", "updated_at": "2025-06-01T00:10:00Z" },
+            "components": [
+                component("openai-chat-completions", "Chat Completions", "degraded_performance"),
+                component("openai-responses", "Responses", "operational"),
+                component("openai-chatgpt", "ChatGPT", "major_outage")
+            ],
+            "incidents": incidents,
+            "status": { "indicator": "major", "description": "Partial outage" }
+        })
+    }
+
+    #[test]
+    fn worst_mapped_component_wins() {
+        let status = evaluate(&entry(&["chat completions", "openai-responses", "Batch"], &[]), Some(&provider(vec![])));
+        assert_eq!(status.status, ComponentStatus::DegradedPerformance);
+        let names: Vec<_> = status.components.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, ["Chat Completions", "Responses"]);
+        assert_eq!(status.missing_components, ["Batch"]);
+    }
+
+    #[test]
+    fn relevant_active_incidents_only() {
+        let incidents = vec![
+            incident("a", "Elevated errors", "investigating", Some("Chat Completions"), "Investigating"),
+            incident("b", "Slow responses for GPT-4o", "monitoring", None, "A fix has been applied"),
+            incident("c", "Login issues", "identified", Some("ChatGPT"), "Users cannot log in"),
+            incident("d", "Errors on gpt-4o-mini", "investigating", None, "Only gpt-4o-mini is affected"),
+            incident("e", "Elevated errors", "resolved", Some("Chat Completions"), "Resolved"),
+        ];
+        let status = evaluate(&entry(&["Chat Completions"], &[]), Some(&provider(incidents)));
+        let ids: Vec<_> = status.incidents.iter().map(|i| i.id.as_str()).collect();
+        assert_eq!(ids, ["a", "b"]);
+    }
+
+    #[test]
+    fn incidents_decide_when_no_component_is_mapped() {
+        let incidents = vec![incident("b", "Degraded 4o", "investigating", None, "Elevated latency for 4o.")];
+        let status = evaluate(&entry(&[], &["4o"]), Some(&provider(incidents)));
+        assert_eq!(status.status, ComponentStatus::DegradedPerformance);
+
+        assert_eq!(evaluate(&entry(&[], &[]), Some(&provider(vec![]))).status, ComponentStatus::Operational);
+        assert_eq!(evaluate(&entry(&["Batch"], &[]), Some(&provider(vec![]))).status, ComponentStatus::Unknown);
+    }
+
+    #[test]
+    fn provider_failures_are_reported() {
+        let failed = serde_json::json!({ "error": "HTTP 错误: 503", "status": "failed" });
+        let status = evaluate(&entry(&["Chat Completions"], &[]), Some(&failed));
+        assert_eq!(status.status, ComponentStatus::Unknown);
+        assert_eq!(status.error.as_deref(), Some("HTTP 错误: 503"));
+        assert!(evaluate(&entry(&[], &[]), None).error.is_some());
+    }
+}
@@ -0,0 +1,495 @@
+//! 模型上下线追踪
+//!
+//! 定期调用各供应商的模型列表接口（`/v1/models` 风格），将模型ID集合保存到数据目录，
+//! 模型出现或消失时记录事件并发送告警，便于及早发现模型下线。
+
+use crate::alerts;
+use crate::config::CONFIG;
+use crate::probes::{self, ApiKind};
+use chrono::{DateTime, Duration as ChronoDuration, Utc};
+use reqwest::{Client, ClientBuilder};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::error::Error;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::{LazyLock, Mutex};
+use std::time::{Duration, Instant};
+use tracing::{error, info, warn};
+
+/// 连续多少次未出现在列表中才视为下线，避免接口偶发返回不完整列表
+const MISSING_CONFIRMATIONS: u32 = 2;
+/// 保留的事件数
+const EVENTS_LIMIT: usize = 500;
+/// 在看板中显示最近多少天的变更
+const RECENT_DAYS: i64 = 7;
+/// 单次获取最多跟随的页数，防止接口不断返回新游标
+const MAX_PAGES: usize = 100;
+
+/// 已保存的模型列表与事件
+static STATE: LazyLock<Mutex<ListingState>> = LazyLock::new(|| Mutex::new(ListingState::load()));
+
+/// 模型列表配置文件
+#[derive(Debug, Deserialize)]
+struct ListingsFile {
+    #[serde(default = "default_interval_secs")]
+    interval_secs: u64,
+    #[serde(default)]
+    listings: Vec<ListingConfig>,
+    /// 模型变更告警 webhook 地址，支持 `${VAR}` 引用环境变量
+    #[serde(default)]
+    webhooks: Vec<String>,
+}
+
+/// 单个供应商的模型列表接口
+#[derive(Debug, Clone, Deserialize)]
+pub struct ListingConfig {
+    /// 对应的供应商名称（status.json 中的键）
+    pub provider: String,
+    #[serde(default)]
+    pub api: ApiKind,
+    /// API 基础地址，如 `https://api.openai.com/v1`
+    pub base_url: String,
+    /// API 密钥，支持 `${VAR}` 引用环境变量
+    #[serde(default)]
+    pub api_key: Option<String>,
+}
+
+fn default_interval_secs() -> u64 {
+    3600
+}
+
+/// 单个供应商当前的模型列表
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct ProviderModels {
+    pub models: BTreeSet<String>,
+    /// 模型首次出现的时间
+    #[serde(default)]
+    pub first_seen: BTreeMap<String, String>,
+    /// 已从列表中消失但尚未确认下线的模型及其连续缺失次数
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub missing: BTreeMap<String, u32>,
+    pub updated_at: String,
+}
+
+/// 模型变更
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangeKind {
+    Added,
+    Removed,
+}
+
+/// 模型变更事件
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ModelEvent {
+    pub provider: String,
+    pub model: String,
+    pub kind: ChangeKind,
+    pub at: String,
+}
+
+/// 持久化的状态
+#[derive(Debug, Default, Deserialize, Serialize)]
+struct ListingState {
+    #[serde(default)]
+    providers: BTreeMap<String, ProviderModels>,
+    #[serde(default)]
+    events: Vec<ModelEvent>,
+}
+
+impl ListingState {
+    fn path() -> PathBuf {
+        PathBuf::from(CONFIG.data_dir).join("model_listings.json")
+    }
+
+    fn load() -> Self {
+        match fs::read_to_string(Self::path()) {
+            Ok(content) => serde_json::from_str(&content).unwrap_or_else(|e| {
+                error!("❌ 解析 {} 失败，将重新记录模型列表: {}", Self::path().display(), e);
+                Self::default()
+            }),
+            Err(_) => Self::default(),
+        }
+    }
+
+    fn save(&self) {
+        let result = fs::create_dir_all(CONFIG.data_dir)
+            .and_then(|_| fs::write(Self::path(), serde_json::to_string_pretty(self).unwrap()));
+        if let Err(e) = result {
+            error!("❌ 保存模型列表失败: {}", e);
+        }
+    }
+
+    /// 与上一次的列表比较，返回新增与确认下线的模型
+    ///
+    /// 首次获取某个供应商的列表时只记录，不产生事件。
+    fn apply(&mut self, provider: &str, current: BTreeSet<String>, now: DateTime<Utc>) -> Vec<ModelEvent> {
+        let at = now.to_rfc3339();
+        let event = |model: &str, kind| ModelEvent {
+            provider: provider.to_string(),
+            model: model.to_string(),
+            kind,
+            at: at.clone(),
+        };
+
+        let Some(previous) = self.providers.get_mut(provider) else {
+            self.providers.insert(
+                provider.to_string(),
+                ProviderModels {
+                    first_seen: current.iter().map(|m| (m.clone(), at.clone())).collect(),
+                    models: current,
+                    missing: BTreeMap::new(),
+                    updated_at: at.clone(),
+                },
+            );
+            return Vec::new();
+        };
+
+        let mut events = Vec::new();
+        for model in current.difference(&previous.models) {
+            previous.first_seen.insert(model.clone(), at.clone());
+            events.push(event(model, ChangeKind::Added));
+        }
+
+        let mut kept = current.clone();
+        previous.missing.retain(|model, _| !current.contains(model));
+        for model in previous.models.difference(&current) {
+            let count = previous.missing.entry(model.clone()).or_default();
+            *count += 1;
+            if *count >= MISSING_CONFIRMATIONS {
+                events.push(event(model, ChangeKind::Removed));
+            } else {
+                kept.insert(model.clone());
+            }
+        }
+        previous.missing.retain(|_, count| *count < MISSING_CONFIRMATIONS);
+        for removed in events.iter().filter(|e| e.kind == ChangeKind::Removed) {
+            previous.first_seen.remove(&removed.model);
+        }
+
+        previous.models = kept;
+        previous.updated_at = at;
+        self.events.extend(events.iter().cloned());
+        if self.events.len() > EVENTS_LIMIT {
+            let excess = self.events.len() - EVENTS_LIMIT;
+            self.events.drain(..excess);
+        }
+        events
+    }
+}
+
+fn load_file() -> Option<ListingsFile> {
+    let content = fs::read_to_string(CONFIG.model_listings_file).ok()?;
+    match serde_json::from_str(&content) {
+        Ok(file) => Some(file),
+        Err(e) => {
+            error!("❌ 解析模型列表配置 {} 失败: {}", CONFIG.model_listings_file, e);
+            None
+        }
+    }
+}
+
+/// 按配置的间隔持续获取模型列表，每轮重新读取配置，修改接口或间隔无需重启；
+/// 未配置任何接口时按默认间隔等待配置出现
+pub async fn run_forever() {
+    let client = match ClientBuilder::new()
+        .timeout(Duration::from_secs(30))
+        .user_agent("LLM-Status-Monitor/1.0 (Rust/1.80.0)")
+        .use_rustls_tls()
+        .build()
+    {
+        Ok(client) => client,
+        Err(e) => {
+            error!("❌ 创建模型列表客户端失败: {}", e);
+            return;
+        }
+    };
+
+    let mut enabled = None;
+    loop {
+        let started = Instant::now();
+        let file = load_file().unwrap_or_else(|| ListingsFile {
+            interval_secs: default_interval_secs(),
+            listings: Vec::new(),
+            webhooks: Vec::new(),
+        });
+        if enabled != Some(!file.listings.is_empty()) {
+            enabled = Some(!file.listings.is_empty());
+            if file.listings.is_empty() {
+                info!("ℹ️ 未配置模型列表追踪");
+            } else {
+                info!("🧩 模型列表追踪已启用，间隔 {} 秒", file.interval_secs);
+            }
+        }
+        for listing in &file.listings {
+            let models = match fetch_models(&client, listing).await {
+                Ok(models) if models.is_empty() => {
+                    warn!("⚠️ {} 模型列表为空，跳过本次比较", listing.provider);
+                    continue;
+                }
+                Ok(models) => models,
+                Err(e) => {
+                    warn!("⚠️ 获取 {} 模型列表失败: {}", listing.provider, e);
+                    continue;
+                }
+            };
+
+            let events = {
+                let mut state = STATE.lock().unwrap();
+                let events = state.apply(&listing.provider, models, Utc::now());
+                state.save();
+                events
+            };
+            for event in events {
+                let text = match event.kind {
+                    ChangeKind::Added => format!("🆕 {} 新增模型 {}", event.provider, event.model),
+                    ChangeKind::Removed => format!("🗑️ {} 的模型 {} 已从列表中移除", event.provider, event.model),
+                };
+                info!("{}", text);
+                let name = match event.kind {
+                    ChangeKind::Added => "model_added",
+                    ChangeKind::Removed => "model_removed",
+                };
+                alerts::send(&file.webhooks, name, &text, serde_json::json!({ "model_event": event })).await;
+            }
+        }
+        tokio::time::sleep(Duration::from_secs(file.interval_secs.max(60)).saturating_sub(started.elapsed())).await;
+    }
+}
+
+/// 分页位置：Anthropic 使用 `has_more` 与 `last_id`，Gemini 使用 `nextPageToken`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum Cursor {
+    AfterId(String),
+    PageToken(String),
+}
+
+/// 获取模型ID列表，跟随分页直到最后一页，避免后续页的模型被误判为下线
+async fn fetch_models(client: &Client, listing: &ListingConfig) -> Result<BTreeSet<String>, Box<dyn Error + Send + Sync>> {
+    let url = format!("{}/models", listing.base_url.trim_end_matches('/'));
+    let mut models = BTreeSet::new();
+    let mut cursor: Option<Cursor> = None;
+    // 游标循环（A→B→A）时各页都已读过，直接结束
+    let mut seen = HashSet::new();
+
+    for _ in 0..MAX_PAGES {
+        let mut request = client.get(&url);
+        if matches!(listing.api, ApiKind::Anthropic) {
+            request = request.query(&[("limit", "1000")]);
+        }
+        match &cursor {
+            Some(Cursor::AfterId(after_id)) => request = request.query(&[("after_id", after_id)]),
+            Some(Cursor::PageToken(token)) => request = request.query(&[("pageToken", token)]),
+            None => {}
+        }
+        let response = probes::authorize(request, listing.api, listing.api_key.as_deref()).send().await?;
+        let status = response.status();
+        if !status.is_success() {
+            return Err(format!("HTTP {} - {}", status.as_u16(), status.canonical_reason().unwrap_or("Unknown")).into());
+        }
+        let body: serde_json::Value = response.json().await?;
+        models.extend(parse_ids(&body));
+
+        match next_cursor(&body) {
+            Some(next) if seen.insert(next.clone()) => cursor = Some(next),
+            _ => return Ok(models),
+        }
+    }
+    // 列表不完整时不能用于判断下线
+    Err(format!("分页超过 {} 页，已放弃本次获取", MAX_PAGES).into())
+}
+
+/// 下一页的位置，已是最后一页时返回 None
+fn next_cursor(body: &serde_json::Value) -> Option<Cursor> {
+    if let Some(token) = body.get("nextPageToken").and_then(|v| v.as_str()).filter(|t| !t.is_empty()) {
+        return Some(Cursor::PageToken(token.to_string()));
+    }
+    let has_more = body.get("has_more").and_then(|v| v.as_bool()).unwrap_or(false);
+    let last_id = body.get("last_id").and_then(|v| v.as_str())?;
+    has_more.then(|| Cursor::AfterId(last_id.to_string()))
+}
+
+/// 从 `data[].id`（OpenAI/Anthropic）或 `models[].name`/`models[].id` 中提取模型ID
+fn parse_ids(body: &serde_json::Value) -> Vec<String> {
+    let items = body
+        .get("data")
+        .or_else(|| body.get("models"))
+        .and_then(|v| v.as_array())
+        .cloned()
+        .unwrap_or_default();
+    items
+        .iter()
+        .filter_map(|item| item.get("id").or_else(|| item.get("name")).and_then(|v| v.as_str()))
+        .map(str::to_string)
+        .collect()
+}
+
+/// 各供应商当前的模型列表
+pub fn current() -> BTreeMap<String, ProviderModels> {
+    STATE.lock().unwrap().providers.clone()
+}
+
+/// 最近的模型变更事件，新的在前
+pub fn events(provider: Option<&str>, limit: usize) -> Vec<ModelEvent> {
+    STATE
+        .lock()
+        .unwrap()
+        .events
+        .iter()
+        .rev()
+        .filter(|e| provider.is_none_or(|p| e.provider == p))
+        .take(limit)
+        .cloned()
+        .collect()
+}
+
+/// 按供应商名称分组的模型数量与最近变更，写入 status.json
+pub fn summary_by_provider(now: DateTime<Utc>) -> serde_json::Value {
+    let cutoff = now - ChronoDuration::days(RECENT_DAYS);
+    let state = STATE.lock().unwrap();
+    state
+        .providers
+        .iter()
+        .map(|(provider, models)| {
+            let recent: Vec<&ModelEvent> = state
+                .events
+                .iter()
+                .rev()
+                .filter(|e| &e.provider == provider)
+                .filter(|e| {
+                    DateTime::parse_from_rfc3339(&e.at).is_ok_and(|at| at.with_timezone(&Utc) >= cutoff)
+                })
+                .collect();
+            (
+                provider.clone(),
+                serde_json::json!({
+                    "model_count": models.models.len(),
+                    "updated_at": models.updated_at,
+                    "recent_changes": recent,
+                }),
+            )
+        })
+        .collect::<serde_json::Map<_, _>>()
+        .into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn set(ids: &[&str]) -> BTreeSet<String> {
+        ids.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn first_listing_is_baseline_then_changes_emit_events() {
+        let now = Utc::now();
+        let mut state = ListingState::default();
+        assert!(state.apply("OpenAI", set(&["gpt-4o", "gpt-4o-mini", "gpt-3.5-turbo"]), now).is_empty());
+
+        let events = state.apply("OpenAI", set(&["gpt-4o", "gpt-4o-mini", "o3"]), now);
+        assert_eq!(events.len(), 1);
+        assert_eq!((events[0].model.as_str(), events[0].kind), ("o3", ChangeKind::Added));
+        // 首次缺失时暂不判定下线
+        assert!(state.providers["OpenAI"].models.contains("gpt-3.5-turbo"));
+
+        let events = state.apply("OpenAI", set(&["gpt-4o", "gpt-4o-mini", "o3"]), now);
+        assert_eq!(events.len(), 1);
+        assert_eq!((events[0].model.as_str(), events[0].kind), ("gpt-3.5-turbo", ChangeKind::Removed));
+        assert!(!state.providers["OpenAI"].models.contains("gpt-3.5-turbo"));
+        assert!(state.providers["OpenAI"].missing.is_empty());
+        assert_eq!(state.events.len(), 2);
+    }
+
+    #[test]
+    fn model_reappearing_before_confirmation_is_not_removed() {
+        let now = Utc::now();
+        let mut state = ListingState::default();
+        state.apply("Anthropic", set(&["claude-a", "claude-b"]), now);
+        assert!(state.apply("Anthropic", set(&["claude-a"]), now).is_empty());
+        assert!(state.apply("Anthropic", set(&["claude-a", "claude-b"]), now).is_empty());
+        assert!(state.apply("Anthropic", set(&["claude-a"]), now).is_empty());
+        assert!(state.providers["Anthropic"].models.contains("claude-b"));
+    }
+
+    #[test]
+    fn parses_openai_and_gemini_style_listings() {
+        let openai = serde_json::json!({ "object": "list", "data": [{ "id": "gpt-4o" }, { "id": "o3" }] });
+        assert_eq!(parse_ids(&openai), vec!["gpt-4o", "o3"]);
+        let gemini = serde_json::json!({ "models": [{ "name": "models/gemini-2.0-flash" }] });
+        assert_eq!(parse_ids(&gemini), vec!["models/gemini-2.0-flash"]);
+    }
+
+    #[tokio::test]
+    async fn follows_every_page() {
+        use axum::{extract::Query, routing::get, Router};
+        use std::collections::HashMap;
+
+        let app = Router::new()
+            .route(
+                "/gemini/models",
+                get(|Query(query): Query<HashMap<String, String>>| async move {
+                    axum::Json(match query.get("pageToken").map(String::as_str) {
+                        None => serde_json::json!({ "models": [{ "name": "models/a" }], "nextPageToken": "p2" }),
+                        Some("p2") => serde_json::json!({ "models": [{ "name": "models/b" }], "nextPageToken": "" }),
+                        Some(_) => serde_json::json!({ "models": [] }),
+                    })
+                }),
+            )
+            .route(
+                "/anthropic/models",
+                get(|Query(query): Query<HashMap<String, String>>| async move {
+                    axum::Json(match query.get("after_id").map(String::as_str) {
+                        None => serde_json::json!({ "data": [{ "id": "claude-a" }], "has_more": true, "last_id": "claude-a" }),
+                        Some(_) => serde_json::json!({ "data": [{ "id": "claude-b" }], "has_more": false, "last_id": "claude-b" }),
+                    })
+                }),
+            )
+            .route(
+                "/cycle/models",
+                get(|Query(query): Query<HashMap<String, String>>| async move {
+                    axum::Json(match query.get("pageToken").map(String::as_str) {
+                        Some("b") => serde_json::json!({ "models": [{ "name": "models/b" }], "nextPageToken": "a" }),
+                        _ => serde_json::json!({ "models": [{ "name": "models/a" }], "nextPageToken": "b" }),
+                    })
+                }),
+            )
+            .route(
+                "/endless/models",
+                get(|Query(query): Query<HashMap<String, String>>| async move {
+                    let page = query.get("pageToken").and_then(|t| t.parse::<u64>().ok()).unwrap_or(0);
+                    axum::Json(serde_json::json!({ "models": [], "nextPageToken": (page + 1).to_string() }))
+                }),
+            );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        let listing = |path: &str, api: ApiKind| ListingConfig {
+            provider: "Mock".to_string(),
+            api,
+            base_url: format!("http://{}/{}", addr, path),
+            api_key: None,
+        };
+        let client = Client::new();
+        let gemini = fetch_models(&client, &listing("gemini", ApiKind::Openai)).await.unwrap();
+        assert_eq!(gemini, set(&["models/a", "models/b"]));
+        let anthropic = fetch_models(&client, &listing("anthropic", ApiKind::Anthropic)).await.unwrap();
+        assert_eq!(anthropic, set(&["claude-a", "claude-b"]));
+        let cycle = fetch_models(&client, &listing("cycle", ApiKind::Openai)).await.unwrap();
+        assert_eq!(cycle, set(&["models/a", "models/b"]));
+        assert!(fetch_models(&client, &listing("endless", ApiKind::Openai)).await.is_err());
+    }
+
+    #[test]
+    fn next_page_cursor() {
+        assert_eq!(next_cursor(&serde_json::json!({ "nextPageToken": "abc" })), Some(Cursor::PageToken("abc".to_string())));
+        assert_eq!(
+            next_cursor(&serde_json::json!({ "has_more": true, "last_id": "m" })),
+            Some(Cursor::AfterId("m".to_string()))
+        );
+        assert_eq!(next_cursor(&serde_json::json!({ "has_more": false, "last_id": "m" })), None);
+        assert_eq!(next_cursor(&serde_json::json!({ "data": [] })), None);
+    }
+}
@@ -58,6 +58,23 @@ pub struct Incident {
     pub scheduled_for: Option<String>,
     pub scheduled_until: Option<String>,
     pub automated: Option<bool>,
+    /// 按规则从事件文本中提取的分类，未配置规则时为空
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub classification: Option<IncidentClassification>,
+}
+
+/// 事件分类结果
+#[derive(Debug, Default, Deserialize, Serialize, Clone, PartialEq)]
+pub struct IncidentClassification {
+    /// 事件类别，如 `elevated_errors`、`latency`
+    #[serde(default)]
+    pub categories: Vec<String>,
+    /// 涉及的区域
+    #[serde(default)]
+    pub regions: Vec<String>,
+    /// 提到的模型
+    #[serde(default)]
+    pub models: Vec<String>,
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
@@ -98,6 +115,30 @@ pub struct OverallStatus {
     pub description: String,
 }
 
+impl OverallStatus {
+    /// 根据各组件中最严重的状态推导整体状态
+    pub fn from_components(components: &[Component]) -> Self {
+        let worst = components
+            .iter()
+            .map(|c| &c.status)
+            .max_by_key(|status| status.severity());
+
+        let (indicator, description) = match worst {
+            None | Some(ComponentStatus::Unknown) => (StatusIndicator::Unknown, "Status Unknown"),
+            Some(ComponentStatus::Operational) => (StatusIndicator::None, "All Systems Operational"),
+            Some(ComponentStatus::UnderMaintenance) => (StatusIndicator::Maintenance, "Service Under Maintenance"),
+            Some(ComponentStatus::DegradedPerformance) => (StatusIndicator::Minor, "Degraded Performance"),
+            Some(ComponentStatus::PartialOutage) => (StatusIndicator::Minor, "Partial Outage"),
+            Some(ComponentStatus::MajorOutage) => (StatusIndicator::Major, "Major Outage"),
+        };
+
+        Self {
+            indicator,
+            description: description.to_string(),
+        }
+    }
+}
+
 /// 组件状态枚举
 #[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
 #[serde(rename_all = "snake_case")]
@@ -111,6 +152,20 @@ pub enum ComponentStatus {
     Unknown,
 }
 
+impl ComponentStatus {
+    /// 严重程度，数值越大越严重（未知状态只在没有其他信息时生效）
+    pub fn severity(&self) -> u8 {
+        match self {
+            ComponentStatus::Unknown => 0,
+            ComponentStatus::Operational => 1,
+            ComponentStatus::UnderMaintenance => 2,
+            ComponentStatus::DegradedPerformance => 3,
+            ComponentStatus::PartialOutage => 4,
+            ComponentStatus::MajorOutage => 5,
+        }
+    }
+}
+
 #[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
 #[serde(rename_all = "snake_case")]
 pub enum IncidentStatus {
@@ -0,0 +1,443 @@
+//! 网络连通性检查
+//!
+//! 状态获取失败时，无法区分是供应商故障还是本地网络（或跨境链路）不通。
+//! 这里对各供应商的 API 地址逐步检查 DNS 解析、TCP 连接、TLS 握手（含证书有效期与签发者）
+//! 和 HTTP 可达性，结果与官方状态分开展示。
+
+use crate::config::CONFIG;
+use chrono::{DateTime, Utc};
+use reqwest::{Client, ClientBuilder, Url};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::future::Future;
+use std::net::SocketAddr;
+use std::sync::{Arc, LazyLock, Mutex};
+use std::time::{Duration, Instant};
+use tokio::net::TcpStream;
+use tokio::task::JoinSet;
+use tokio_rustls::rustls::{self, OwnedTrustAnchor, RootCertStore, ServerName};
+use tokio_rustls::TlsConnector;
+use tracing::{error, info, warn};
+
+/// 各检查目标最近一次的结果，按地址索引
+static LATEST_RESULTS: LazyLock<Mutex<HashMap<String, NetCheck>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// 校验服务端证书使用的根证书
+static TLS_CONNECTOR: LazyLock<TlsConnector> = LazyLock::new(|| {
+    let mut roots = RootCertStore::empty();
+    roots.add_trust_anchors(webpki_roots::TLS_SERVER_ROOTS.iter().map(|ta| {
+        OwnedTrustAnchor::from_subject_spki_name_constraints(ta.subject, ta.spki, ta.name_constraints)
+    }));
+    let config = rustls::ClientConfig::builder()
+        .with_safe_defaults()
+        .with_root_certificates(roots)
+        .with_no_client_auth();
+    TlsConnector::from(Arc::new(config))
+});
+
+/// 网络检查配置文件
+#[derive(Debug, Deserialize)]
+struct NetCheckFile {
+    #[serde(default = "default_interval_secs")]
+    interval_secs: u64,
+    #[serde(default)]
+    targets: Vec<NetCheckTarget>,
+}
+
+/// 检查目标
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct NetCheckTarget {
+    /// 对应的供应商名称（status.json 中的键）
+    pub provider: String,
+    /// API 地址，如 `https://api.openai.com/v1/models`
+    pub url: String,
+}
+
+/// 检查结论：第一个失败的步骤
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Verdict {
+    Reachable,
+    DnsFailed,
+    TcpFailed,
+    TlsFailed,
+    HttpFailed,
+}
+
+/// 单个步骤的结果
+#[derive(Debug, Clone, Serialize)]
+pub struct Step {
+    pub ok: bool,
+    pub ms: u64,
+    pub detail: String,
+}
+
+/// 服务端证书信息
+#[derive(Debug, Clone, Serialize)]
+pub struct Certificate {
+    pub subject: String,
+    pub issuer: String,
+    pub not_after: String,
+    pub days_left: i64,
+}
+
+/// 单个目标的检查结果，前一步失败时后续步骤为 None
+#[derive(Debug, Clone, Serialize)]
+pub struct NetCheck {
+    pub provider: String,
+    pub url: String,
+    pub host: String,
+    pub port: u16,
+    pub verdict: Verdict,
+    pub dns: Option<Step>,
+    pub tcp: Option<Step>,
+    /// http 地址不检查 TLS
+    pub tls: Option<Step>,
+    pub certificate: Option<Certificate>,
+    pub http: Option<Step>,
+    pub checked_at: String,
+}
+
+fn default_interval_secs() -> u64 {
+    300
+}
+
+/// 每个步骤的超时时间
+const STEP_TIMEOUT: Duration = Duration::from_secs(10);
+
+fn load_file() -> Option<NetCheckFile> {
+    let content = fs::read_to_string(CONFIG.netcheck_file).ok()?;
+    match serde_json::from_str(&content) {
+        Ok(file) => Some(file),
+        Err(e) => {
+            error!("❌ 解析网络检查配置 {} 失败: {}", CONFIG.netcheck_file, e);
+            None
+        }
+    }
+}
+
+/// 配置文件中的检查目标
+pub fn targets() -> Vec<NetCheckTarget> {
+    load_file().map(|f| f.targets).unwrap_or_default()
+}
+
+/// 按配置的间隔持续检查，每轮重新读取配置，修改目标或间隔无需重启；
+/// 未配置任何目标时按默认间隔等待配置出现
+pub async fn run_forever() {
+    let client = match client() {
+        Ok(client) => client,
+        Err(e) => {
+            error!("❌ 创建网络检查客户端失败: {}", e);
+            return;
+        }
+    };
+
+    let mut enabled = None;
+    loop {
+        let started = Instant::now();
+        let (interval_secs, targets) = load_file()
+            .map(|f| (f.interval_secs, f.targets))
+            .unwrap_or((default_interval_secs(), Vec::new()));
+        if enabled != Some(!targets.is_empty()) {
+            enabled = Some(!targets.is_empty());
+            if targets.is_empty() {
+                info!("ℹ️ 未配置网络连通性检查");
+            } else {
+                info!("🌐 网络连通性检查已启用，间隔 {} 秒", interval_secs);
+            }
+        }
+        let results = if targets.is_empty() { Vec::new() } else { check_all(&client, targets).await };
+        {
+            let mut latest = LATEST_RESULTS.lock().unwrap();
+            latest.clear();
+            for result in results {
+                latest.insert(result.url.clone(), result);
+            }
+        }
+        tokio::time::sleep(Duration::from_secs(interval_secs.max(1)).saturating_sub(started.elapsed())).await;
+    }
+}
+
+/// 检查使用的 HTTP 客户端
+pub fn client() -> Result<Client, reqwest::Error> {
+    ClientBuilder::new()
+        .timeout(STEP_TIMEOUT)
+        .user_agent("LLM-Status-Monitor/1.0 (Rust/1.80.0)")
+        .use_rustls_tls()
+        .build()
+}
+
+/// 并发检查全部目标
+pub async fn check_all(client: &Client, targets: Vec<NetCheckTarget>) -> Vec<NetCheck> {
+    let mut tasks = JoinSet::new();
+    for target in targets {
+        let client = client.clone();
+        tasks.spawn(async move { check(&client, &target).await });
+    }
+
+    let mut results = Vec::new();
+    while let Some(result) = tasks.join_next().await {
+        match result {
+            Ok(result) => results.push(result),
+            Err(e) => error!("❌ 网络检查任务异常退出: {}", e),
+        }
+    }
+    results.sort_by(|a, b| a.provider.cmp(&b.provider).then_with(|| a.url.cmp(&b.url)));
+    results
+}
+
+/// 依次检查 DNS、TCP、TLS 与 HTTP，遇到失败即停止
+pub async fn check(client: &Client, target: &NetCheckTarget) -> NetCheck {
+    let mut result = NetCheck {
+        provider: target.provider.clone(),
+        url: target.url.clone(),
+        host: String::new(),
+        port: 0,
+        verdict: Verdict::DnsFailed,
+        dns: None,
+        tcp: None,
+        tls: None,
+        certificate: None,
+        http: None,
+        checked_at: Utc::now().to_rfc3339(),
+    };
+
+    let url = match Url::parse(&target.url) {
+        Ok(url) => url,
+        Err(e) => {
+            result.dns = Some(Step { ok: false, ms: 0, detail: format!("无效的URL: {}", e) });
+            return finish(result);
+        }
+    };
+    result.host = url.host_str().unwrap_or_default().to_string();
+    result.port = url.port_or_known_default().unwrap_or(443);
+
+    // DNS 解析
+    let (elapsed, resolved) = timed(tokio::net::lookup_host((result.host.clone(), result.port))).await;
+    let addrs: Vec<SocketAddr> = match resolved {
+        Ok(Ok(addrs)) => addrs.collect(),
+        Ok(Err(e)) => {
+            result.dns = Some(Step { ok: false, ms: elapsed, detail: e.to_string() });
+            return finish(result);
+        }
+        Err(e) => {
+            result.dns = Some(Step { ok: false, ms: elapsed, detail: e });
+            return finish(result);
+        }
+    };
+    if addrs.is_empty() {
+        result.dns = Some(Step { ok: false, ms: elapsed, detail: "未解析到任何地址".to_string() });
+        return finish(result);
+    }
+    let listed: Vec<String> = addrs.iter().map(|a| a.ip().to_string()).collect();
+    result.dns = Some(Step { ok: true, ms: elapsed, detail: listed.join(", ") });
+
+    // TCP 连接
+    result.verdict = Verdict::TcpFailed;
+    let (elapsed, connected) = connect_any(&addrs).await;
+    let stream = match connected {
+        Ok((addr, stream, failures)) => {
+            let detail = if failures.is_empty() {
+                addr.to_string()
+            } else {
+                format!("{}（{} 失败）", addr, failures.join("; "))
+            };
+            result.tcp = Some(Step { ok: true, ms: elapsed, detail });
+            stream
+        }
+        Err(failures) => {
+            result.tcp = Some(Step { ok: false, ms: elapsed, detail: failures.join("; ") });
+            return finish(result);
+        }
+    };
+
+    // TLS 握手
+    if url.scheme() == "https" {
+        result.verdict = Verdict::TlsFailed;
+        let Ok(server_name) = ServerName::try_from(result.host.as_str()) else {
+            result.tls = Some(Step { ok: false, ms: 0, detail: "无效的服务器名称".to_string() });
+            return finish(result);
+        };
+        let (elapsed, handshake) = timed(TLS_CONNECTOR.connect(server_name, stream)).await;
+        match handshake {
+            Ok(Ok(tls)) => {
+                let (_, connection) = tls.get_ref();
+                let version = connection
+                    .protocol_version()
+                    .map(|v| format!("{:?}", v))
+                    .unwrap_or_default();
+                result.certificate = connection
+                    .peer_certificates()
+                    .and_then(|certs| certs.first())
+                    .and_then(|cert| certificate_info(&cert.0, Utc::now()));
+                result.tls = Some(Step { ok: true, ms: elapsed, detail: version });
+            }
+            Ok(Err(e)) => {
+                result.tls = Some(Step { ok: false, ms: elapsed, detail: e.to_string() });
+                return finish(result);
+            }
+            Err(e) => {
+                result.tls = Some(Step { ok: false, ms: elapsed, detail: e });
+                return finish(result);
+            }
+        }
+    }
+
+    // HTTP 可达性：收到任何响应（包括 401/404）都说明链路通畅
+    result.verdict = Verdict::HttpFailed;
+    let started = Instant::now();
+    let response = client.get(url).send().await;
+    let elapsed = started.elapsed().as_millis() as u64;
+    result.http = Some(match response {
+        Ok(response) => {
+            result.verdict = Verdict::Reachable;
+            Step { ok: true, ms: elapsed, detail: format!("HTTP {}", response.status().as_u16()) }
+        }
+        Err(e) => Step { ok: false, ms: elapsed, detail: e.to_string() },
+    });
+    finish(result)
+}
+
+/// 优先 IPv4 依次连接各个解析地址，避免本机没有 IPv6 路由时误报连接失败。
+/// 返回成功地址的连接耗时，以及在它之前失败的地址
+async fn connect_any(
+    addrs: &[SocketAddr],
+) -> (u64, Result<(SocketAddr, TcpStream, Vec<String>), Vec<String>>) {
+    let mut ordered = addrs.to_vec();
+    ordered.sort_by_key(SocketAddr::is_ipv6);
+    let mut failures = Vec::new();
+    let mut total = 0;
+    for addr in ordered {
+        let (elapsed, connected) = timed(TcpStream::connect(addr)).await;
+        total += elapsed;
+        match connected {
+            Ok(Ok(stream)) => return (elapsed, Ok((addr, stream, failures))),
+            Ok(Err(e)) => failures.push(format!("{}: {}", addr, e)),
+            Err(e) => failures.push(format!("{}: {}", addr, e)),
+        }
+    }
+    (total, Err(failures))
+}
+
+/// 带超时地执行一个步骤，返回耗时（毫秒）
+async fn timed<F: Future>(future: F) -> (u64, Result<F::Output, String>) {
+    let started = Instant::now();
+    let output = tokio::time::timeout(STEP_TIMEOUT, future)
+        .await
+        .map_err(|_| format!("超时（{} 秒）", STEP_TIMEOUT.as_secs()));
+    (started.elapsed().as_millis() as u64, output)
+}
+
+fn finish(result: NetCheck) -> NetCheck {
+    match result.verdict {
+        Verdict::Reachable => info!("🌐 {} 网络连通", result.url),
+        verdict => warn!("⚠️ {} 网络检查失败: {:?}", result.url, verdict),
+    }
+    if let Some(cert) = result.certificate.as_ref().filter(|c| c.days_left < 14) {
+        warn!("⚠️ {} 证书将在 {} 天后过期", result.host, cert.days_left);
+    }
+    result
+}
+
+/// 解析 DER 格式证书的主体、签发者与有效期
+fn certificate_info(der: &[u8], now: DateTime<Utc>) -> Option<Certificate> {
+    let (_, cert) = x509_parser::parse_x509_certificate(der).ok()?;
+    let not_after = DateTime::from_timestamp(cert.validity().not_after.timestamp(), 0)?;
+    Some(Certificate {
+        subject: cert.subject().to_string(),
+        issuer: cert.issuer().to_string(),
+        not_after: not_after.to_rfc3339(),
+        days_left: (not_after - now).num_days(),
+    })
+}
+
+/// 全部目标的最近结果
+pub fn latest_results() -> Vec<NetCheck> {
+    let mut results: Vec<NetCheck> = LATEST_RESULTS.lock().unwrap().values().cloned().collect();
+    results.sort_by(|a, b| a.provider.cmp(&b.provider).then_with(|| a.url.cmp(&b.url)));
+    results
+}
+
+/// 按供应商名称分组的最近结果，写入 status.json
+pub fn results_by_provider() -> serde_json::Value {
+    let mut grouped: serde_json::Map<String, serde_json::Value> = serde_json::Map::new();
+    for result in latest_results() {
+        let entry = grouped
+            .entry(result.provider.clone())
+            .or_insert_with(|| serde_json::Value::Array(Vec::new()));
+        if let serde_json::Value::Array(list) = entry {
+            list.push(serde_json::to_value(&result).unwrap());
+        }
+    }
+    grouped.into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{routing::get, Router};
+
+    fn target(url: &str) -> NetCheckTarget {
+        NetCheckTarget { provider: "Mock".to_string(), url: url.to_string() }
+    }
+
+    #[tokio::test]
+    async fn local_http_endpoint_is_reachable() {
+        let app = Router::new().route("/v1/models", get(|| async { (axum::http::StatusCode::UNAUTHORIZED, "no key") }));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        let result = check(&client().unwrap(), &target(&format!("http://{}/v1/models", addr))).await;
+        assert_eq!(result.verdict, Verdict::Reachable);
+        assert!(result.dns.unwrap().ok);
+        assert!(result.tcp.unwrap().ok);
+        assert!(result.tls.is_none());
+        assert_eq!(result.http.unwrap().detail, "HTTP 401");
+    }
+
+    #[tokio::test]
+    async fn closed_port_fails_at_tcp() {
+        let result = check(&client().unwrap(), &target("https://127.0.0.1:1/v1/models")).await;
+        assert_eq!(result.verdict, Verdict::TcpFailed);
+        assert!(result.dns.unwrap().ok);
+        assert!(!result.tcp.unwrap().ok);
+        assert!(result.tls.is_none() && result.http.is_none());
+    }
+
+    #[tokio::test]
+    async fn plain_tcp_server_fails_tls_handshake() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            while let Ok((mut socket, _)) = listener.accept().await {
+                use tokio::io::AsyncWriteExt;
+                let _ = socket.write_all(b"HTTP/1.1 400 Bad Request\r\n\r\n").await;
+            }
+        });
+
+        let result = check(&client().unwrap(), &target(&format!("https://{}/", addr))).await;
+        assert_eq!(result.verdict, Verdict::TlsFailed);
+        assert!(!result.tls.unwrap().ok);
+        assert!(result.http.is_none());
+    }
+
+    #[tokio::test]
+    async fn connect_tries_every_address_and_prefers_ipv4() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let open = listener.local_addr().unwrap();
+        let closed: SocketAddr = "127.0.0.1:1".parse().unwrap();
+        let ipv6: SocketAddr = format!("[::1]:{}", open.port()).parse().unwrap();
+
+        let (_, connected) = connect_any(&[ipv6, closed, open]).await;
+        let (addr, _, failures) = connected.unwrap();
+        assert_eq!(addr, open);
+        assert_eq!(failures.len(), 1);
+        assert!(failures[0].starts_with("127.0.0.1:1: "), "{}", failures[0]);
+
+        let (_, connected) = connect_any(&[closed]).await;
+        assert_eq!(connected.unwrap_err().len(), 1);
+    }
+}
@@ -0,0 +1,431 @@
+//! 客户端上报的实测数据
+//!
+//! 网关等客户端按供应商与模型批量上报请求结果（成功数、按类别统计的错误数、延迟分桶），
+//! 服务端按分钟聚合并保留最近一小时，计算各时间窗口的“实测健康度”与官方状态并列展示，
+//! 同时计入官方状态与实测的差异检测。
+
+use crate::discrepancy::{self, ObservationSource};
+use chrono::{DateTime, Duration as ChronoDuration, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{LazyLock, Mutex};
+
+/// 保留的时间范围（分钟），同时也是最长的统计窗口
+const RETENTION_MINS: i64 = 60;
+/// 统计窗口（分钟）
+const WINDOWS: [i64; 3] = [5, 15, 60];
+/// 单次上报的最大条数
+pub const MAX_BATCH: usize = 1000;
+/// 单条上报（一分钟）的最大请求数，超过视为异常数据
+const MAX_REQUESTS_PER_MINUTE: u64 = 10_000_000;
+/// 供应商与模型名称的最大长度
+const MAX_NAME_LEN: usize = 64;
+/// 最多保存的供应商与模型组合数
+const MAX_KEYS: usize = 10_000;
+
+/// 按分钟（Unix 时间戳 / 60）索引的聚合数据
+type Minutes = BTreeMap<i64, MinuteBucket>;
+
+/// 按供应商与模型聚合的分钟数据
+static STORE: LazyLock<Mutex<HashMap<(String, String), Minutes>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// 批量上报
+#[derive(Debug, Deserialize)]
+pub struct ObservationBatch {
+    pub observations: Vec<Observation>,
+}
+
+/// 一段时间内某个供应商（及模型）的请求结果
+#[derive(Debug, Deserialize)]
+pub struct Observation {
+    /// 供应商名称（status.json 中的键）
+    pub provider: String,
+    #[serde(default)]
+    pub model: Option<String>,
+    /// 统计时间，默认为接收时间
+    #[serde(default)]
+    pub timestamp: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub success: u64,
+    /// 按类别统计的错误数
+    #[serde(default)]
+    pub errors: HashMap<ErrorClass, u64>,
+    /// 延迟分桶（非累计），键为上界毫秒数或 `+Inf`
+    #[serde(default)]
+    pub latency_ms_buckets: BTreeMap<String, u64>,
+}
+
+/// 错误类别
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorClass {
+    RateLimit,
+    ServerError,
+    Timeout,
+    Network,
+    /// 鉴权失败，属于调用方问题
+    Auth,
+    /// 请求参数错误，属于调用方问题
+    BadRequest,
+    #[serde(other)]
+    Other,
+}
+
+impl ErrorClass {
+    /// 是否属于供应商侧的故障，调用方问题不计入错误率
+    fn is_provider_side(self) -> bool {
+        !matches!(self, ErrorClass::Auth | ErrorClass::BadRequest)
+    }
+}
+
+/// 上报结果
+#[derive(Debug, Serialize)]
+pub struct IngestResult {
+    pub accepted: usize,
+    pub rejected: usize,
+    pub errors: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default)]
+struct MinuteBucket {
+    success: u64,
+    errors: HashMap<ErrorClass, u64>,
+    /// 延迟上界（毫秒，`u64::MAX` 表示 `+Inf`） -> 请求数
+    latency: BTreeMap<u64, u64>,
+}
+
+impl MinuteBucket {
+    fn merge(&mut self, other: &MinuteBucket) {
+        self.success = self.success.saturating_add(other.success);
+        for (class, count) in &other.errors {
+            let total = self.errors.entry(*class).or_default();
+            *total = total.saturating_add(*count);
+        }
+        for (bound, count) in &other.latency {
+            let total = self.latency.entry(*bound).or_default();
+            *total = total.saturating_add(*count);
+        }
+    }
+}
+
+/// 单个时间窗口的统计
+#[derive(Debug, Clone, Serialize)]
+pub struct WindowSummary {
+    pub requests: u64,
+    pub success: u64,
+    pub errors: u64,
+    /// 供应商侧错误（不含 auth、bad_request）占全部请求的比例
+    pub error_rate: f64,
+    pub errors_by_class: BTreeMap<ErrorClass, u64>,
+    /// 由分桶估算的延迟分位数（所在分桶的上界），落在 `+Inf` 分桶时为 null
+    pub latency_p50_ms: Option<u64>,
+    pub latency_p95_ms: Option<u64>,
+}
+
+/// 单个模型最近 15 分钟的统计
+#[derive(Debug, Clone, Serialize)]
+pub struct ModelHealth {
+    pub model: String,
+    #[serde(flatten)]
+    pub summary: WindowSummary,
+}
+
+/// 单个供应商的实测健康度
+#[derive(Debug, Clone, Serialize)]
+pub struct ObservedHealth {
+    pub provider: String,
+    /// 键为窗口长度，如 `5m`
+    pub windows: BTreeMap<String, WindowSummary>,
+    pub models: Vec<ModelHealth>,
+}
+
+/// 校验并写入一批上报数据
+pub fn ingest(batch: ObservationBatch, now: DateTime<Utc>) -> IngestResult {
+    let mut result = IngestResult { accepted: 0, rejected: 0, errors: Vec::new() };
+    let oldest = now - ChronoDuration::minutes(RETENTION_MINS);
+    let newest = now + ChronoDuration::minutes(5);
+
+    for (index, observation) in batch.observations.into_iter().enumerate() {
+        let checked = validate(&observation, oldest, newest);
+        let bucket = match checked {
+            Ok(bucket) => bucket,
+            Err(e) => {
+                result.rejected += 1;
+                result.errors.push(format!("第 {} 条: {}", index, e));
+                continue;
+            }
+        };
+
+        let key = (observation.provider, observation.model.unwrap_or_default());
+        let mut store = STORE.lock().unwrap();
+        if !store.contains_key(&key) && store.len() >= MAX_KEYS {
+            result.rejected += 1;
+            result.errors.push(format!("第 {} 条: 供应商与模型组合超过 {} 个", index, MAX_KEYS));
+            continue;
+        }
+        let at = observation.timestamp.unwrap_or(now);
+        store.entry(key.clone()).or_default().entry(at.timestamp() / 60).or_default().merge(&bucket);
+        drop(store);
+
+        let provider_errors: u64 = bucket
+            .errors
+            .iter()
+            .filter(|(class, _)| class.is_provider_side())
+            .map(|(_, count)| count)
+            .sum();
+        let requests = bucket.success + bucket.errors.values().sum::<u64>();
+        discrepancy::record_requests(&key.0, ObservationSource::Client, at, requests, provider_errors);
+        result.accepted += 1;
+    }
+    result
+}
+
+fn validate(observation: &Observation, oldest: DateTime<Utc>, newest: DateTime<Utc>) -> Result<MinuteBucket, String> {
+    if observation.provider.trim().is_empty() {
+        return Err("provider 不能为空".to_string());
+    }
+    check_name("provider", &observation.provider)?;
+    if let Some(model) = observation.model.as_deref().filter(|m| !m.is_empty()) {
+        check_name("model", model)?;
+    }
+    if let Some(at) = observation.timestamp {
+        if at < oldest || at > newest {
+            return Err(format!("timestamp {} 超出可接受的范围（最近 {} 分钟）", at.to_rfc3339(), RETENTION_MINS));
+        }
+    }
+
+    let mut latency = BTreeMap::new();
+    for (bound, count) in &observation.latency_ms_buckets {
+        let bound = match bound.as_str() {
+            "+Inf" | "inf" => u64::MAX,
+            other => other.parse::<u64>().map_err(|_| format!("无效的延迟分桶 {}", other))?,
+        };
+        // `100` 与 `0100` 是同一个分桶，合并时同样可能溢出
+        let total: &mut u64 = latency.entry(bound).or_default();
+        *total = total
+            .checked_add(*count)
+            .ok_or_else(|| format!("请求数超过每分钟上限 {}", MAX_REQUESTS_PER_MINUTE))?;
+    }
+
+    let requests = observation
+        .errors
+        .values()
+        .try_fold(observation.success, |total, count| total.checked_add(*count));
+    let latency_total = latency.values().try_fold(0u64, |total, count| total.checked_add(*count));
+    if requests.is_none_or(|n| n > MAX_REQUESTS_PER_MINUTE)
+        || latency_total.is_none_or(|n| n > MAX_REQUESTS_PER_MINUTE)
+    {
+        return Err(format!("请求数超过每分钟上限 {}", MAX_REQUESTS_PER_MINUTE));
+    }
+
+    Ok(MinuteBucket {
+        success: observation.success,
+        errors: observation.errors.iter().filter(|(_, c)| **c > 0).map(|(k, v)| (*k, *v)).collect(),
+        latency,
+    })
+}
+
+/// 名称会作为键保存并显示在看板上，限制长度与字符集
+fn check_name(field: &str, value: &str) -> Result<(), String> {
+    if value.chars().count() > MAX_NAME_LEN {
+        return Err(format!("{} 超过 {} 个字符", field, MAX_NAME_LEN));
+    }
+    let allowed = |c: char| c.is_alphanumeric() || " -_.:/@()+".contains(c);
+    match value.chars().find(|c| !allowed(*c)) {
+        Some(c) => Err(format!("{} 包含不允许的字符 {:?}", field, c)),
+        None => Ok(()),
+    }
+}
+
+fn summarize<'a>(buckets: impl Iterator<Item = &'a MinuteBucket>) -> WindowSummary {
+    let mut total = MinuteBucket::default();
+    for bucket in buckets {
+        total.merge(bucket);
+    }
+
+    let errors = total.errors.values().fold(0u64, |sum, count| sum.saturating_add(*count));
+    let provider_errors = total
+        .errors
+        .iter()
+        .filter(|(class, _)| class.is_provider_side())
+        .fold(0u64, |sum, (_, count)| sum.saturating_add(*count));
+    let requests = total.success.saturating_add(errors);
+    let error_rate = if requests == 0 { 0.0 } else { provider_errors as f64 / requests as f64 };
+
+    WindowSummary {
+        requests,
+        success: total.success,
+        errors,
+        error_rate: (error_rate * 1000.0).round() / 1000.0,
+        errors_by_class: total.errors.into_iter().collect(),
+        latency_p50_ms: percentile(&total.latency, 0.5),
+        latency_p95_ms: percentile(&total.latency, 0.95),
+    }
+}
+
+/// 分位数所在分桶的上界
+fn percentile(histogram: &BTreeMap<u64, u64>, quantile: f64) -> Option<u64> {
+    let total = histogram.values().fold(0u64, |sum, count| sum.saturating_add(*count));
+    if total == 0 {
+        return None;
+    }
+    let target = (total as f64 * quantile).ceil() as u64;
+    let mut seen: u64 = 0;
+    for (bound, count) in histogram {
+        seen = seen.saturating_add(*count);
+        if seen >= target {
+            return (*bound != u64::MAX).then_some(*bound);
+        }
+    }
+    None
+}
+
+/// 各供应商的实测健康度，同时清理过期数据
+pub fn observed_health(now: DateTime<Utc>) -> Vec<ObservedHealth> {
+    let current = now.timestamp() / 60;
+    let mut store = STORE.lock().unwrap();
+    for buckets in store.values_mut() {
+        buckets.retain(|minute, _| *minute > current - RETENTION_MINS);
+    }
+    store.retain(|_, buckets| !buckets.is_empty());
+
+    let mut providers: BTreeMap<&str, Vec<(&str, &Minutes)>> = BTreeMap::new();
+    for ((provider, model), buckets) in store.iter() {
+        providers.entry(provider).or_default().push((model, buckets));
+    }
+
+    let in_window = |buckets: &Minutes, mins: i64| {
+        buckets.range(current - mins + 1..).map(|(_, b)| b).cloned().collect::<Vec<_>>()
+    };
+
+    providers
+        .into_iter()
+        .map(|(provider, models)| {
+            let windows = WINDOWS
+                .iter()
+                .map(|mins| {
+                    let buckets: Vec<MinuteBucket> = models.iter().flat_map(|(_, b)| in_window(b, *mins)).collect();
+                    (format!("{}m", mins), summarize(buckets.iter()))
+                })
+                .collect();
+            let mut models: Vec<ModelHealth> = models
+                .iter()
+                .filter(|(model, _)| !model.is_empty())
+                .map(|(model, buckets)| ModelHealth {
+                    model: model.to_string(),
+                    summary: summarize(in_window(buckets, 15).iter()),
+                })
+                .filter(|m| m.summary.requests > 0)
+                .collect();
+            models.sort_by(|a, b| a.model.cmp(&b.model));
+            ObservedHealth { provider: provider.to_string(), windows, models }
+        })
+        .collect()
+}
+
+/// 按供应商名称索引的实测健康度，写入 status.json
+pub fn health_by_provider(now: DateTime<Utc>) -> serde_json::Value {
+    observed_health(now)
+        .into_iter()
+        .map(|health| (health.provider.clone(), serde_json::to_value(&health).unwrap()))
+        .collect::<serde_json::Map<_, _>>()
+        .into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn batch(json: serde_json::Value) -> ObservationBatch {
+        serde_json::from_value(json).unwrap()
+    }
+
+    #[test]
+    fn aggregates_windows_and_excludes_client_errors() {
+        let now = Utc::now();
+        let result = ingest(
+            batch(serde_json::json!({ "observations": [
+                { "provider": "Aggregate", "model": "m1", "success": 60,
+                  "errors": { "server_error": 30, "auth": 10 },
+                  "latency_ms_buckets": { "500": 50, "2000": 40, "+Inf": 10 } },
+                { "provider": "Aggregate", "model": "m2", "success": 10,
+                  "timestamp": (now - ChronoDuration::minutes(30)).to_rfc3339(),
+                  "errors": { "something_new": 5 } },
+                { "provider": "", "success": 1 },
+                { "provider": "Aggregate", "success": 1, "timestamp": (now - ChronoDuration::hours(3)).to_rfc3339() },
+                { "provider": "Aggregate", "success": 1, "latency_ms_buckets": { "fast": 1 } }
+            ]})),
+            now,
+        );
+        assert_eq!(result.accepted, 2);
+        assert_eq!(result.rejected, 3);
+
+        let health = observed_health(now).into_iter().find(|h| h.provider == "Aggregate").unwrap();
+        let recent = &health.windows["5m"];
+        assert_eq!(recent.requests, 100);
+        assert_eq!(recent.errors, 40);
+        assert_eq!(recent.error_rate, 0.3);
+        assert_eq!(recent.latency_p50_ms, Some(500));
+        assert_eq!(recent.latency_p95_ms, None);
+
+        let hour = &health.windows["60m"];
+        assert_eq!(hour.requests, 115);
+        assert_eq!(hour.errors_by_class[&ErrorClass::Other], 5);
+
+        // 15 分钟窗口内只有 m1
+        assert_eq!(health.models.len(), 1);
+        assert_eq!(health.models[0].model, "m1");
+    }
+
+    #[test]
+    fn percentile_uses_bucket_upper_bound() {
+        let histogram = BTreeMap::from([(100, 90), (1000, 9), (u64::MAX, 1)]);
+        assert_eq!(percentile(&histogram, 0.5), Some(100));
+        assert_eq!(percentile(&histogram, 0.95), Some(1000));
+        assert_eq!(percentile(&histogram, 1.0), None);
+        assert_eq!(percentile(&BTreeMap::new(), 0.5), None);
+    }
+
+    #[test]
+    fn rejects_oversized_counts_and_unsafe_names() {
+        let now = Utc::now();
+        let result = ingest(
+            batch(serde_json::json!({ "observations": [
+                { "provider": "Limits", "success": u64::MAX, "errors": { "server_error": u64::MAX } },
+                { "provider": "Limits", "success": MAX_REQUESTS_PER_MINUTE + 1 },
+                { "provider": "Limits", "success": 1, "latency_ms_buckets": { "100": u64::MAX, "+Inf": 1 } },
+                { "provider": "Limits", "success": 1, "latency_ms_buckets": { "100": u64::MAX, "0100": 1 } },
+                { "provider": "Limits", "model": "<img src=x onerror=alert(1)>", "success": 1 },
+                { "provider": "L".repeat(MAX_NAME_LEN + 1), "success": 1 },
+                { "provider": "Limits", "model": "gpt-4o-mini (2024-07-18)", "success": MAX_REQUESTS_PER_MINUTE },
+                { "provider": "Limits", "model": "models/gemini-2.0-flash", "success": 1 }
+            ]})),
+            now,
+        );
+        assert_eq!(result.accepted, 2);
+        assert_eq!(result.rejected, 6);
+        assert!(result.errors[0].ends_with(&format!("请求数超过每分钟上限 {}", MAX_REQUESTS_PER_MINUTE)));
+        assert_eq!(result.errors[3], format!("第 3 条: 请求数超过每分钟上限 {}", MAX_REQUESTS_PER_MINUTE));
+        assert_eq!(result.errors[4], "第 4 条: model 包含不允许的字符 '<'");
+        assert_eq!(result.errors[5], format!("第 5 条: provider 超过 {} 个字符", MAX_NAME_LEN));
+    }
+
+    #[test]
+    fn merging_saturates_instead_of_overflowing() {
+        let mut bucket = MinuteBucket { success: u64::MAX - 1, ..MinuteBucket::default() };
+        bucket.errors.insert(ErrorClass::ServerError, u64::MAX);
+        let other = MinuteBucket {
+            success: 5,
+            errors: HashMap::from([(ErrorClass::ServerError, 5), (ErrorClass::RateLimit, u64::MAX)]),
+            latency: BTreeMap::from([(100, u64::MAX)]),
+        };
+        bucket.merge(&other);
+        bucket.merge(&other);
+        assert_eq!(bucket.success, u64::MAX);
+
+        let summary = summarize([bucket].iter());
+        assert_eq!(summary.requests, u64::MAX);
+        assert_eq!(summary.latency_p50_ms, Some(100));
+    }
+}
@@ -0,0 +1,835 @@
+//! 主动探测：定期向供应商 API 发送最小的鉴权请求
+//!
+//! 官方状态页面往往在故障发生数十分钟后才更新。探测直接调用 API（列出模型或生成 1 个 token），
+//! 记录是否成功、HTTP 状态码与耗时，在官方状态旁展示“探测状态”。
+//! 支持任意 OpenAI/Anthropic 兼容的地址，包括本地的模拟服务。
+//!
+//! `stream` 模式发起一次小的流式生成，额外记录首 token 耗时与输出速度，
+//! 并与该探测自身的滚动基线比较，明显变慢时标记为 `degraded`。
+
+use crate::config::CONFIG;
+use crate::discrepancy;
+use crate::sources::expand_env;
+use chrono::Utc;
+use reqwest::{Client, ClientBuilder, RequestBuilder, Response};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::fs;
+use std::path::PathBuf;
+use std::sync::{LazyLock, Mutex};
+use std::time::{Duration, Instant};
+use tokio::task::JoinSet;
+use tracing::{error, info, warn};
+
+/// 每个探测保留的历史样本数
+const HISTORY_LIMIT: usize = 1440;
+/// 计算基线使用的最近成功样本数，变慢的样本同样计入，
+/// 延迟长期变化后基线会在约半个窗口后跟上
+const BASELINE_WINDOW: usize = 20;
+/// 样本少于该数量时不判断是否变慢
+const BASELINE_MIN_SAMPLES: usize = 5;
+
+/// 各探测最近一次的结果
+static LATEST_RESULTS: LazyLock<Mutex<HashMap<String, ProbeResult>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// 流式探测的历史样本，保存在数据目录中，重启后基线继续有效
+static HISTORY: LazyLock<Mutex<HashMap<String, VecDeque<StreamSample>>>> =
+    LazyLock::new(|| Mutex::new(load_history()));
+
+/// 探测配置文件
+#[derive(Debug, Deserialize)]
+struct ProbesFile {
+    #[serde(default = "default_interval_secs")]
+    interval_secs: u64,
+    #[serde(default)]
+    probes: Vec<ProbeConfig>,
+}
+
+/// 单个探测
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ProbeConfig {
+    pub id: String,
+    /// 对应的供应商名称（status.json 中的键）
+    pub provider: String,
+    #[serde(default)]
+    pub api: ApiKind,
+    /// API 基础地址，如 `https://api.openai.com/v1`
+    pub base_url: String,
+    /// API 密钥，支持 `${VAR}` 引用环境变量
+    #[serde(default)]
+    pub api_key: Option<String>,
+    #[serde(default)]
+    pub request: ProbeRequest,
+    /// `completion`、`stream` 请求使用的模型
+    #[serde(default)]
+    pub model: Option<String>,
+    #[serde(default = "default_timeout_secs")]
+    pub timeout_secs: u64,
+    /// 耗时超过该值（毫秒）视为性能下降
+    #[serde(default = "default_slow_ms")]
+    pub slow_ms: u64,
+    /// `stream` 请求的提示词
+    #[serde(default = "default_prompt")]
+    pub prompt: String,
+    /// `stream` 请求的最大输出 token 数
+    #[serde(default = "default_max_tokens")]
+    pub max_tokens: u32,
+    /// 首 token 耗时超过基线该倍数，或输出速度低于基线的该分之一时视为变慢
+    #[serde(default = "default_slow_factor")]
+    pub slow_factor: f64,
+}
+
+/// API 协议
+#[derive(Debug, Clone, Copy, Default, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ApiKind {
+    #[default]
+    Openai,
+    Anthropic,
+}
+
+/// 探测请求类型
+#[derive(Debug, Clone, Copy, Default, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProbeRequest {
+    /// 列出模型，不消耗 token
+    #[default]
+    ListModels,
+    /// 生成 1 个 token
+    Completion,
+    /// 流式生成，记录首 token 耗时与输出速度
+    Stream,
+}
+
+/// 探测状态
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProbeStatus {
+    Up,
+    /// 成功但耗时超过 `slow_ms`，或明显慢于自身基线
+    Degraded,
+    /// 超时、连接失败、429 或 5xx
+    Down,
+    /// 401/403/404 等配置问题，不代表供应商故障
+    Misconfigured,
+}
+
+/// 单次探测结果
+#[derive(Debug, Clone, Serialize)]
+pub struct ProbeResult {
+    pub id: String,
+    pub provider: String,
+    pub request: ProbeRequest,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    pub status: ProbeStatus,
+    pub http_status: Option<u16>,
+    pub latency_ms: u64,
+    pub error: Option<String>,
+    pub checked_at: String,
+    /// 流式探测指标
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stream: Option<StreamMetrics>,
+    /// 流式探测的滚动基线
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub baseline: Option<Baseline>,
+    /// 是否明显慢于基线
+    pub slow: bool,
+}
+
+/// 流式探测指标
+#[derive(Debug, Clone, Serialize)]
+pub struct StreamMetrics {
+    /// 发出请求到收到响应头的耗时（包含连接与服务端排队）
+    pub headers_ms: u64,
+    /// 首 token 耗时
+    pub ttft_ms: u64,
+    /// 总耗时
+    pub total_ms: u64,
+    pub output_tokens: u64,
+    /// 输出速度（首 token 之后），单位 token/s
+    pub tokens_per_sec: f64,
+    /// 接口未返回用量时，以内容分片数估算 token 数
+    pub tokens_estimated: bool,
+}
+
+/// 最近成功样本的中位数
+#[derive(Debug, Clone, Serialize)]
+pub struct Baseline {
+    pub ttft_ms: u64,
+    pub tokens_per_sec: f64,
+    pub samples: usize,
+}
+
+/// 流式探测历史样本
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct StreamSample {
+    pub checked_at: String,
+    pub status: ProbeStatus,
+    pub headers_ms: Option<u64>,
+    pub ttft_ms: Option<u64>,
+    pub total_ms: u64,
+    pub tokens_per_sec: Option<f64>,
+    pub slow: bool,
+}
+
+fn default_interval_secs() -> u64 {
+    60
+}
+
+fn default_timeout_secs() -> u64 {
+    15
+}
+
+fn default_slow_ms() -> u64 {
+    5000
+}
+
+fn default_prompt() -> String {
+    "Count from 1 to 20, separated by spaces.".to_string()
+}
+
+fn default_max_tokens() -> u32 {
+    64
+}
+
+fn default_slow_factor() -> f64 {
+    2.0
+}
+
+/// 探测使用的 HTTP 客户端（允许 http，便于连接本地模拟服务）
+fn client() -> Result<Client, reqwest::Error> {
+    ClientBuilder::new()
+        .connect_timeout(Duration::from_secs(10))
+        .user_agent("LLM-Status-Monitor/1.0 (Rust/1.80.0)")
+        .use_rustls_tls()
+        .build()
+}
+
+fn load_file() -> Option<ProbesFile> {
+    let content = fs::read_to_string(CONFIG.probes_file).ok()?;
+    match serde_json::from_str(&content) {
+        Ok(file) => Some(file),
+        Err(e) => {
+            error!("❌ 解析探测配置 {} 失败: {}", CONFIG.probes_file, e);
+            None
+        }
+    }
+}
+
+/// 按配置的间隔持续运行探测，每轮重新读取配置，修改探测列表或间隔无需重启；
+/// 未配置任何探测时按默认间隔等待配置出现
+pub async fn run_forever() {
+    let client = match client() {
+        Ok(client) => client,
+        Err(e) => {
+            error!("❌ 创建探测客户端失败: {}", e);
+            return;
+        }
+    };
+
+    let mut enabled = None;
+    loop {
+        let started = Instant::now();
+        let (interval_secs, probes) = load_file()
+            .map(|f| (f.interval_secs, f.probes))
+            .unwrap_or((default_interval_secs(), Vec::new()));
+        if enabled != Some(!probes.is_empty()) {
+            enabled = Some(!probes.is_empty());
+            if probes.is_empty() {
+                info!("ℹ️ 未配置主动探测");
+            } else {
+                info!("📡 主动探测已启用，间隔 {} 秒", interval_secs);
+            }
+        }
+        if !probes.is_empty() {
+            run_all(&client, probes).await;
+        }
+        tokio::time::sleep(Duration::from_secs(interval_secs.max(1)).saturating_sub(started.elapsed())).await;
+    }
+}
+
+/// 并发运行一轮探测并保存结果
+async fn run_all(client: &Client, probes: Vec<ProbeConfig>) {
+    let mut tasks = JoinSet::new();
+    for probe in probes {
+        let client = client.clone();
+        tasks.spawn(async move {
+            let result = run_probe(&client, &probe).await;
+            discrepancy::record_probe(&result);
+            record_stream(result, probe.slow_factor)
+        });
+    }
+
+    let mut results = Vec::new();
+    while let Some(result) = tasks.join_next().await {
+        match result {
+            Ok(result) => results.push(result),
+            Err(e) => error!("❌ 探测任务异常退出: {}", e),
+        }
+    }
+
+    if results.iter().any(|r| r.request == ProbeRequest::Stream) {
+        save_history();
+    }
+
+    let mut latest = LATEST_RESULTS.lock().unwrap();
+    latest.retain(|id, _| results.iter().any(|r| &r.id == id));
+    for result in results {
+        latest.insert(result.id.clone(), result);
+    }
+}
+
+fn history_path() -> PathBuf {
+    PathBuf::from(CONFIG.data_dir).join("probe_history.json")
+}
+
+fn load_history() -> HashMap<String, VecDeque<StreamSample>> {
+    match fs::read_to_string(history_path()) {
+        Ok(content) => serde_json::from_str(&content).unwrap_or_else(|e| {
+            error!("❌ 解析 {} 失败，将重新记录探测历史: {}", history_path().display(), e);
+            HashMap::new()
+        }),
+        Err(_) => HashMap::new(),
+    }
+}
+
+fn save_history() {
+    let content = serde_json::to_string(&*HISTORY.lock().unwrap()).unwrap();
+    let result = fs::create_dir_all(CONFIG.data_dir).and_then(|_| fs::write(history_path(), content));
+    if let Err(e) = result {
+        error!("❌ 保存探测历史失败: {}", e);
+    }
+}
+
+/// 执行单个探测
+pub async fn run_probe(client: &Client, probe: &ProbeConfig) -> ProbeResult {
+    let started = Instant::now();
+    let timeout = Duration::from_secs(probe.timeout_secs);
+    let outcome = match build_request(client, probe) {
+        Ok(request) => request
+            .timeout(timeout)
+            .send()
+            .await
+            .map_err(|e| if e.is_timeout() { "请求超时".to_string() } else { e.to_string() }),
+        Err(e) => Err(e),
+    };
+    let headers_ms = started.elapsed().as_millis() as u64;
+
+    let mut stream = None;
+    let (status, http_status, error) = match outcome {
+        Ok(response) => {
+            let code = response.status();
+            let mut error = (!code.is_success()).then(|| {
+                format!("HTTP {} - {}", code.as_u16(), code.canonical_reason().unwrap_or("Unknown"))
+            });
+            let mut status = if code.is_success() {
+                ProbeStatus::Up
+            } else if code.as_u16() == 429 || code.is_server_error() {
+                ProbeStatus::Down
+            } else {
+                ProbeStatus::Misconfigured
+            };
+            if code.is_success() && probe.request == ProbeRequest::Stream {
+                match read_stream(response, started, headers_ms, timeout).await {
+                    Ok(metrics) => stream = Some(metrics),
+                    Err(e) => {
+                        status = ProbeStatus::Down;
+                        error = Some(e);
+                    }
+                }
+            }
+            (status, Some(code.as_u16()), error)
+        }
+        Err(e) => (ProbeStatus::Down, None, Some(e)),
+    };
+    let latency_ms = started.elapsed().as_millis() as u64;
+    let status = if status == ProbeStatus::Up && latency_ms > probe.slow_ms {
+        ProbeStatus::Degraded
+    } else {
+        status
+    };
+
+    match (status, &stream) {
+        (ProbeStatus::Up, Some(m)) => info!(
+            "📡 {} 探测正常 - 首token {}ms, {:.1} token/s, 共 {}ms",
+            probe.id, m.ttft_ms, m.tokens_per_sec, m.total_ms
+        ),
+        (ProbeStatus::Up, None) => info!("📡 {} 探测正常 ({}ms)", probe.id, latency_ms),
+        _ => warn!("⚠️ {} 探测异常: {:?} ({}ms) {}", probe.id, status, latency_ms, error.as_deref().unwrap_or_default()),
+    }
+
+    ProbeResult {
+        id: probe.id.clone(),
+        provider: probe.provider.clone(),
+        request: probe.request,
+        model: probe.model.clone().filter(|_| probe.request != ProbeRequest::ListModels),
+        status,
+        http_status,
+        latency_ms,
+        error,
+        checked_at: Utc::now().to_rfc3339(),
+        stream,
+        baseline: None,
+        slow: false,
+    }
+}
+
+fn build_request(client: &Client, probe: &ProbeConfig) -> Result<RequestBuilder, String> {
+    let base = probe.base_url.trim_end_matches('/');
+    let model = || probe.model.clone().ok_or_else(|| "completion/stream 探测需要配置 model".to_string());
+    let (prompt, max_tokens, stream) = match probe.request {
+        ProbeRequest::Stream => (probe.prompt.as_str(), probe.max_tokens, true),
+        _ => ("ping", 1, false),
+    };
+
+    let request = match (probe.api, probe.request) {
+        (_, ProbeRequest::ListModels) => client.get(format!("{}/models", base)),
+        (ApiKind::Openai, _) => {
+            let mut body = serde_json::json!({
+                "model": model()?,
+                "messages": [{ "role": "user", "content": prompt }],
+                "max_tokens": max_tokens,
+            });
+            if stream {
+                body["stream"] = true.into();
+                // 在最后一个分片中返回用量，用于统计输出 token 数
+                body["stream_options"] = serde_json::json!({ "include_usage": true });
+            }
+            client.post(format!("{}/chat/completions", base)).json(&body)
+        }
+        (ApiKind::Anthropic, _) => {
+            let mut body = serde_json::json!({
+                "model": model()?,
+                "messages": [{ "role": "user", "content": prompt }],
+                "max_tokens": max_tokens,
+            });
+            if stream {
+                body["stream"] = true.into();
+            }
+            client.post(format!("{}/messages", base)).json(&body)
+        }
+    };
+
+    Ok(authorize(request, probe.api, probe.api_key.as_deref()))
+}
+
+/// 按 API 协议添加鉴权请求头，密钥支持 `${VAR}` 引用环境变量
+pub fn authorize(request: RequestBuilder, api: ApiKind, api_key: Option<&str>) -> RequestBuilder {
+    let api_key = api_key.map(expand_env).filter(|k| !k.is_empty());
+    match (api, api_key) {
+        (ApiKind::Openai, Some(key)) => request.bearer_auth(key),
+        (ApiKind::Anthropic, Some(key)) => request
+            .header("x-api-key", key)
+            .header("anthropic-version", "2023-06-01"),
+        (ApiKind::Anthropic, None) => request.header("anthropic-version", "2023-06-01"),
+        (ApiKind::Openai, None) => request,
+    }
+}
+
+/// 读取 SSE 响应，统计首 token 耗时与输出速度
+async fn read_stream(
+    mut response: Response,
+    started: Instant,
+    headers_ms: u64,
+    timeout: Duration,
+) -> Result<StreamMetrics, String> {
+    let mut buffer = String::new();
+    let mut ttft_ms = None;
+    let mut chunks = 0u64;
+    let mut usage_tokens = None;
+
+    loop {
+        let remaining = timeout.saturating_sub(started.elapsed());
+        let chunk = match tokio::time::timeout(remaining, response.chunk()).await {
+            Ok(Ok(Some(chunk))) => chunk,
+            Ok(Ok(None)) => break,
+            Ok(Err(e)) => return Err(format!("读取流失败: {}", e)),
+            Err(_) => return Err("流式响应超时".to_string()),
+        };
+        buffer.push_str(&String::from_utf8_lossy(&chunk));
+
+        while let Some(end) = buffer.find('\n') {
+            let line: String = buffer.drain(..=end).collect();
+            let Some(data) = line.trim().strip_prefix("data:").map(str::trim) else {
+                continue;
+            };
+            let Ok(event) = serde_json::from_str::<serde_json::Value>(data) else {
+                continue;
+            };
+            if let Some(message) = event.get("error") {
+                return Err(format!("流中返回错误: {}", message));
+            }
+            let (has_content, tokens) = parse_event(&event);
+            if has_content {
+                chunks += 1;
+                ttft_ms.get_or_insert(started.elapsed().as_millis() as u64);
+            }
+            if tokens.is_some() {
+                usage_tokens = tokens;
+            }
+        }
+    }
+
+    let ttft_ms = ttft_ms.ok_or("流式响应中没有任何输出内容")?;
+    let total_ms = started.elapsed().as_millis() as u64;
+    let output_tokens = usage_tokens.unwrap_or(chunks);
+    // 首 token 之后的生成阶段，至少按 1ms 计算
+    let generation_secs = (total_ms.saturating_sub(ttft_ms)).max(1) as f64 / 1000.0;
+    let tokens_per_sec = output_tokens.saturating_sub(1) as f64 / generation_secs;
+
+    Ok(StreamMetrics {
+        headers_ms,
+        ttft_ms,
+        total_ms,
+        output_tokens,
+        tokens_per_sec: (tokens_per_sec * 10.0).round() / 10.0,
+        tokens_estimated: usage_tokens.is_none(),
+    })
+}
+
+/// 解析单个 SSE 事件，返回是否包含输出内容以及用量中的输出 token 数
+fn parse_event(event: &serde_json::Value) -> (bool, Option<u64>) {
+    let non_empty = |value: Option<&serde_json::Value>| value.and_then(|v| v.as_str()).is_some_and(|s| !s.is_empty());
+
+    // OpenAI: choices[].delta.content，最后一个分片携带 usage.completion_tokens
+    let openai_content = event
+        .get("choices")
+        .and_then(|c| c.as_array())
+        .is_some_and(|choices| choices.iter().any(|c| non_empty(c.pointer("/delta/content"))));
+    // Anthropic: content_block_delta 携带 delta.text，message_delta 携带 usage.output_tokens
+    let anthropic_content = event.get("type").and_then(|t| t.as_str()) == Some("content_block_delta")
+        && non_empty(event.pointer("/delta/text"));
+
+    let tokens = event
+        .pointer("/usage/completion_tokens")
+        .or_else(|| event.pointer("/usage/output_tokens"))
+        .and_then(|v| v.as_u64());
+    (openai_content || anthropic_content, tokens)
+}
+
+/// 将流式探测结果与滚动基线比较并记录到历史
+fn record_stream(mut result: ProbeResult, slow_factor: f64) -> ProbeResult {
+    if result.request != ProbeRequest::Stream {
+        return result;
+    }
+
+    let mut history = HISTORY.lock().unwrap();
+    let samples = history.entry(result.id.clone()).or_default();
+    result.baseline = baseline(samples);
+    if let (Some(metrics), Some(baseline)) = (&result.stream, &result.baseline) {
+        result.slow = is_slow(metrics, baseline, slow_factor);
+        if result.slow && result.status == ProbeStatus::Up {
+            result.status = ProbeStatus::Degraded;
+            warn!(
+                "🐢 {} 明显慢于基线 - 首token {}ms（基线 {}ms）, {:.1} token/s（基线 {:.1}）",
+                result.id, metrics.ttft_ms, baseline.ttft_ms, metrics.tokens_per_sec, baseline.tokens_per_sec
+            );
+        }
+    }
+
+    samples.push_back(StreamSample {
+        checked_at: result.checked_at.clone(),
+        status: result.status,
+        headers_ms: result.stream.as_ref().map(|m| m.headers_ms),
+        ttft_ms: result.stream.as_ref().map(|m| m.ttft_ms),
+        total_ms: result.latency_ms,
+        tokens_per_sec: result.stream.as_ref().map(|m| m.tokens_per_sec),
+        slow: result.slow,
+    });
+    while samples.len() > HISTORY_LIMIT {
+        samples.pop_front();
+    }
+    result
+}
+
+/// 最近成功样本的中位数，样本不足时返回 None
+fn baseline(samples: &VecDeque<StreamSample>) -> Option<Baseline> {
+    let recent: Vec<&StreamSample> = samples
+        .iter()
+        .rev()
+        .filter(|s| s.ttft_ms.is_some())
+        .take(BASELINE_WINDOW)
+        .collect();
+    if recent.len() < BASELINE_MIN_SAMPLES {
+        return None;
+    }
+
+    let mut ttft: Vec<u64> = recent.iter().filter_map(|s| s.ttft_ms).collect();
+    let mut speed: Vec<f64> = recent.iter().filter_map(|s| s.tokens_per_sec).collect();
+    ttft.sort_unstable();
+    speed.sort_by(f64::total_cmp);
+    Some(Baseline {
+        ttft_ms: ttft[ttft.len() / 2],
+        tokens_per_sec: speed[speed.len() / 2],
+        samples: recent.len(),
+    })
+}
+
+fn is_slow(metrics: &StreamMetrics, baseline: &Baseline, slow_factor: f64) -> bool {
+    let slow_ttft = metrics.ttft_ms as f64 > baseline.ttft_ms as f64 * slow_factor;
+    let slow_speed = baseline.tokens_per_sec > 0.0 && metrics.tokens_per_sec < baseline.tokens_per_sec / slow_factor;
+    slow_ttft || slow_speed
+}
+
+/// 全部探测的最近结果
+pub fn latest_results() -> Vec<ProbeResult> {
+    let mut results: Vec<ProbeResult> = LATEST_RESULTS.lock().unwrap().values().cloned().collect();
+    results.sort_by(|a, b| a.id.cmp(&b.id));
+    results
+}
+
+/// 流式探测的历史样本，探测不存在时返回 None
+pub fn stream_history(id: &str) -> Option<Vec<StreamSample>> {
+    HISTORY.lock().unwrap().get(id).map(|samples| samples.iter().cloned().collect())
+}
+
+/// 按供应商名称分组的最近结果，写入 status.json
+pub fn results_by_provider() -> serde_json::Value {
+    let mut grouped: serde_json::Map<String, serde_json::Value> = serde_json::Map::new();
+    for result in latest_results() {
+        let entry = grouped
+            .entry(result.provider.clone())
+            .or_insert_with(|| serde_json::Value::Array(Vec::new()));
+        if let serde_json::Value::Array(list) = entry {
+            list.push(serde_json::to_value(&result).unwrap());
+        }
+    }
+    grouped.into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{http::HeaderMap, routing::{get, post}, Json, Router};
+
+    /// 启动本地模拟 API，返回基础地址
+    async fn mock_server() -> String {
+        let app = Router::new()
+            .route(
+                "/v1/models",
+                get(|headers: HeaderMap| async move {
+                    let authorized = headers.get("authorization").and_then(|v| v.to_str().ok()) == Some("Bearer test-key")
+                        || headers.get("x-api-key").and_then(|v| v.to_str().ok()) == Some("test-key");
+                    if authorized {
+                        (axum::http::StatusCode::OK, Json(serde_json::json!({ "data": [] })))
+                    } else {
+                        (axum::http::StatusCode::UNAUTHORIZED, Json(serde_json::json!({ "error": "unauthorized" })))
+                    }
+                }),
+            )
+            .route(
+                "/v1/chat/completions",
+                post(|| async { (axum::http::StatusCode::SERVICE_UNAVAILABLE, "overloaded") }),
+            )
+            .route(
+                "/ok/v1/chat/completions",
+                post(|| async {
+                    let chunks = ["1", " 2", " 3", " 4"]
+                        .iter()
+                        .map(|c| format!("data: {}\n\n", serde_json::json!({ "choices": [{ "delta": { "content": c } }] })))
+                        .collect::<String>();
+                    let usage = serde_json::json!({ "choices": [], "usage": { "completion_tokens": 4 } });
+                    format!("{}data: {}\n\ndata: [DONE]\n\n", chunks, usage)
+                }),
+            )
+            .route(
+                "/ok/v1/messages",
+                post(|| async {
+                    [
+                        "event: message_start\ndata: {\"type\":\"message_start\"}\n\n",
+                        "event: content_block_delta\ndata: {\"type\":\"content_block_delta\",\"delta\":{\"type\":\"text_delta\",\"text\":\"1 2\"}}\n\n",
+                        "event: content_block_delta\ndata: {\"type\":\"content_block_delta\",\"delta\":{\"type\":\"text_delta\",\"text\":\" 3\"}}\n\n",
+                        "event: message_delta\ndata: {\"type\":\"message_delta\",\"usage\":{\"output_tokens\":3}}\n\n",
+                    ]
+                    .concat()
+                }),
+            )
+            .route(
+                "/v1/messages",
+                post(|| async {
+                    tokio::time::sleep(Duration::from_millis(50)).await;
+                    Json(serde_json::json!({ "content": [{ "type": "text", "text": "p" }] }))
+                }),
+            );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        format!("http://{}/v1", addr)
+    }
+
+    fn probe(base_url: &str, api: ApiKind, request: ProbeRequest, api_key: Option<&str>) -> ProbeConfig {
+        ProbeConfig {
+            id: "mock".to_string(),
+            provider: "Mock".to_string(),
+            api,
+            base_url: base_url.to_string(),
+            api_key: api_key.map(str::to_string),
+            request,
+            model: Some("mock-model".to_string()),
+            timeout_secs: 5,
+            slow_ms: 5000,
+            prompt: default_prompt(),
+            max_tokens: default_max_tokens(),
+            slow_factor: default_slow_factor(),
+        }
+    }
+
+    #[tokio::test]
+    async fn list_models_up_with_valid_key() {
+        let base = mock_server().await;
+        let result = run_probe(&client().unwrap(), &probe(&base, ApiKind::Openai, ProbeRequest::ListModels, Some("test-key"))).await;
+        assert_eq!(result.status, ProbeStatus::Up);
+        assert_eq!(result.http_status, Some(200));
+    }
+
+    #[tokio::test]
+    async fn bad_key_is_misconfigured_not_down() {
+        let base = mock_server().await;
+        let result = run_probe(&client().unwrap(), &probe(&base, ApiKind::Openai, ProbeRequest::ListModels, Some("wrong"))).await;
+        assert_eq!(result.status, ProbeStatus::Misconfigured);
+        assert_eq!(result.http_status, Some(401));
+    }
+
+    #[tokio::test]
+    async fn server_error_is_down() {
+        let base = mock_server().await;
+        let result = run_probe(&client().unwrap(), &probe(&base, ApiKind::Openai, ProbeRequest::Completion, Some("test-key"))).await;
+        assert_eq!(result.status, ProbeStatus::Down);
+        assert_eq!(result.http_status, Some(503));
+    }
+
+    #[tokio::test]
+    async fn anthropic_completion_slow_is_degraded() {
+        let base = mock_server().await;
+        let mut config = probe(&base, ApiKind::Anthropic, ProbeRequest::Completion, Some("test-key"));
+        config.slow_ms = 10;
+        let result = run_probe(&client().unwrap(), &config).await;
+        assert_eq!(result.status, ProbeStatus::Degraded);
+        assert!(result.latency_ms >= 50);
+    }
+
+    #[tokio::test]
+    async fn unreachable_host_is_down() {
+        let result = run_probe(&client().unwrap(), &probe("http://127.0.0.1:1/v1", ApiKind::Openai, ProbeRequest::ListModels, None)).await;
+        assert_eq!(result.status, ProbeStatus::Down);
+        assert!(result.http_status.is_none());
+    }
+
+    #[tokio::test]
+    async fn openai_stream_uses_reported_usage() {
+        let base = mock_server().await.replace("/v1", "/ok/v1");
+        let result = run_probe(&client().unwrap(), &probe(&base, ApiKind::Openai, ProbeRequest::Stream, Some("test-key"))).await;
+        assert_eq!(result.status, ProbeStatus::Up);
+        let metrics = result.stream.unwrap();
+        assert_eq!(metrics.output_tokens, 4);
+        assert!(!metrics.tokens_estimated);
+        assert!(metrics.ttft_ms <= metrics.total_ms);
+    }
+
+    #[tokio::test]
+    async fn anthropic_stream_reads_content_deltas() {
+        let base = mock_server().await.replace("/v1", "/ok/v1");
+        let result = run_probe(&client().unwrap(), &probe(&base, ApiKind::Anthropic, ProbeRequest::Stream, Some("test-key"))).await;
+        assert_eq!(result.status, ProbeStatus::Up);
+        assert_eq!(result.stream.unwrap().output_tokens, 3);
+    }
+
+    #[tokio::test]
+    async fn stream_without_content_is_down() {
+        let base = mock_server().await;
+        let mut config = probe(&base, ApiKind::Anthropic, ProbeRequest::Stream, Some("test-key"));
+        config.id = "empty-stream".to_string();
+        let result = run_probe(&client().unwrap(), &config).await;
+        assert_eq!(result.status, ProbeStatus::Down);
+        assert!(result.stream.is_none());
+    }
+
+    fn stream_result(id: &str, ttft_ms: u64, tokens_per_sec: f64) -> ProbeResult {
+        ProbeResult {
+            id: id.to_string(),
+            provider: "Mock".to_string(),
+            request: ProbeRequest::Stream,
+            model: None,
+            status: ProbeStatus::Up,
+            http_status: Some(200),
+            latency_ms: ttft_ms + 500,
+            error: None,
+            checked_at: Utc::now().to_rfc3339(),
+            stream: Some(StreamMetrics {
+                headers_ms: 100,
+                ttft_ms,
+                total_ms: ttft_ms + 500,
+                output_tokens: 30,
+                tokens_per_sec,
+                tokens_estimated: false,
+            }),
+            baseline: None,
+            slow: false,
+        }
+    }
+
+    #[test]
+    fn slow_relative_to_rolling_baseline() {
+        let id = "baseline-test";
+        for ttft in [300, 320, 280, 310, 290] {
+            let result = record_stream(stream_result(id, ttft, 60.0), 2.0);
+            assert!(!result.slow);
+        }
+
+        let normal = record_stream(stream_result(id, 400, 55.0), 2.0);
+        assert_eq!(normal.baseline.as_ref().unwrap().ttft_ms, 300);
+        assert_eq!(normal.status, ProbeStatus::Up);
+
+        let slow_ttft = record_stream(stream_result(id, 900, 60.0), 2.0);
+        assert!(slow_ttft.slow);
+        assert_eq!(slow_ttft.status, ProbeStatus::Degraded);
+
+        let slow_speed = record_stream(stream_result(id, 300, 20.0), 2.0);
+        assert!(slow_speed.slow);
+
+        // 变慢的样本同样计入基线
+        assert_eq!(stream_history(id).unwrap().len(), 8);
+        let next = record_stream(stream_result(id, 300, 60.0), 2.0);
+        assert_eq!(next.baseline.unwrap().samples, 8);
+    }
+
+    #[test]
+    fn baseline_follows_a_lasting_latency_change() {
+        let id = "baseline-shift";
+        for _ in 0..BASELINE_WINDOW {
+            record_stream(stream_result(id, 300, 60.0), 2.0);
+        }
+
+        // 延迟长期翻三倍：起初判为变慢，约半个窗口后基线跟上
+        let slow: Vec<bool> = (0..BASELINE_WINDOW).map(|_| record_stream(stream_result(id, 900, 60.0), 2.0).slow).collect();
+        assert!(slow[0]);
+        assert!(!slow[BASELINE_WINDOW - 1]);
+        let last = record_stream(stream_result(id, 900, 60.0), 2.0);
+        assert_eq!(last.baseline.unwrap().ttft_ms, 900);
+        assert_eq!(last.status, ProbeStatus::Up);
+    }
+
+    #[test]
+    fn history_round_trips() {
+        let history = HashMap::from([(
+            "persisted".to_string(),
+            VecDeque::from([StreamSample {
+                checked_at: "2025-06-01T00:00:00+00:00".to_string(),
+                status: ProbeStatus::Degraded,
+                headers_ms: Some(120),
+                ttft_ms: Some(300),
+                total_ms: 800,
+                tokens_per_sec: Some(60.5),
+                slow: true,
+            }]),
+        )]);
+        let json = serde_json::to_string(&history).unwrap();
+        let loaded: HashMap<String, VecDeque<StreamSample>> = serde_json::from_str(&json).unwrap();
+        let sample = &loaded["persisted"][0];
+        assert_eq!(sample.status, ProbeStatus::Degraded);
+        assert_eq!(sample.headers_ms, Some(120));
+        assert!(sample.slow);
+    }
+}
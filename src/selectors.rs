@@ -0,0 +1,188 @@
+use crate::config::CONFIG;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::sync::{Arc, LazyLock, RwLock};
+use std::time::SystemTime;
+use tracing::{info, warn};
+
+/// 当前生效的选择器及其来源文件的修改时间
+static SELECTORS: LazyLock<RwLock<LoadedSelectors>> =
+    LazyLock::new(|| RwLock::new(LoadedSelectors::load()));
+
+/// Google AI Studio 状态页面的CSS选择器
+///
+/// 列表类字段按优先级排列，第一个为首选选择器，其余为兜底。
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct GoogleSelectors {
+    /// 选择器文件版本，随诊断报告一起输出
+    pub version: String,
+    pub page_container: Vec<String>,
+    pub overall_status: Vec<String>,
+    pub dashboards_container: Vec<String>,
+    pub service_name: String,
+    pub status_dashboard: String,
+    pub timeline_day: String,
+    pub incident_dialog: Vec<String>,
+    pub severity: SeverityClasses,
+}
+
+/// 时间轴上表示故障等级的class
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct SeverityClasses {
+    pub major: String,
+    pub moderate: String,
+    pub minor: String,
+}
+
+impl Default for GoogleSelectors {
+    /// 内置选择器，在选择器文件缺失或无效时使用
+    ///
+    /// 与 `config/google_selectors.json` 同源，编译时嵌入，避免两处默认值不一致。
+    fn default() -> Self {
+        let mut selectors = parse(include_str!("../config/google_selectors.json")).expect("内置选择器文件无效");
+        selectors.version = "builtin".to_string();
+        selectors
+    }
+}
+
+struct LoadedSelectors {
+    selectors: Arc<GoogleSelectors>,
+    modified: Option<SystemTime>,
+}
+
+impl LoadedSelectors {
+    /// 首次加载，文件无效时回退到内置选择器
+    fn load() -> Self {
+        let modified = file_modified();
+        let selectors = match read_file() {
+            Ok(selectors) => {
+                info!("🎯 已加载选择器文件 {} (版本 {})", CONFIG.selectors_file, selectors.version);
+                selectors
+            }
+            Err(e) => {
+                warn!("⚠️ 加载选择器文件失败，使用内置选择器: {}", e);
+                GoogleSelectors::default()
+            }
+        };
+        Self {
+            selectors: Arc::new(selectors),
+            modified,
+        }
+    }
+}
+
+/// 获取当前选择器，文件被修改时自动重新加载
+pub fn current() -> Arc<GoogleSelectors> {
+    let modified = file_modified();
+    {
+        let loaded = SELECTORS.read().unwrap();
+        if loaded.modified == modified {
+            return loaded.selectors.clone();
+        }
+    }
+
+    match reload() {
+        Ok(selectors) => selectors,
+        Err(e) => {
+            warn!("⚠️ 选择器文件已修改但无法加载，继续使用旧版本: {}", e);
+            let mut loaded = SELECTORS.write().unwrap();
+            // 记录修改时间，避免每次爬取都重复报错
+            loaded.modified = modified;
+            loaded.selectors.clone()
+        }
+    }
+}
+
+/// 立即从文件重新加载选择器，失败时保留当前版本
+pub fn reload() -> Result<Arc<GoogleSelectors>, String> {
+    let modified = file_modified();
+    let selectors = Arc::new(read_file()?);
+    let mut loaded = SELECTORS.write().unwrap();
+    if loaded.selectors.version != selectors.version {
+        info!("🔄 选择器已更新: {} -> {}", loaded.selectors.version, selectors.version);
+    }
+    loaded.selectors = selectors.clone();
+    loaded.modified = modified;
+    Ok(selectors)
+}
+
+fn read_file() -> Result<GoogleSelectors, String> {
+    let content = fs::read_to_string(CONFIG.selectors_file)
+        .map_err(|e| format!("读取 {} 失败: {}", CONFIG.selectors_file, e))?;
+    parse(&content).map_err(|e| format!("{}: {}", CONFIG.selectors_file, e))
+}
+
+/// 解析并校验选择器，任何选择器或故障等级 class 为空都会导致匹配失控，因此直接拒绝
+fn parse(content: &str) -> Result<GoogleSelectors, String> {
+    let selectors: GoogleSelectors = serde_json::from_str(content).map_err(|e| format!("解析失败: {}", e))?;
+
+    let lists = [
+        ("page_container", &selectors.page_container),
+        ("overall_status", &selectors.overall_status),
+        ("dashboards_container", &selectors.dashboards_container),
+        ("incident_dialog", &selectors.incident_dialog),
+    ];
+    for (name, list) in lists {
+        if list.is_empty() || list.iter().any(|s| s.trim().is_empty()) {
+            return Err(format!("选择器 {} 不能为空", name));
+        }
+    }
+
+    let fields = [
+        ("service_name", &selectors.service_name),
+        ("status_dashboard", &selectors.status_dashboard),
+        ("timeline_day", &selectors.timeline_day),
+        ("severity.major", &selectors.severity.major),
+        ("severity.moderate", &selectors.severity.moderate),
+        ("severity.minor", &selectors.severity.minor),
+    ];
+    if let Some((name, _)) = fields.iter().find(|(_, value)| value.trim().is_empty()) {
+        return Err(format!("选择器 {} 不能为空", name));
+    }
+    Ok(selectors)
+}
+
+fn file_modified() -> Option<SystemTime> {
+    fs::metadata(CONFIG.selectors_file)
+        .and_then(|m| m.modified())
+        .ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SHIPPED: &str = include_str!("../config/google_selectors.json");
+
+    fn with(pointer: &str, value: serde_json::Value) -> String {
+        let mut json: serde_json::Value = serde_json::from_str(SHIPPED).unwrap();
+        *json.pointer_mut(pointer).unwrap() = value;
+        json.to_string()
+    }
+
+    #[test]
+    fn shipped_file_is_valid_and_is_the_default() {
+        let shipped = parse(SHIPPED).unwrap();
+        let builtin = GoogleSelectors::default();
+        assert_eq!(builtin.version, "builtin");
+        assert_eq!(builtin.service_name, shipped.service_name);
+        assert_eq!(builtin.page_container, shipped.page_container);
+        assert_eq!(builtin.severity.major, shipped.severity.major);
+    }
+
+    #[test]
+    fn rejects_empty_selectors() {
+        let cases = [
+            ("/severity/major", serde_json::json!(""), "severity.major"),
+            ("/severity/minor", serde_json::json!("  "), "severity.minor"),
+            ("/service_name", serde_json::json!(""), "service_name"),
+            ("/incident_dialog", serde_json::json!([]), "incident_dialog"),
+            ("/page_container", serde_json::json!(["body", ""]), "page_container"),
+        ];
+        for (pointer, value, name) in cases {
+            let error = parse(&with(pointer, value)).unwrap_err();
+            assert_eq!(error, format!("选择器 {} 不能为空", name));
+        }
+        assert!(parse("{}").unwrap_err().starts_with("解析失败"));
+    }
+}
@@ -0,0 +1,558 @@
+//! 公告数据源：没有状态页面的供应商只发布故障/维护公告
+//!
+//! 从公告列表（HTML 或 JSON）中提取标题与发布时间，按关键词把近期公告归类为
+//! 故障、恢复或维护：每条恢复公告解决标题与其最相近的一条更早的故障，其余故障计入组件状态。
+
+use super::{build_summary, html, json, ProviderConfig, ScrapedComponent};
+use crate::component_ids;
+use crate::models::{
+    ComponentStatus, Incident, IncidentImpact, IncidentStatus, IncidentUpdate, IncidentUpdateStatus,
+    MaintenanceStatus, ScheduledMaintenance, StatusPageSummary,
+};
+use chrono::{DateTime, Duration, FixedOffset, NaiveDate, NaiveDateTime, TimeZone, Utc};
+use regex::Regex;
+use scraper::Html;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+use std::error::Error;
+use std::sync::LazyLock;
+use tracing::info;
+
+/// 从文本中截取日期时间，兼容 `2025-06-01 10:00`、`2025/6/1`、`2025年6月1日 10:00` 等写法
+static DATE_PATTERN: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(\d{4})[-/.年](\d{1,2})[-/.月](\d{1,2})日?(?:[\sT]*(\d{1,2}):(\d{2})(?::(\d{2}))?)?").unwrap()
+});
+
+/// 公告数据源配置
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct AnnouncementsConfig {
+    /// 公告列表地址
+    pub url: String,
+    /// 额外请求头，值中的 `${VAR}` 会替换为环境变量
+    #[serde(default)]
+    pub headers: HashMap<String, String>,
+    #[serde(default)]
+    pub format: AnnouncementFormat,
+    /// 公告条目：HTML 为CSS选择器，JSON 为 JSONPath
+    pub list: String,
+    /// 标题（相对于条目）
+    pub title: String,
+    /// 发布时间（相对于条目）
+    pub date: String,
+    /// 链接（相对于条目），HTML 读取 `href` 属性
+    #[serde(default)]
+    pub link: Option<String>,
+    /// 摘要（相对于条目）
+    #[serde(default)]
+    pub body: Option<String>,
+    /// 公告列表包含多个产品时，只保留标题包含其中任一文本的公告
+    #[serde(default)]
+    pub title_filter: Vec<String>,
+    /// 受故障影响的组件名称
+    #[serde(default = "default_component")]
+    pub component: String,
+    #[serde(default = "default_incident_keywords")]
+    pub incident_keywords: Vec<String>,
+    #[serde(default = "default_maintenance_keywords")]
+    pub maintenance_keywords: Vec<String>,
+    #[serde(default = "default_resolved_keywords")]
+    pub resolved_keywords: Vec<String>,
+    /// 只考虑该时长（小时）内发布的公告
+    #[serde(default = "default_max_age_hours")]
+    pub max_age_hours: i64,
+    /// 不带时区的发布时间所在的 UTC 偏移（小时），默认北京时间
+    #[serde(default = "default_utc_offset_hours")]
+    pub utc_offset_hours: i32,
+}
+
+/// 公告列表格式
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AnnouncementFormat {
+    #[default]
+    Html,
+    Json,
+}
+
+fn default_component() -> String {
+    "API".to_string()
+}
+
+fn default_incident_keywords() -> Vec<String> {
+    ["故障", "异常", "中断", "不可用", "报错", "outage", "degraded", "disruption"]
+        .map(String::from)
+        .to_vec()
+}
+
+fn default_maintenance_keywords() -> Vec<String> {
+    ["维护", "停服", "maintenance"].map(String::from).to_vec()
+}
+
+fn default_resolved_keywords() -> Vec<String> {
+    ["恢复", "已解决", "已修复", "resolved"].map(String::from).to_vec()
+}
+
+fn default_max_age_hours() -> i64 {
+    72
+}
+
+fn default_utc_offset_hours() -> i32 {
+    8
+}
+
+/// 公告条目
+#[derive(Debug)]
+struct Announcement {
+    title: String,
+    published: DateTime<Utc>,
+    link: Option<String>,
+    body: Option<String>,
+}
+
+/// 公告类别
+enum Kind {
+    Incident,
+    Resolved,
+    Maintenance,
+}
+
+impl AnnouncementsConfig {
+    fn classify(&self, title: &str) -> Option<Kind> {
+        let title = title.to_lowercase();
+        let any = |keywords: &[String]| keywords.iter().any(|k| title.contains(&k.to_lowercase()));
+        if !self.title_filter.is_empty() && !any(&self.title_filter) {
+            None
+        } else if any(&self.resolved_keywords) {
+            Some(Kind::Resolved)
+        } else if any(&self.maintenance_keywords) {
+            Some(Kind::Maintenance)
+        } else if any(&self.incident_keywords) {
+            Some(Kind::Incident)
+        } else {
+            None
+        }
+    }
+
+    /// 标题中用于配对故障与恢复公告的词：去掉产品过滤词与分类关键词后，
+    /// 英文按词切分，中文按相邻两字切分
+    fn title_terms(&self, title: &str) -> HashSet<String> {
+        let mut text = title.to_lowercase();
+        let keywords = self
+            .title_filter
+            .iter()
+            .chain(&self.incident_keywords)
+            .chain(&self.maintenance_keywords)
+            .chain(&self.resolved_keywords);
+        for keyword in keywords.filter(|k| !k.is_empty()) {
+            text = text.replace(&keyword.to_lowercase(), " ");
+        }
+
+        let mut terms = HashSet::new();
+        let mut flush = |run: &mut Vec<char>, ascii: bool| {
+            if ascii {
+                if run.len() >= 2 {
+                    terms.insert(run.iter().collect());
+                }
+            } else if run.len() == 1 {
+                terms.insert(run[0].to_string());
+            } else {
+                terms.extend(run.windows(2).map(|pair| pair.iter().collect::<String>()));
+            }
+            run.clear();
+        };
+        let (mut run, mut ascii) = (Vec::new(), true);
+        for c in text.chars() {
+            let word = c.is_ascii_alphanumeric() || (c == '-' || c == '.') && ascii && !run.is_empty();
+            let cjk = !c.is_ascii() && c.is_alphanumeric();
+            if (word && !ascii) || (cjk && ascii) || !(word || cjk) {
+                flush(&mut run, ascii);
+            }
+            if word || cjk {
+                ascii = word;
+                run.push(c);
+            }
+        }
+        flush(&mut run, ascii);
+        terms
+    }
+}
+
+/// 两组词的重合程度：共有词数占较小一组的比例
+fn overlap(a: &HashSet<String>, b: &HashSet<String>) -> f64 {
+    let smaller = a.len().min(b.len());
+    if smaller == 0 {
+        return 0.0;
+    }
+    a.intersection(b).count() as f64 / smaller as f64
+}
+
+/// 恢复公告与故障标题至少有这一比例的词重合时才视为同一事件
+const MIN_OVERLAP: f64 = 0.5;
+
+/// 解析公告列表，`now` 用于判断公告是否过期
+pub fn parse(
+    provider: &ProviderConfig,
+    config: &AnnouncementsConfig,
+    body: &str,
+    now: DateTime<Utc>,
+) -> Result<StatusPageSummary, Box<dyn Error + Send + Sync>> {
+    let offset = FixedOffset::east_opt(config.utc_offset_hours * 3600).ok_or("无效的 UTC 偏移")?;
+    let entries = match config.format {
+        AnnouncementFormat::Html => extract_html(config, body, offset)?,
+        AnnouncementFormat::Json => extract_json(config, body, offset)?,
+    };
+    if entries.is_empty() {
+        return Err(format!("公告列表中未找到任何条目: {}", config.list).into());
+    }
+
+    let cutoff = now - Duration::hours(config.max_age_hours);
+    let mut recent: Vec<(&Announcement, Kind)> = entries
+        .iter()
+        .filter(|a| a.published >= cutoff)
+        .filter_map(|a| config.classify(&a.title).map(|kind| (a, kind)))
+        .collect();
+    recent.sort_by_key(|(a, _)| std::cmp::Reverse(a.published));
+
+    // 按发布时间正序处理恢复公告，每条只解决一条发布在它之前、尚未解决且标题最相近的故障；
+    // 与任何故障都不相近的恢复公告不解决任何故障
+    let terms: Vec<HashSet<String>> = recent.iter().map(|(a, _)| config.title_terms(&a.title)).collect();
+    let mut resolved: HashMap<usize, DateTime<Utc>> = HashMap::new();
+    for (i, (resolution, _)) in recent.iter().enumerate().rev().filter(|(_, (_, kind))| matches!(kind, Kind::Resolved)) {
+        let best = recent
+            .iter()
+            .enumerate()
+            .filter(|(j, (incident, kind))| {
+                matches!(kind, Kind::Incident) && incident.published <= resolution.published && !resolved.contains_key(j)
+            })
+            .map(|(j, (incident, _))| (j, overlap(&terms[i], &terms[j]), incident.published))
+            .filter(|(_, score, _)| *score >= MIN_OVERLAP)
+            .max_by(|a, b| a.1.total_cmp(&b.1).then(a.2.cmp(&b.2)));
+        if let Some((j, _, _)) = best {
+            resolved.insert(j, resolution.published);
+        }
+    }
+
+    let mut incidents = Vec::new();
+    let mut maintenances = Vec::new();
+    for (i, (announcement, kind)) in recent.iter().enumerate() {
+        match kind {
+            Kind::Incident => {
+                incidents.push(into_incident(provider, config, announcement, resolved.get(&i).copied()));
+            }
+            Kind::Maintenance => maintenances.push(into_maintenance(provider, announcement)),
+            Kind::Resolved => {}
+        }
+    }
+
+    let active = incidents.iter().any(|i| i.status != IncidentStatus::Resolved);
+    let components = vec![ScrapedComponent {
+        name: config.component.clone(),
+        status: if active { ComponentStatus::PartialOutage } else { ComponentStatus::Operational },
+        description: None,
+    }];
+
+    info!(
+        "📊 {} 公告解析完成 - 公告数: {}, 近期故障: {}, 维护: {}",
+        provider.name,
+        entries.len(),
+        incidents.len(),
+        maintenances.len()
+    );
+    Ok(build_summary(provider, &config.url, components, incidents, maintenances, None))
+}
+
+fn extract_html(
+    config: &AnnouncementsConfig,
+    body: &str,
+    offset: FixedOffset,
+) -> Result<Vec<Announcement>, Box<dyn Error + Send + Sync>> {
+    let document = Html::parse_document(body);
+    let title_selector = html::compile(&config.title)?;
+    let date_selector = html::compile(&config.date)?;
+    let link_selector = config.link.as_deref().map(html::compile).transpose()?;
+    let body_selector = config.body.as_deref().map(html::compile).transpose()?;
+
+    let mut entries = Vec::new();
+    for item in document.select(&html::compile(&config.list)?) {
+        let Some(title) = item.select(&title_selector).next().map(html::element_text) else {
+            continue;
+        };
+        let Some(published) = item
+            .select(&date_selector)
+            .next()
+            .and_then(|e| parse_date(&html::element_text(e), offset))
+        else {
+            continue;
+        };
+        let link = link_selector
+            .as_ref()
+            .and_then(|s| item.select(s).next())
+            .and_then(|e| e.value().attr("href"))
+            .map(|href| absolute_url(&config.url, href));
+        let body = body_selector
+            .as_ref()
+            .and_then(|s| item.select(s).next())
+            .map(html::element_text);
+        entries.push(Announcement { title, published, link, body });
+    }
+    Ok(entries)
+}
+
+fn extract_json(
+    config: &AnnouncementsConfig,
+    body: &str,
+    offset: FixedOffset,
+) -> Result<Vec<Announcement>, Box<dyn Error + Send + Sync>> {
+    let document: Value = serde_json::from_str(body)?;
+    let mut items = json::query(&config.list, &document)?;
+    if let [Value::Array(list)] = items.as_slice() {
+        items = list.clone();
+    }
+
+    let mut entries = Vec::new();
+    for item in &items {
+        let Some(title) = json::query_text(&config.title, item)? else {
+            continue;
+        };
+        let Some(published) = json::query_text(&config.date, item)?.and_then(|d| parse_date(&d, offset)) else {
+            continue;
+        };
+        let link = match &config.link {
+            Some(path) => json::query_text(path, item)?.map(|href| absolute_url(&config.url, &href)),
+            None => None,
+        };
+        let body = match &config.body {
+            Some(path) => json::query_text(path, item)?,
+            None => None,
+        };
+        entries.push(Announcement { title, published, link, body });
+    }
+    Ok(entries)
+}
+
+/// 解析发布时间：Unix 时间戳（秒或毫秒）、RFC 3339，或文本中的日期时间
+fn parse_date(text: &str, offset: FixedOffset) -> Option<DateTime<Utc>> {
+    let text = text.trim();
+    if !text.is_empty() && text.chars().all(|c| c.is_ascii_digit()) {
+        let value: i64 = text.parse().ok()?;
+        return if text.len() >= 13 {
+            DateTime::from_timestamp_millis(value)
+        } else {
+            DateTime::from_timestamp(value, 0)
+        };
+    }
+    if let Ok(date) = DateTime::parse_from_rfc3339(text) {
+        return Some(date.with_timezone(&Utc));
+    }
+
+    let caps = DATE_PATTERN.captures(text)?;
+    let number = |i: usize| caps.get(i).and_then(|m| m.as_str().parse::<u32>().ok());
+    let date = NaiveDate::from_ymd_opt(number(1)? as i32, number(2)?, number(3)?)?;
+    let time = date.and_hms_opt(number(4).unwrap_or(0), number(5).unwrap_or(0), number(6).unwrap_or(0))?;
+    local_to_utc(time, offset)
+}
+
+fn local_to_utc(time: NaiveDateTime, offset: FixedOffset) -> Option<DateTime<Utc>> {
+    offset.from_local_datetime(&time).single().map(|t| t.with_timezone(&Utc))
+}
+
+/// 将相对链接补全为绝对地址
+fn absolute_url(base: &str, href: &str) -> String {
+    reqwest::Url::parse(base)
+        .and_then(|base| base.join(href))
+        .map(|url| url.to_string())
+        .unwrap_or_else(|_| href.to_string())
+}
+
+/// 由供应商与公告标题派生稳定的ID
+fn announcement_id(provider: &ProviderConfig, announcement: &Announcement) -> String {
+    let published = announcement.published.timestamp().to_string();
+    let hash = component_ids::stable_hash([announcement.title.as_str(), published.as_str()]);
+    format!("{}-announcement-{:016x}", provider.id, hash)
+}
+
+fn into_incident(
+    provider: &ProviderConfig,
+    config: &AnnouncementsConfig,
+    announcement: &Announcement,
+    resolved_at: Option<DateTime<Utc>>,
+) -> Incident {
+    let id = announcement_id(provider, announcement);
+    let published = announcement.published.to_rfc3339();
+    let (status, update_status) = if resolved_at.is_some() {
+        (IncidentStatus::Resolved, IncidentUpdateStatus::Resolved)
+    } else {
+        (IncidentStatus::Investigating, IncidentUpdateStatus::Investigating)
+    };
+
+    Incident {
+        id: id.clone(),
+        name: announcement.title.clone(),
+        status,
+        created_at: published.clone(),
+        updated_at: resolved_at.map(|t| t.to_rfc3339()).unwrap_or_else(|| published.clone()),
+        monitoring_at: None,
+        resolved_at: resolved_at.map(|t| t.to_rfc3339()),
+        impact: IncidentImpact::Major,
+        shortlink: announcement.link.clone(),
+        page_id: None,
+        incident_updates: vec![IncidentUpdate {
+            id: format!("{}-latest", id),
+            status: update_status,
+            body: announcement.body.clone().unwrap_or_else(|| announcement.title.clone()),
+            display_at: Some(published),
+            incident_id: Some(id),
+            affected_components: Some(vec![HashMap::from([("name".to_string(), config.component.clone())])]),
+            delights_resolved: None,
+        }],
+        scheduled_for: None,
+        scheduled_until: None,
+        automated: Some(false),
+        classification: None,
+    }
+}
+
+fn into_maintenance(provider: &ProviderConfig, announcement: &Announcement) -> ScheduledMaintenance {
+    let published = announcement.published.to_rfc3339();
+
+    ScheduledMaintenance {
+        id: announcement_id(provider, announcement),
+        name: announcement.title.clone(),
+        status: MaintenanceStatus::Scheduled,
+        created_at: published.clone(),
+        updated_at: published,
+        monitoring_at: None,
+        resolved_at: None,
+        shortlink: announcement.link.clone(),
+        incident_updates: Vec::new(),
+        scheduled_for: None,
+        scheduled_until: None,
+        automated: Some(false),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn setup() -> (ProviderConfig, AnnouncementsConfig) {
+        let provider: ProviderConfig = serde_json::from_value(serde_json::json!({
+            "id": "example", "name": "Example",
+            "source": { "type": "announcements", "url": "https://example.com/notice", "format": "json",
+                        "list": "$.data[*]", "title": "$.title", "date": "$.time", "title_filter": ["百炼", "通义"] }
+        }))
+        .unwrap();
+        let crate::sources::SourceConfig::Announcements(config) = provider.source.clone() else {
+            panic!("不是公告数据源");
+        };
+        (provider, config)
+    }
+
+    fn parse_titles(titles: &[(&str, &str)]) -> StatusPageSummary {
+        let (provider, config) = setup();
+        let data: Vec<Value> = titles.iter().map(|(title, time)| serde_json::json!({ "title": title, "time": time })).collect();
+        let body = serde_json::json!({ "data": data }).to_string();
+        let now = DateTime::parse_from_rfc3339("2025-06-01T12:00:00+08:00").unwrap().with_timezone(&Utc);
+        parse(&provider, &config, &body, now).unwrap()
+    }
+
+    fn statuses(summary: &StatusPageSummary) -> Vec<(&str, IncidentStatus, Option<&str>)> {
+        summary
+            .incidents
+            .iter()
+            .map(|i| (i.name.as_str(), i.status.clone(), i.resolved_at.as_deref()))
+            .collect()
+    }
+
+    #[test]
+    fn resolution_only_resolves_its_own_incident() {
+        let summary = parse_titles(&[
+            ("【恢复】百炼平台部分模型调用已恢复", "2025-06-01 11:00"),
+            ("【故障】百炼平台部分模型调用异常", "2025-06-01 10:00"),
+            ("【故障】通义千问 qwen-max 调用超时", "2025-06-01 09:00"),
+        ]);
+        assert_eq!(
+            statuses(&summary),
+            [
+                ("【故障】百炼平台部分模型调用异常", IncidentStatus::Resolved, Some("2025-06-01T03:00:00+00:00")),
+                ("【故障】通义千问 qwen-max 调用超时", IncidentStatus::Investigating, None),
+            ]
+        );
+        assert_eq!(summary.components[0].status, ComponentStatus::PartialOutage);
+    }
+
+    #[test]
+    fn each_resolution_is_used_once() {
+        // 同一问题两次故障公告只被一条恢复公告解决较近的一次，恢复之后的故障不受影响
+        let summary = parse_titles(&[
+            ("百炼 qwen-max 调用异常", "2025-06-01 11:30"),
+            ("百炼 qwen-max 调用已恢复", "2025-06-01 11:00"),
+            ("百炼 qwen-max 调用异常", "2025-06-01 10:00"),
+            ("百炼 qwen-max 调用异常", "2025-06-01 09:00"),
+        ]);
+        let resolved: Vec<IncidentStatus> = summary.incidents.iter().map(|i| i.status.clone()).collect();
+        assert_eq!(resolved, [IncidentStatus::Investigating, IncidentStatus::Resolved, IncidentStatus::Investigating]);
+
+        // 与故障标题无关的恢复公告不解决任何故障
+        let summary = parse_titles(&[
+            ("百炼控制台登录已恢复", "2025-06-01 11:00"),
+            ("百炼 qwen-max 调用异常", "2025-06-01 10:00"),
+        ]);
+        assert_eq!(summary.incidents[0].status, IncidentStatus::Investigating);
+    }
+
+    #[test]
+    fn title_terms_split_words_and_bigrams() {
+        let (_, config) = setup();
+        let terms = config.title_terms("【故障】通义千问 Qwen-Max 调用超时");
+        let mut terms: Vec<&str> = terms.iter().map(String::as_str).collect();
+        terms.sort_unstable();
+        assert_eq!(terms, ["qwen-max", "千问", "用超", "调用", "超时"]);
+    }
+
+    #[test]
+    fn html_list_with_chinese_dates() {
+        let provider: ProviderConfig = serde_json::from_value(serde_json::json!({
+            "id": "example", "name": "Example",
+            "source": { "type": "announcements", "url": "https://cloud.example.com/doc/notice/index.html",
+                        "list": ".notice-item", "title": "a.title", "date": ".date", "link": "a.title",
+                        "title_filter": ["千帆", "文心"], "component": "千帆 API" }
+        }))
+        .unwrap();
+        let crate::sources::SourceConfig::Announcements(config) = provider.source.clone() else {
+            panic!("不是公告数据源");
+        };
+        let body = r#"<div class="notice-list">
+            <div class="notice-item"><a class="title" href="/doc/notice/s/0601">文心 4.0 服务异常公告</a><span class="date">2025年06月01日 09:15</span></div>
+            <div class="notice-item"><a class="title" href="/doc/notice/s/0531">对象存储华北区域故障公告</a><span class="date">2025年05月31日 14:00</span></div>
+            <div class="notice-item"><a class="title" href="/doc/notice/s/0530">千帆平台服务维护公告</a><span class="date">2025/5/30</span></div>
+            <div class="notice-item"><a class="title" href="/doc/notice/s/0520">千帆新功能上线</a><span class="date">2025-05-20</span></div>
+            <div class="notice-item"><a class="title">千帆接口不可用</a><span class="date">近期</span></div>
+        </div>"#;
+        let now = DateTime::parse_from_rfc3339("2025-06-01T12:00:00+08:00").unwrap().with_timezone(&Utc);
+        let summary = parse(&provider, &config, body, now).unwrap();
+
+        // 其他产品、超出时间范围与无法识别日期的公告被忽略
+        assert_eq!(statuses(&summary), [("文心 4.0 服务异常公告", IncidentStatus::Investigating, None)]);
+        let incident = &summary.incidents[0];
+        assert_eq!(incident.created_at, "2025-06-01T01:15:00+00:00");
+        assert_eq!(incident.shortlink.as_deref(), Some("https://cloud.example.com/doc/notice/s/0601"));
+        assert_eq!(summary.components[0].name, "千帆 API");
+        assert_eq!(summary.components[0].status, ComponentStatus::PartialOutage);
+        assert_eq!(summary.scheduled_maintenances[0].name, "千帆平台服务维护公告");
+        assert_eq!(summary.scheduled_maintenances[0].created_at, "2025-05-29T16:00:00+00:00");
+    }
+
+    #[test]
+    fn dates_in_every_format() {
+        let beijing = FixedOffset::east_opt(8 * 3600).unwrap();
+        let date = |text: &str| parse_date(text, beijing).map(|t| t.to_rfc3339());
+        assert_eq!(date("1748743200"), Some("2025-06-01T02:00:00+00:00".to_string()));
+        assert_eq!(date("1748743200000"), Some("2025-06-01T02:00:00+00:00".to_string()));
+        assert_eq!(date("2025-06-01T10:00:00+08:00"), Some("2025-06-01T02:00:00+00:00".to_string()));
+        assert_eq!(date("发布于 2025-06-01 10:00"), Some("2025-06-01T02:00:00+00:00".to_string()));
+        assert_eq!(date("2025年6月1日"), Some("2025-05-31T16:00:00+00:00".to_string()));
+        assert_eq!(date("近期"), None);
+    }
+}
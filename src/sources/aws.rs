@@ -0,0 +1,341 @@
+//! AWS Health 数据源
+//!
+//! Amazon Bedrock 上的 Claude 等模型的故障发布在 AWS Health Dashboard 的公开事件接口
+//! `currentevents` 中。该接口返回 UTF-16 编码（带 BOM）的 JSON，服务键形如
+//! `bedrock-us-east-1`，由服务名与区域代码组成。
+
+use super::{build_summary, ProviderConfig, ScrapedComponent};
+use crate::models::{
+    ComponentStatus, Incident, IncidentImpact, IncidentStatus, IncidentUpdate, IncidentUpdateStatus,
+    StatusPageSummary,
+};
+use chrono::DateTime;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::error::Error;
+use std::sync::LazyLock;
+use tracing::info;
+
+/// AWS Health 数据源配置
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct AwsHealthConfig {
+    /// 事件接口地址
+    #[serde(default = "default_url")]
+    pub url: String,
+    /// 关注的服务，可填写服务键中的服务名（如 `bedrock`）或服务显示名称（如 `Amazon Bedrock (Oregon)`），
+    /// 每个服务对应一个组件。服务名需完整匹配，以 `*` 结尾时按前缀匹配（如 `bedrock*` 同时包含
+    /// `bedrock-agent`、`bedrock-runtime`）
+    pub services: Vec<String>,
+    /// 关注的区域代码或名称（如 `us-east-1`），为空时不按区域过滤
+    #[serde(default)]
+    pub regions: Vec<String>,
+}
+
+fn default_url() -> String {
+    "https://health.aws.amazon.com/public/currentevents".to_string()
+}
+
+#[derive(Debug, Deserialize)]
+struct AwsEvent {
+    #[serde(default)]
+    date: Value,
+    #[serde(default)]
+    region_name: String,
+    #[serde(default)]
+    status: Value,
+    #[serde(default)]
+    service: String,
+    #[serde(default)]
+    service_name: String,
+    #[serde(default)]
+    summary: String,
+    #[serde(default)]
+    event_log: Vec<EventLog>,
+}
+
+#[derive(Debug, Deserialize)]
+struct EventLog {
+    #[serde(default)]
+    message: String,
+    #[serde(default)]
+    timestamp: Value,
+}
+
+impl AwsEvent {
+    /// 事件状态：0 已恢复，1 信息通告，2 性能下降，3 服务中断
+    fn status(&self) -> u8 {
+        match &self.status {
+            Value::Number(n) => n.as_u64().unwrap_or(0) as u8,
+            Value::String(s) => s.parse().unwrap_or(0),
+            _ => 0,
+        }
+    }
+
+    /// 服务键去掉区域代码后的服务名，如 `bedrock-agent-us-east-1` 为 `bedrock-agent`
+    fn service_key(&self) -> &str {
+        static REGION: LazyLock<Regex> =
+            LazyLock::new(|| Regex::new(r"-[a-z]{2}(-gov|-iso[a-z]*)?-[a-z]+-\d+$").unwrap());
+        match REGION.find(&self.service) {
+            Some(region) => &self.service[..region.start()],
+            None => &self.service,
+        }
+    }
+
+    fn matches_service(&self, wanted: &str) -> bool {
+        let key = self.service_key();
+        match wanted.strip_suffix('*') {
+            Some(prefix) => {
+                key.eq_ignore_ascii_case(prefix)
+                    || key.get(..prefix.len() + 1).is_some_and(|head| head.eq_ignore_ascii_case(&format!("{}-", prefix)))
+            }
+            None => self.service_name.eq_ignore_ascii_case(wanted) || key.eq_ignore_ascii_case(wanted),
+        }
+    }
+
+    fn matches_region(&self, regions: &[String]) -> bool {
+        regions.is_empty()
+            || regions.iter().any(|region| {
+                self.service.ends_with(&format!("-{}", region))
+                    || self.region_name.eq_ignore_ascii_case(region)
+            })
+    }
+}
+
+/// 解析 `currentevents` 返回内容
+pub fn parse(
+    provider: &ProviderConfig,
+    config: &AwsHealthConfig,
+    body: &[u8],
+) -> Result<StatusPageSummary, Box<dyn Error + Send + Sync>> {
+    if config.services.is_empty() {
+        return Err("未配置任何 AWS 服务".into());
+    }
+    let text = decode(body);
+    let events: Vec<AwsEvent> = if text.trim().is_empty() {
+        Vec::new()
+    } else {
+        serde_json::from_str(&text)?
+    };
+
+    let mut statuses: HashMap<&str, ComponentStatus> = HashMap::new();
+    let mut incidents = Vec::new();
+    for event in events.iter().filter(|e| e.status() > 0) {
+        let services: Vec<&str> = config
+            .services
+            .iter()
+            .filter(|wanted| event.matches_service(wanted))
+            .map(String::as_str)
+            .collect();
+        if services.is_empty() || !event.matches_region(&config.regions) {
+            continue;
+        }
+
+        let status = component_status(event.status());
+        for service in &services {
+            let current = statuses.entry(service).or_insert(ComponentStatus::Operational);
+            if status.severity() > current.severity() {
+                *current = status.clone();
+            }
+        }
+        incidents.push(into_incident(event, &services));
+    }
+
+    let components = config
+        .services
+        .iter()
+        .map(|service| ScrapedComponent {
+            name: service.clone(),
+            status: statuses.get(service.as_str()).cloned().unwrap_or(ComponentStatus::Operational),
+            description: None,
+        })
+        .collect();
+
+    info!(
+        "📊 {} AWS Health 解析完成 - 事件数: {}, 相关事件: {}",
+        provider.name,
+        events.len(),
+        incidents.len()
+    );
+    Ok(build_summary(provider, &config.url, components, incidents, vec![], None))
+}
+
+/// 按 BOM 解码，无 BOM 时根据第二个字节是否为 0 判断是否为 UTF-16LE
+fn decode(body: &[u8]) -> String {
+    let utf16 = |bytes: &[u8], from: fn([u8; 2]) -> u16| {
+        let units: Vec<u16> = bytes.chunks_exact(2).map(|pair| from([pair[0], pair[1]])).collect();
+        String::from_utf16_lossy(&units)
+    };
+    match body {
+        [0xFF, 0xFE, rest @ ..] => utf16(rest, u16::from_le_bytes),
+        [0xFE, 0xFF, rest @ ..] => utf16(rest, u16::from_be_bytes),
+        [0xEF, 0xBB, 0xBF, rest @ ..] => String::from_utf8_lossy(rest).into_owned(),
+        [_, 0, ..] => utf16(body, u16::from_le_bytes),
+        _ => String::from_utf8_lossy(body).into_owned(),
+    }
+}
+
+fn component_status(status: u8) -> ComponentStatus {
+    match status {
+        1 => ComponentStatus::Operational,
+        2 => ComponentStatus::DegradedPerformance,
+        3 => ComponentStatus::MajorOutage,
+        _ => ComponentStatus::Unknown,
+    }
+}
+
+/// 时间戳为秒级 Unix 时间，可能以字符串形式给出
+fn timestamp(value: &Value) -> Option<String> {
+    let secs = match value {
+        Value::Number(n) => n.as_i64(),
+        Value::String(s) => s.parse().ok(),
+        _ => None,
+    }?;
+    DateTime::from_timestamp(secs, 0).map(|t| t.to_rfc3339())
+}
+
+fn into_incident(event: &AwsEvent, services: &[&str]) -> Incident {
+    let impact = match event.status() {
+        1 => IncidentImpact::None,
+        2 => IncidentImpact::Minor,
+        3 => IncidentImpact::Major,
+        _ => IncidentImpact::Unknown,
+    };
+    let created_at = timestamp(&event.date).unwrap_or_default();
+    let latest = event.event_log.iter().max_by_key(|log| timestamp(&log.timestamp));
+    let updated_at = latest
+        .and_then(|log| timestamp(&log.timestamp))
+        .unwrap_or_else(|| created_at.clone());
+    let id = format!("{}-{}", event.service, created_at);
+
+    Incident {
+        id: id.clone(),
+        name: event.summary.trim().to_string(),
+        status: IncidentStatus::Investigating,
+        created_at,
+        updated_at: updated_at.clone(),
+        monitoring_at: None,
+        resolved_at: None,
+        impact,
+        shortlink: Some("https://health.aws.amazon.com/health/status".to_string()),
+        page_id: None,
+        incident_updates: vec![IncidentUpdate {
+            id: format!("{}-latest", id),
+            status: IncidentUpdateStatus::Investigating,
+            body: latest.map(|log| log.message.trim().to_string()).unwrap_or_default(),
+            display_at: Some(updated_at),
+            incident_id: Some(id),
+            affected_components: Some(
+                services
+                    .iter()
+                    .map(|name| HashMap::from([("name".to_string(), name.to_string())]))
+                    .collect(),
+            ),
+            delights_resolved: None,
+        }],
+        scheduled_for: None,
+        scheduled_until: None,
+        automated: Some(false),
+        classification: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 读取 `tests/fixtures/aws` 下记录的接口返回（UTF-16LE，带 BOM）
+    fn fixture() -> Vec<u8> {
+        let path = format!("{}/tests/fixtures/aws/currentevents.json", env!("CARGO_MANIFEST_DIR"));
+        std::fs::read(&path).unwrap_or_else(|e| panic!("缺少测试数据 {}: {}", path, e))
+    }
+
+    fn parse_with(body: &[u8], services: &[&str], regions: &[&str]) -> StatusPageSummary {
+        let config = AwsHealthConfig {
+            url: default_url(),
+            services: services.iter().map(|s| s.to_string()).collect(),
+            regions: regions.iter().map(|r| r.to_string()).collect(),
+        };
+        let provider: ProviderConfig = serde_json::from_value(serde_json::json!({
+            "id": "bedrock", "name": "Amazon Bedrock",
+            "source": { "type": "aws_health", "services": services }
+        }))
+        .unwrap();
+        parse(&provider, &config, body).unwrap()
+    }
+
+    #[test]
+    fn decodes_every_encoding() {
+        let utf16le = |text: &str| text.encode_utf16().flat_map(u16::to_le_bytes).collect::<Vec<u8>>();
+        let utf16be = |text: &str| text.encode_utf16().flat_map(u16::to_be_bytes).collect::<Vec<u8>>();
+        let text = r#"[{"summary":"Bedrock – 延迟"}]"#;
+
+        assert_eq!(decode(&[&[0xFF, 0xFE][..], &utf16le(text)].concat()), text);
+        assert_eq!(decode(&[&[0xFE, 0xFF][..], &utf16be(text)].concat()), text);
+        assert_eq!(decode(&[&[0xEF, 0xBB, 0xBF][..], text.as_bytes()].concat()), text);
+        // 无 BOM 时按第二个字节判断
+        assert_eq!(decode(&utf16le(text)), text);
+        assert_eq!(decode(text.as_bytes()), text);
+        assert_eq!(decode(b""), "");
+    }
+
+    #[test]
+    fn events_filtered_by_service_and_region() {
+        let summary = parse_with(&fixture(), &["bedrock"], &[]);
+        // 已恢复与其他服务的事件不计入，取最严重的状态
+        let ids: Vec<&str> = summary.incidents.iter().map(|i| i.id.as_str()).collect();
+        assert_eq!(
+            ids,
+            vec!["bedrock-us-east-1-2025-06-01T03:00:00+00:00", "bedrock-us-west-2-2025-06-01T02:00:00+00:00"]
+        );
+        assert_eq!(summary.components[0].status, ComponentStatus::MajorOutage);
+
+        let summary = parse_with(&fixture(), &["bedrock"], &["us-east-1"]);
+        assert_eq!(summary.incidents.len(), 1);
+        assert_eq!(summary.components[0].status, ComponentStatus::DegradedPerformance);
+
+        let summary = parse_with(&fixture(), &["Amazon Bedrock (Oregon)", "bed"], &["oregon"]);
+        assert_eq!(summary.components[0].status, ComponentStatus::MajorOutage);
+        assert_eq!(summary.components[1].status, ComponentStatus::Operational);
+    }
+
+    #[test]
+    fn service_names_match_whole_unless_prefixed() {
+        // `bedrock` 不包含 `bedrock-agent` 的事件
+        let summary = parse_with(&fixture(), &["bedrock", "bedrock-agent"], &["us-east-1"]);
+        let names: Vec<&str> = summary.incidents.iter().map(|i| i.name.as_str()).collect();
+        assert_eq!(names, ["Increased Error Rates", "Increased Agent Invocation Latency"]);
+        let affected = |i: usize| summary.incidents[i].incident_updates[0].affected_components.clone().unwrap();
+        assert_eq!(affected(0)[0]["name"], "bedrock");
+        assert_eq!(affected(1)[0]["name"], "bedrock-agent");
+
+        // 以 `*` 结尾时按前缀匹配，前缀必须在 `-` 处结束
+        let summary = parse_with(&fixture(), &["bedrock*", "bed*"], &[]);
+        assert_eq!(summary.incidents.len(), 3);
+        assert_eq!(summary.components[0].status, ComponentStatus::MajorOutage);
+        assert_eq!(summary.components[1].status, ComponentStatus::Operational);
+    }
+
+    #[test]
+    fn events_are_mapped() {
+        let summary = parse_with(&fixture(), &["bedrock"], &["us-east-1"]);
+        let incident = &summary.incidents[0];
+        assert_eq!(incident.name, "Increased Error Rates");
+        assert_eq!(incident.impact, IncidentImpact::Minor);
+        assert_eq!(incident.created_at, "2025-06-01T03:00:00+00:00");
+        // 字符串形式的时间戳同样参与取最新日志
+        assert_eq!(incident.updated_at, "2025-06-01T04:00:00+00:00");
+        assert_eq!(
+            incident.incident_updates[0].body,
+            "We have identified the cause and are working towards mitigation."
+        );
+    }
+
+    #[test]
+    fn empty_body_has_no_events() {
+        let summary = parse_with(&[0xFF, 0xFE], &["bedrock"], &[]);
+        assert!(summary.incidents.is_empty());
+        assert_eq!(summary.components[0].status, ComponentStatus::Operational);
+    }
+}
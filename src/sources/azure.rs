@@ -0,0 +1,341 @@
+//! Azure 状态数据源
+//!
+//! Azure OpenAI 的故障不会出现在 `status.openai.com` 上，而是发布在 Azure 状态页面的 RSS
+//! 订阅中。订阅只包含正在发生或刚刚缓解的重大事件，条目没有结构化的服务与区域字段，
+//! 因此按标题与正文中的文本过滤。
+
+use super::{build_summary, mentions, ProviderConfig, ScrapedComponent};
+use crate::models::{
+    ComponentStatus, Incident, IncidentImpact, IncidentStatus, IncidentUpdate, IncidentUpdateStatus,
+    StatusPageSummary,
+};
+use chrono::{DateTime, Duration, Utc};
+use scraper::Html;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::error::Error;
+use tracing::info;
+
+/// 标题以这些前缀开头的条目表示事件已缓解或为事后报告
+const RESOLVED_PREFIXES: [&str; 4] = ["mitigated", "resolved", "post incident review", "rca"];
+
+/// 表示服务完全不可用的用语
+const MAJOR_TERMS: [&str; 4] = ["major outage", "full outage", "complete outage", "fully unavailable"];
+
+/// 表示部分请求失败的用语
+const OUTAGE_TERMS: [&str; 8] = ["outage", "unavailable", "unable to", "error rates", "errors", "failures", "failed", "failing"];
+
+/// 表示性能下降的用语
+const DEGRADED_TERMS: [&str; 7] = ["degraded", "degradation", "latency", "delays", "delayed", "slow", "intermittent"];
+
+/// 区域名称前可能出现的方位词，`Central US` 不应命中 `North Central US`
+const DIRECTIONS: [&str; 5] = ["north", "south", "east", "west", "central"];
+
+/// Azure 数据源配置
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct AzureStatusConfig {
+    /// RSS 订阅地址
+    #[serde(default = "default_url")]
+    pub url: String,
+    /// 关注的服务名称（如 `Azure OpenAI`），每个服务对应一个组件
+    pub services: Vec<String>,
+    /// 关注的区域（如 `East US`），为空时不按区域过滤
+    #[serde(default)]
+    pub regions: Vec<String>,
+    /// 超过该时长（小时）的条目视为过期
+    #[serde(default = "default_max_age_hours")]
+    pub max_age_hours: i64,
+}
+
+fn default_url() -> String {
+    "https://azure.status.microsoft/en-us/status/feed/".to_string()
+}
+
+fn default_max_age_hours() -> i64 {
+    24
+}
+
+/// RSS 条目
+struct FeedItem {
+    id: String,
+    title: String,
+    link: Option<String>,
+    description: String,
+    published: Option<DateTime<Utc>>,
+}
+
+/// 解析 RSS 订阅
+pub fn parse(
+    provider: &ProviderConfig,
+    config: &AzureStatusConfig,
+    body: &str,
+) -> Result<StatusPageSummary, Box<dyn Error + Send + Sync>> {
+    if config.services.is_empty() {
+        return Err("未配置任何 Azure 服务".into());
+    }
+    let items = parse_items(body)?;
+    let cutoff = Utc::now() - Duration::hours(config.max_age_hours);
+
+    let mut affected: HashMap<&str, ComponentStatus> = HashMap::new();
+    let mut incidents = Vec::new();
+    for item in &items {
+        let title = item.title.to_lowercase();
+        if RESOLVED_PREFIXES.iter().any(|prefix| title.starts_with(prefix))
+            || item.published.is_some_and(|published| published < cutoff)
+        {
+            continue;
+        }
+
+        let text = format!("{} {}", item.title, item.description).to_lowercase();
+        let services: Vec<&str> = config
+            .services
+            .iter()
+            .filter(|service| mentions(&text, &service.to_lowercase()))
+            .map(String::as_str)
+            .collect();
+        let in_region = config.regions.is_empty()
+            || config.regions.iter().any(|region| mentions_region(&text, region));
+        if services.is_empty() || !in_region {
+            continue;
+        }
+
+        let (status, impact) = severity(&text);
+        for service in &services {
+            let current = affected.entry(service).or_insert(ComponentStatus::Operational);
+            if status.severity() > current.severity() {
+                *current = status.clone();
+            }
+        }
+        incidents.push(into_incident(item, &services, impact));
+    }
+
+    let components = config
+        .services
+        .iter()
+        .map(|service| ScrapedComponent {
+            name: service.clone(),
+            status: affected.get(service.as_str()).cloned().unwrap_or(ComponentStatus::Operational),
+            description: None,
+        })
+        .collect();
+
+    info!(
+        "📊 {} Azure 状态订阅解析完成 - 条目数: {}, 相关事件: {}",
+        provider.name,
+        items.len(),
+        incidents.len()
+    );
+    Ok(build_summary(provider, &config.url, components, incidents, vec![], None))
+}
+
+fn parse_items(body: &str) -> Result<Vec<FeedItem>, Box<dyn Error + Send + Sync>> {
+    let document = roxmltree::Document::parse(body)?;
+    let items = document
+        .descendants()
+        .filter(|node| node.has_tag_name("item"))
+        .map(|item| {
+            let child = |name: &str| {
+                item.children()
+                    .find(|node| node.has_tag_name(name))
+                    .and_then(|node| node.text())
+                    .map(|text| text.trim().to_string())
+                    .filter(|text| !text.is_empty())
+            };
+            let title = child("title").unwrap_or_default();
+            let link = child("link");
+            FeedItem {
+                id: child("guid").or_else(|| link.clone()).unwrap_or_else(|| title.clone()),
+                description: child("description").map(|html| strip_html(&html)).unwrap_or_default(),
+                published: child("pubDate")
+                    .and_then(|date| DateTime::parse_from_rfc2822(&date).ok())
+                    .map(|date| date.with_timezone(&Utc)),
+                title,
+                link,
+            }
+        })
+        .collect();
+    Ok(items)
+}
+
+/// 按词匹配区域名称，`East US` 不命中 `East US 2`，`Central US` 不命中 `North Central US`
+fn mentions_region(text: &str, region: &str) -> bool {
+    let words = |s: &str| -> Vec<String> {
+        s.split(|c: char| !c.is_alphanumeric())
+            .filter(|w| !w.is_empty())
+            .map(str::to_lowercase)
+            .collect()
+    };
+    let text = words(text);
+    let region = words(region);
+    if region.is_empty() {
+        return false;
+    }
+    text.windows(region.len()).enumerate().any(|(start, window)| {
+        let before = start.checked_sub(1).map(|i| text[i].as_str());
+        let after = text.get(start + region.len());
+        window == region.as_slice()
+            && !before.is_some_and(|w| DIRECTIONS.contains(&w))
+            && !after.is_some_and(|w| w.chars().all(|c| c.is_ascii_digit()))
+    })
+}
+
+/// 根据标题与正文中的用语判断严重程度
+///
+/// 订阅只发布影响较大的事件，没有任何可识别的用语时按部分中断处理。
+fn severity(text: &str) -> (ComponentStatus, IncidentImpact) {
+    let any = |terms: &[&str]| terms.iter().any(|term| mentions(text, term));
+    if any(&MAJOR_TERMS) {
+        (ComponentStatus::MajorOutage, IncidentImpact::Critical)
+    } else if any(&OUTAGE_TERMS) {
+        (ComponentStatus::PartialOutage, IncidentImpact::Major)
+    } else if any(&DEGRADED_TERMS) {
+        (ComponentStatus::DegradedPerformance, IncidentImpact::Minor)
+    } else {
+        (ComponentStatus::PartialOutage, IncidentImpact::Major)
+    }
+}
+
+/// 条目正文为 HTML，只保留文本
+fn strip_html(html: &str) -> String {
+    let fragment = Html::parse_fragment(html);
+    let text: Vec<&str> = fragment
+        .root_element()
+        .text()
+        .map(str::trim)
+        .filter(|t| !t.is_empty())
+        .collect();
+    text.join(" ")
+}
+
+fn into_incident(item: &FeedItem, services: &[&str], impact: IncidentImpact) -> Incident {
+    let published = item.published.map(|p| p.to_rfc3339()).unwrap_or_default();
+
+    Incident {
+        id: item.id.clone(),
+        name: item.title.clone(),
+        status: IncidentStatus::Investigating,
+        created_at: published.clone(),
+        updated_at: published.clone(),
+        monitoring_at: None,
+        resolved_at: None,
+        impact,
+        shortlink: item.link.clone(),
+        page_id: None,
+        incident_updates: vec![IncidentUpdate {
+            id: format!("{}-latest", item.id),
+            status: IncidentUpdateStatus::Investigating,
+            body: item.description.clone(),
+            display_at: Some(published),
+            incident_id: Some(item.id.clone()),
+            affected_components: Some(
+                services
+                    .iter()
+                    .map(|name| HashMap::from([("name".to_string(), name.to_string())]))
+                    .collect(),
+            ),
+            delights_resolved: None,
+        }],
+        scheduled_for: None,
+        scheduled_until: None,
+        automated: Some(false),
+        classification: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 读取 `tests/fixtures/azure` 下记录的 RSS 订阅
+    fn fixture() -> String {
+        let path = format!("{}/tests/fixtures/azure/feed.xml", env!("CARGO_MANIFEST_DIR"));
+        std::fs::read_to_string(&path).unwrap_or_else(|e| panic!("缺少测试数据 {}: {}", path, e))
+    }
+
+    /// 测试数据是固定时间录制的，默认不按时间过期
+    fn parse_with(regions: &[&str], max_age_hours: i64) -> StatusPageSummary {
+        let config = AzureStatusConfig {
+            url: default_url(),
+            services: vec!["Azure OpenAI".to_string()],
+            regions: regions.iter().map(|r| r.to_string()).collect(),
+            max_age_hours,
+        };
+        let provider: ProviderConfig = serde_json::from_value(serde_json::json!({
+            "id": "azure-openai", "name": "Azure OpenAI",
+            "source": { "type": "azure_status", "services": ["Azure OpenAI"] }
+        }))
+        .unwrap();
+        parse(&provider, &config, &fixture()).unwrap()
+    }
+
+    fn incident_ids(summary: &StatusPageSummary) -> Vec<&str> {
+        summary.incidents.iter().map(|i| i.id.as_str()).collect()
+    }
+
+    const NO_EXPIRY: i64 = 24 * 365 * 100;
+
+    #[test]
+    fn regions_match_whole_words() {
+        assert!(mentions_region("customers in East US may see", "East US"));
+        assert!(mentions_region("customers in eastus2.", "eastus2"));
+        assert!(!mentions_region("customers in East US 2 may see", "East US"));
+        assert!(!mentions_region("customers in North Central US", "Central US"));
+        assert!(mentions_region("Central US, West US 2", "West US 2"));
+        assert!(!mentions_region("Central US", ""));
+    }
+
+    #[test]
+    fn open_items_filtered_by_service_and_region() {
+        // 不限区域：已缓解的条目与其他服务的条目被忽略
+        let summary = parse_with(&[], NO_EXPIRY);
+        assert_eq!(incident_ids(&summary), vec!["QK3T-9ZB", "7LMN-H2C", "B4RX-PQ1"]);
+        assert_eq!(summary.components[0].status, ComponentStatus::PartialOutage);
+
+        let summary = parse_with(&["East US", "Sweden Central"], NO_EXPIRY);
+        assert_eq!(incident_ids(&summary), vec!["7LMN-H2C"]);
+        assert_eq!(summary.components[0].status, ComponentStatus::DegradedPerformance);
+
+        let summary = parse_with(&["East US 2"], NO_EXPIRY);
+        assert_eq!(incident_ids(&summary), vec!["QK3T-9ZB"]);
+
+        let summary = parse_with(&["Central US"], NO_EXPIRY);
+        assert!(summary.incidents.is_empty());
+        assert_eq!(summary.components[0].status, ComponentStatus::Operational);
+    }
+
+    #[test]
+    fn severity_follows_item_text() {
+        let summary = parse_with(&[], NO_EXPIRY);
+        let impacts: Vec<IncidentImpact> = summary.incidents.iter().map(|i| i.impact.clone()).collect();
+        // 错误率上升、延迟增加、部署失败
+        assert_eq!(impacts, [IncidentImpact::Major, IncidentImpact::Minor, IncidentImpact::Major]);
+
+        let lower = |text: &str| severity(&text.to_lowercase());
+        assert_eq!(lower("Azure OpenAI - Full outage in East US"), (ComponentStatus::MajorOutage, IncidentImpact::Critical));
+        assert_eq!(lower("Customers may experience intermittent slowness"), (ComponentStatus::DegradedPerformance, IncidentImpact::Minor));
+        assert_eq!(lower("Customers may see timeouts and errors"), (ComponentStatus::PartialOutage, IncidentImpact::Major));
+        // 没有可识别的用语时按部分中断处理，`terrors` 不命中 `errors`
+        assert_eq!(lower("Azure OpenAI - Investigating terrors"), (ComponentStatus::PartialOutage, IncidentImpact::Major));
+    }
+
+    #[test]
+    fn old_items_expire() {
+        assert!(parse_with(&[], default_max_age_hours()).incidents.is_empty());
+    }
+
+    #[test]
+    fn items_are_mapped() {
+        let summary = parse_with(&["East US 2"], NO_EXPIRY);
+        let incident = &summary.incidents[0];
+        assert_eq!(incident.name, "Azure OpenAI Service - East US 2 - Investigating");
+        assert_eq!(incident.created_at, "2025-06-01T03:55:00+00:00");
+        assert_eq!(
+            incident.shortlink.as_deref(),
+            Some("https://azure.status.microsoft/en-us/status/history/?trackingId=QK3T-9ZB")
+        );
+        let update = &incident.incident_updates[0];
+        assert!(update.body.starts_with("Impact Statement: Starting at 02:40 UTC"), "{}", update.body);
+        assert!(!update.body.contains('<'));
+        assert_eq!(update.affected_components.as_ref().unwrap()[0]["name"], "Azure OpenAI");
+    }
+}
@@ -0,0 +1,287 @@
+//! Better Stack 状态页面数据源
+//!
+//! 公开状态页面的 `/index.json` 采用 JSON:API 格式：页面本身在 `data` 中，
+//! 监控资源（`status_page_resource`）与状态报告（`status_report`）在 `included` 中。
+
+use super::{build_summary, ProviderConfig, ScrapedComponent};
+use crate::models::{
+    ComponentStatus, Incident, IncidentImpact, IncidentStatus, IncidentUpdate, IncidentUpdateStatus,
+    MaintenanceStatus, OverallStatus, ScheduledMaintenance, StatusIndicator, StatusPageSummary,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::error::Error;
+use tracing::info;
+
+/// Better Stack 数据源配置
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct BetterStackConfig {
+    /// 状态页面地址，如 `https://status.example.com`
+    pub url: String,
+}
+
+impl BetterStackConfig {
+    /// 页面数据接口地址
+    pub fn api_url(&self) -> String {
+        format!("{}/index.json", self.url.trim_end_matches('/'))
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct Document {
+    data: Resource,
+    #[serde(default)]
+    included: Vec<Resource>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Resource {
+    #[serde(default)]
+    id: Value,
+    #[serde(rename = "type")]
+    kind: String,
+    #[serde(default)]
+    attributes: Value,
+}
+
+impl Resource {
+    fn id(&self) -> String {
+        match &self.id {
+            Value::String(s) => s.clone(),
+            other => other.to_string(),
+        }
+    }
+
+    fn attr(&self, name: &str) -> Option<&str> {
+        self.attributes.get(name).and_then(Value::as_str).filter(|s| !s.is_empty())
+    }
+
+    fn position(&self) -> i64 {
+        self.attributes.get("position").and_then(Value::as_i64).unwrap_or(i64::MAX)
+    }
+}
+
+/// 解析 `/index.json`，`now` 用于判断报告是否已结束、维护是否已开始
+pub fn parse(
+    provider: &ProviderConfig,
+    config: &BetterStackConfig,
+    body: &str,
+    now: DateTime<Utc>,
+) -> Result<StatusPageSummary, Box<dyn Error + Send + Sync>> {
+    let document: Document = serde_json::from_str(body)?;
+
+    let mut resources: Vec<&Resource> = document
+        .included
+        .iter()
+        .filter(|r| r.kind == "status_page_resource")
+        .collect();
+    resources.sort_by_key(|r| r.position());
+
+    let components: Vec<ScrapedComponent> = resources
+        .iter()
+        .filter_map(|r| {
+            Some(ScrapedComponent {
+                name: r.attr("public_name")?.to_string(),
+                status: component_status(r.attr("status").unwrap_or_default()),
+                description: r.attr("explanation").map(str::to_string),
+            })
+        })
+        .collect();
+
+    if components.is_empty() {
+        return Err("页面数据中未找到任何监控资源".into());
+    }
+
+    let mut incidents = Vec::new();
+    let mut maintenances = Vec::new();
+    for report in document.included.iter().filter(|r| r.kind == "status_report") {
+        let ends_at = report
+            .attr("ends_at")
+            .and_then(|t| DateTime::parse_from_rfc3339(t).ok())
+            .map(|t| t.with_timezone(&Utc));
+        let starts_at = report
+            .attr("starts_at")
+            .and_then(|t| DateTime::parse_from_rfc3339(t).ok())
+            .map(|t| t.with_timezone(&Utc));
+        if report.attr("report_type") == Some("maintenance") {
+            if ends_at.is_none_or(|end| end > now) {
+                let started = starts_at.is_some_and(|start| start <= now);
+                maintenances.push(into_maintenance(report, started));
+            }
+        } else if ends_at.is_none_or(|end| end > now) {
+            incidents.push(into_incident(report));
+        }
+    }
+
+    let status = match document.data.attr("aggregate_state") {
+        Some("operational") => Some(OverallStatus {
+            indicator: StatusIndicator::None,
+            description: "All Systems Operational".to_string(),
+        }),
+        Some("maintenance") => Some(OverallStatus {
+            indicator: StatusIndicator::Maintenance,
+            description: "Under Maintenance".to_string(),
+        }),
+        _ => None,
+    };
+
+    info!(
+        "📊 {} Better Stack 解析完成 - 组件数: {}, 进行中事件: {}, 维护: {}",
+        provider.name,
+        components.len(),
+        incidents.len(),
+        maintenances.len()
+    );
+    Ok(build_summary(provider, &config.url, components, incidents, maintenances, status))
+}
+
+fn component_status(status: &str) -> ComponentStatus {
+    match status {
+        "operational" => ComponentStatus::Operational,
+        "degraded" => ComponentStatus::DegradedPerformance,
+        "downtime" => ComponentStatus::MajorOutage,
+        "maintenance" => ComponentStatus::UnderMaintenance,
+        _ => ComponentStatus::Unknown,
+    }
+}
+
+fn into_incident(report: &Resource) -> Incident {
+    let id = report.id();
+    let impact = match report.attr("aggregate_state") {
+        Some("degraded") => IncidentImpact::Minor,
+        Some("downtime") => IncidentImpact::Major,
+        _ => IncidentImpact::Unknown,
+    };
+    let created_at = report.attr("starts_at").unwrap_or_default().to_string();
+
+    Incident {
+        id: id.clone(),
+        name: report.attr("title").unwrap_or("Untitled").to_string(),
+        status: IncidentStatus::Investigating,
+        created_at: created_at.clone(),
+        updated_at: created_at.clone(),
+        monitoring_at: None,
+        resolved_at: None,
+        impact,
+        shortlink: None,
+        page_id: None,
+        incident_updates: vec![IncidentUpdate {
+            id: format!("{}-latest", id),
+            status: IncidentUpdateStatus::Investigating,
+            body: String::new(),
+            display_at: Some(created_at),
+            incident_id: Some(id),
+            affected_components: None,
+            delights_resolved: None,
+        }],
+        scheduled_for: None,
+        scheduled_until: None,
+        automated: Some(false),
+        classification: None,
+    }
+}
+
+fn into_maintenance(report: &Resource, started: bool) -> ScheduledMaintenance {
+    let created_at = report.attr("starts_at").unwrap_or_default().to_string();
+
+    ScheduledMaintenance {
+        id: report.id(),
+        name: report.attr("title").unwrap_or("Untitled").to_string(),
+        status: if started { MaintenanceStatus::InProgress } else { MaintenanceStatus::Scheduled },
+        created_at: created_at.clone(),
+        updated_at: created_at,
+        monitoring_at: None,
+        resolved_at: None,
+        shortlink: None,
+        incident_updates: Vec::new(),
+        scheduled_for: report.attr("starts_at").map(str::to_string),
+        scheduled_until: report.attr("ends_at").map(str::to_string),
+        automated: Some(false),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fixture() -> String {
+        let path = format!("{}/tests/fixtures/betterstack/index.json", env!("CARGO_MANIFEST_DIR"));
+        std::fs::read_to_string(&path).unwrap_or_else(|e| panic!("缺少测试数据 {}: {}", path, e))
+    }
+
+    fn parse_at(now: &str) -> StatusPageSummary {
+        let provider: ProviderConfig = serde_json::from_value(serde_json::json!({
+            "id": "example", "name": "Example", "source": { "type": "better_stack", "url": "https://status.example.ai" }
+        }))
+        .unwrap();
+        let config = BetterStackConfig { url: "https://status.example.ai".to_string() };
+        let now = DateTime::parse_from_rfc3339(now).unwrap().with_timezone(&Utc);
+        parse(&provider, &config, &fixture(), now).unwrap()
+    }
+
+    #[test]
+    fn resources_ordered_by_position() {
+        let summary = parse_at("2025-06-01T09:00:00Z");
+        let components: Vec<(&str, ComponentStatus)> =
+            summary.components.iter().map(|c| (c.name.as_str(), c.status.clone())).collect();
+        // 没有公开名称的资源被忽略
+        assert_eq!(
+            components,
+            [
+                ("Embed API", ComponentStatus::Operational),
+                ("Chat API", ComponentStatus::DegradedPerformance),
+                ("Inference Endpoints", ComponentStatus::MajorOutage),
+                ("Dashboard", ComponentStatus::Unknown),
+            ]
+        );
+        assert_eq!(summary.components[0].description.as_deref(), Some("Includes the v1 and v2 embed endpoints"));
+        assert_eq!(summary.components[1].description, None);
+        // downtime 不对应固定的整体状态，由组件状态推导
+        assert_eq!(summary.status.indicator, StatusIndicator::Major);
+    }
+
+    #[test]
+    fn reports_follow_the_given_time() {
+        let summary = parse_at("2025-06-01T09:00:00Z");
+        let incidents: Vec<(&str, IncidentImpact)> =
+            summary.incidents.iter().map(|i| (i.name.as_str(), i.impact.clone())).collect();
+        assert_eq!(incidents, [("Inference Endpoints unavailable", IncidentImpact::Major)]);
+        let maintenances: Vec<(&str, MaintenanceStatus)> =
+            summary.scheduled_maintenances.iter().map(|m| (m.name.as_str(), m.status.clone())).collect();
+        assert_eq!(
+            maintenances,
+            [
+                ("Database failover", MaintenanceStatus::InProgress),
+                ("Dashboard storage migration", MaintenanceStatus::Scheduled),
+            ]
+        );
+
+        // 延迟报告结束前仍在进行中，维护尚未开始
+        let earlier = parse_at("2025-06-01T06:30:00Z");
+        assert_eq!(earlier.incidents.len(), 2);
+        assert_eq!(earlier.incidents[1].impact, IncidentImpact::Minor);
+        assert_eq!(earlier.scheduled_maintenances[0].status, MaintenanceStatus::Scheduled);
+    }
+
+    #[test]
+    fn operational_page_and_empty_resources() {
+        let provider: ProviderConfig = serde_json::from_value(serde_json::json!({
+            "id": "example", "name": "Example", "source": { "type": "better_stack", "url": "https://status.example.ai" }
+        }))
+        .unwrap();
+        let config = BetterStackConfig { url: "https://status.example.ai/".to_string() };
+        assert_eq!(config.api_url(), "https://status.example.ai/index.json");
+
+        let body = serde_json::json!({
+            "data": { "id": "1", "type": "status_page", "attributes": { "aggregate_state": "operational" } },
+            "included": [{ "id": 7, "type": "status_page_resource", "attributes": { "public_name": "API", "status": "degraded" } }]
+        });
+        let summary = parse(&provider, &config, &body.to_string(), Utc::now()).unwrap();
+        assert_eq!(summary.status.indicator, StatusIndicator::None);
+        assert_eq!(summary.status.description, "All Systems Operational");
+
+        let empty = r#"{"data": {"id": "1", "type": "status_page"}, "included": []}"#;
+        assert!(parse(&provider, &config, empty, Utc::now()).is_err());
+    }
+}
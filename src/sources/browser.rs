@@ -0,0 +1,260 @@
+//! 声明式无头浏览器爬虫：适用于需要执行 JavaScript 才能渲染的状态页面
+
+use super::PageSelectors;
+use serde::{Deserialize, Serialize};
+
+/// 浏览器爬虫配置
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct BrowserScrapeConfig {
+    pub url: String,
+    #[serde(default)]
+    pub wait: WaitCondition,
+    #[serde(flatten)]
+    pub selectors: PageSelectors,
+}
+
+/// 页面就绪条件
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct WaitCondition {
+    /// 等待该选择器出现
+    #[serde(default)]
+    pub selector: Option<String>,
+    #[serde(default = "default_timeout_secs")]
+    pub timeout_secs: u64,
+    /// 页面就绪后额外等待前端渲染的毫秒数
+    #[serde(default = "default_settle_ms")]
+    pub settle_ms: u64,
+}
+
+impl Default for WaitCondition {
+    fn default() -> Self {
+        Self {
+            selector: None,
+            timeout_secs: default_timeout_secs(),
+            settle_ms: default_settle_ms(),
+        }
+    }
+}
+
+fn default_timeout_secs() -> u64 {
+    30
+}
+
+fn default_settle_ms() -> u64 {
+    2000
+}
+
+#[cfg(feature = "browser")]
+pub use engine::fetch;
+
+#[cfg(feature = "browser")]
+mod engine {
+    use super::BrowserScrapeConfig;
+    use crate::diagnostics::{self, ScrapeReport};
+    use crate::models::{OverallStatus, StatusPageSummary};
+    use crate::sources::{build_summary, map_value, ProviderConfig, ScrapedComponent, StatusSource};
+    use crate::webdriver;
+    use std::error::Error;
+    use thirtyfour::prelude::*;
+    use tokio::time::{sleep, Duration, Instant};
+    use tracing::{error, info, warn};
+
+    const RETRY_ATTEMPTS: u32 = 3;
+
+    /// 使用无头浏览器获取状态
+    pub async fn fetch(
+        provider: &ProviderConfig,
+        config: &BrowserScrapeConfig,
+    ) -> Result<StatusPageSummary, Box<dyn Error + Send + Sync>> {
+        let driver = webdriver::create_webdriver().await?;
+        let result = fetch_with_retry(&driver, provider, config).await;
+        if let Err(e) = driver.quit().await {
+            error!("❌ WebDriver 关闭失败: {}", e);
+        }
+        result
+    }
+
+    async fn fetch_with_retry(
+        driver: &WebDriver,
+        provider: &ProviderConfig,
+        config: &BrowserScrapeConfig,
+    ) -> Result<StatusPageSummary, Box<dyn Error + Send + Sync>> {
+        for attempt in 1..=RETRY_ATTEMPTS {
+            let mut report = ScrapeReport::new(&provider.name, &config.url);
+            let result = scrape(driver, provider, config, &mut report).await;
+
+            let last_attempt = attempt == RETRY_ATTEMPTS;
+            if result.is_ok() || last_attempt {
+                report.finalize(result.as_ref().err().map(|e| e.to_string()));
+                if report.is_failure() {
+                    let (page_source, screenshot) = webdriver::capture_page(driver).await;
+                    if let Err(e) = diagnostics::save_snapshot(&mut report, page_source, screenshot) {
+                        error!("❌ 保存诊断快照失败: {}", e);
+                    }
+                }
+                diagnostics::publish(report);
+            }
+
+            match result {
+                Ok(summary) => {
+                    info!("✅ {} 爬取成功 (尝试 {}/{})", provider.name, attempt, RETRY_ATTEMPTS);
+                    return Ok(summary);
+                }
+                Err(e) if !last_attempt => {
+                    warn!("⚠️ {} 爬取失败 (尝试 {}/{}): {}", provider.name, attempt, RETRY_ATTEMPTS, e);
+                    sleep(Duration::from_secs(3)).await;
+                }
+                Err(e) => {
+                    return Err(format!("所有 {} 次尝试均失败，最后错误: {}", RETRY_ATTEMPTS, e).into());
+                }
+            }
+        }
+
+        unreachable!()
+    }
+
+    /// 单次爬取
+    async fn scrape(
+        driver: &WebDriver,
+        provider: &ProviderConfig,
+        config: &BrowserScrapeConfig,
+        report: &mut ScrapeReport,
+    ) -> Result<StatusPageSummary, Box<dyn Error + Send + Sync>> {
+        info!("🌐 正在访问: {}", config.url);
+        driver.goto(&config.url).await?;
+        webdriver::wait_for_page_ready(driver, Duration::ZERO).await?;
+
+        if let Some(selector) = &config.wait.selector {
+            let deadline = Instant::now() + Duration::from_secs(config.wait.timeout_secs);
+            let mut found = false;
+            while !found && Instant::now() < deadline {
+                found = driver.find(By::Css(selector)).await.is_ok();
+                if !found {
+                    sleep(Duration::from_millis(500)).await;
+                }
+            }
+            report.record("wait", selector, true, found);
+            if !found {
+                return Err(format!("等待 {} 超时", selector).into());
+            }
+        }
+        sleep(Duration::from_millis(config.wait.settle_ms)).await;
+
+        let selectors = &config.selectors;
+
+        let status = match &selectors.overall_status {
+            Some(selector) => {
+                let text = match driver.find(By::Css(selector)).await {
+                    Ok(element) => element.text().await?,
+                    Err(_) => String::new(),
+                };
+                report.record("overall_status", selector, true, !text.trim().is_empty());
+                match map_value(&selectors.overall_mappings, &text) {
+                    Some(indicator) => Some(OverallStatus {
+                        indicator,
+                        description: text.trim().to_string(),
+                    }),
+                    None => {
+                        report.flag_drift(format!("无法识别的整体状态文本: {}", text.trim()));
+                        None
+                    }
+                }
+            }
+            None => None,
+        };
+
+        let rows = driver.find_all(By::Css(&selectors.service_list)).await.unwrap_or_default();
+        report.record("service_list", &selectors.service_list, true, !rows.is_empty());
+
+        let mut components = Vec::new();
+        for row in &rows {
+            let name = match row.find(By::Css(&selectors.service_name)).await {
+                Ok(element) => element.text().await?.trim().to_string(),
+                Err(_) => String::new(),
+            };
+            report.record("service_name", &selectors.service_name, true, !name.is_empty());
+            if name.is_empty() {
+                continue;
+            }
+
+            let status_element = match &selectors.service_status {
+                Some(selector) => {
+                    let found = row.find(By::Css(selector)).await;
+                    report.record("service_status", selector, true, found.is_ok());
+                    match found {
+                        Ok(element) => element,
+                        Err(_) => continue,
+                    }
+                }
+                None => row.clone(),
+            };
+
+            let raw = match &selectors.status_from {
+                StatusSource::Class => status_element.attr("class").await?.unwrap_or_default(),
+                StatusSource::Text => status_element.text().await?,
+                StatusSource::Attribute(name) => status_element.attr(name.as_str()).await?.unwrap_or_default(),
+            };
+            let status = map_value(&selectors.mappings, &raw).unwrap_or_else(|| {
+                report.flag_drift(format!("服务 {} 的状态值未命中任何映射: {}", name, raw.trim()));
+                selectors.default_status.clone()
+            });
+
+            components.push(ScrapedComponent {
+                name,
+                status,
+                description: None,
+            });
+        }
+
+        if components.is_empty() {
+            return Err("未获取到任何服务".into());
+        }
+
+        info!("📊 {} 爬取完成 - 服务数: {}", provider.name, components.len());
+        Ok(build_summary(provider, &config.url, components, vec![], vec![], status))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{ComponentStatus, StatusIndicator};
+    use crate::sources::map_value;
+
+    fn config(mappings: serde_json::Value) -> Result<BrowserScrapeConfig, serde_json::Error> {
+        serde_json::from_value(serde_json::json!({
+            "url": "https://status.example.com",
+            "wait": { "selector": ".components" },
+            "overall_status": ".page-status",
+            "overall_mappings": [{ "contains": "all systems", "status": "none" }],
+            "service_list": ".component",
+            "service_name": ".name",
+            "status_from": "text",
+            "mappings": mappings
+        }))
+    }
+
+    #[test]
+    fn flattened_selectors_and_mappings() {
+        let config = config(serde_json::json!([
+            { "regex": "^\\s*(major|full) outage", "status": "major_outage" },
+            { "contains": "outage", "status": "partial_outage" }
+        ]))
+        .unwrap();
+        assert_eq!(config.wait.timeout_secs, 30);
+        assert_eq!(config.wait.settle_ms, 2000);
+
+        let selectors = &config.selectors;
+        assert_eq!(map_value(&selectors.overall_mappings, "All Systems Operational"), Some(StatusIndicator::None));
+        assert_eq!(map_value(&selectors.mappings, " Full Outage"), Some(ComponentStatus::MajorOutage));
+        assert_eq!(map_value(&selectors.mappings, "Partial Outage"), Some(ComponentStatus::PartialOutage));
+        assert_eq!(map_value(&selectors.mappings, "Operational"), None);
+        assert_eq!(selectors.default_status, ComponentStatus::Unknown);
+    }
+
+    #[test]
+    fn invalid_regex_fails_when_loading() {
+        let error = config(serde_json::json!([{ "regex": "[", "status": "major_outage" }])).unwrap_err();
+        assert!(error.to_string().contains("无效的映射正则 ["), "{}", error);
+    }
+}
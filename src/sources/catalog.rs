@@ -0,0 +1,155 @@
+//! 内置供应商目录
+//!
+//! 目录随程序一起发布，记录常见大模型供应商的数据源类型、状态页面链接与图标。
+//! 用户只需在 `config/providers.json` 的 `enabled` 中列出供应商ID即可启用，
+//! 状态页面迁移到其他托管平台时只需更新 `catalog.json`。
+
+use super::ProviderConfig;
+use serde::Deserialize;
+use std::sync::LazyLock;
+use tracing::warn;
+
+/// 内置目录
+static CATALOG: LazyLock<Vec<ProviderConfig>> = LazyLock::new(|| {
+    #[derive(Deserialize)]
+    struct CatalogFile {
+        providers: Vec<ProviderConfig>,
+    }
+    serde_json::from_str::<CatalogFile>(include_str!("catalog.json"))
+        .expect("内置供应商目录格式错误")
+        .providers
+});
+
+/// `enabled` 中的条目：供应商ID，或带覆盖选项的对象
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum CatalogEntry {
+    Id(String),
+    Options {
+        id: String,
+        /// 覆盖目录中的组件过滤
+        #[serde(default)]
+        component_filter: Option<Vec<String>>,
+    },
+}
+
+impl CatalogEntry {
+    fn id(&self) -> &str {
+        match self {
+            CatalogEntry::Id(id) | CatalogEntry::Options { id, .. } => id,
+        }
+    }
+}
+
+/// 按ID查找目录中的供应商
+pub fn find(id: &str) -> Option<&'static ProviderConfig> {
+    CATALOG.iter().find(|p| p.id.eq_ignore_ascii_case(id))
+}
+
+/// 将启用列表解析为供应商配置，忽略未知ID
+pub fn resolve(entries: &[CatalogEntry]) -> Vec<ProviderConfig> {
+    entries
+        .iter()
+        .filter_map(|entry| {
+            let Some(provider) = find(entry.id()) else {
+                warn!("⚠️ 内置目录中没有供应商 {}，已忽略", entry.id());
+                return None;
+            };
+            let mut provider = provider.clone();
+            if let CatalogEntry::Options { component_filter: Some(filter), .. } = entry {
+                provider.component_filter = filter.clone();
+            }
+            Some(provider)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::StatusPageSummary;
+    use crate::sources::announcements::{self, AnnouncementFormat};
+    use crate::sources::{apply_component_filter, betterstack, html, incident_io, instatus, statuspage, SourceConfig};
+    use chrono::{DateTime, NaiveDate, Utc};
+    use std::collections::HashSet;
+    use std::path::{Path, PathBuf};
+
+    /// 记录的接口返回按抓取日期存放在 `tests/fixtures/catalog/<YYYY-MM-DD>/` 下
+    fn recordings() -> Vec<(NaiveDate, PathBuf)> {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/catalog");
+        let mut dirs: Vec<(NaiveDate, PathBuf)> = std::fs::read_dir(&root)
+            .map(|entries| {
+                entries
+                    .filter_map(Result::ok)
+                    .filter(|e| e.path().is_dir())
+                    .filter_map(|e| {
+                        let name = e.file_name().into_string().ok()?;
+                        Some((NaiveDate::parse_from_str(&name, "%Y-%m-%d").ok()?, e.path()))
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        dirs.sort();
+        dirs
+    }
+
+    /// 按目录中的数据源类型解析某次抓取的记录，没有该供应商的记录时返回 `None`
+    fn parse_recording(provider: &ProviderConfig, dir: &Path, recorded_at: DateTime<Utc>) -> Option<StatusPageSummary> {
+        let read = |suffix: &str| std::fs::read_to_string(dir.join(format!("{}{}", provider.id, suffix))).ok();
+        let mut summary = match &provider.source {
+            SourceConfig::Statuspage(config) => statuspage::parse(provider, config, &read(".json")?),
+            SourceConfig::IncidentIo(config) => incident_io::parse(provider, config, &read(".json")?),
+            SourceConfig::Instatus(config) => {
+                instatus::parse(provider, config, &read(".summary.json")?, &read(".components.json")?)
+            }
+            SourceConfig::BetterStack(config) => betterstack::parse(provider, config, &read(".json")?, recorded_at),
+            SourceConfig::StaticHtml(config) => html::parse(provider, config, &read(".html")?),
+            SourceConfig::Announcements(config) => {
+                let extension = match config.format {
+                    AnnouncementFormat::Html => ".html",
+                    AnnouncementFormat::Json => ".json",
+                };
+                announcements::parse(provider, config, &read(extension)?, recorded_at)
+            }
+            _ => return None,
+        }
+        .unwrap_or_else(|e| panic!("解析 {} 在 {} 的记录失败: {}", provider.id, dir.display(), e));
+        apply_component_filter(&mut summary, &provider.component_filter);
+        Some(summary)
+    }
+
+    #[test]
+    fn catalog_ids_are_unique() {
+        let mut seen = HashSet::new();
+        for provider in CATALOG.iter() {
+            assert!(seen.insert(provider.id.clone()), "重复的供应商ID: {}", provider.id);
+            assert!(provider.link.is_some(), "{} 缺少状态页面链接", provider.id);
+            assert!(provider.icon.is_some(), "{} 缺少图标", provider.id);
+        }
+    }
+
+    #[test]
+    fn recorded_responses_parse() {
+        for (date, dir) in recordings() {
+            // 抓取时间只精确到日期，按当天结束计算公告与报告是否过期
+            let recorded_at = date.and_hms_opt(23, 59, 59).unwrap().and_utc();
+            for provider in CATALOG.iter() {
+                if let Some(summary) = parse_recording(provider, &dir, recorded_at) {
+                    assert!(!summary.components.is_empty(), "{} 在 {} 的记录中没有解析出组件", provider.id, date);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn resolve_ignores_unknown_ids_and_overrides_filter() {
+        let entries: Vec<CatalogEntry> = serde_json::from_str(
+            r#"["groq", "no-such-provider", {"id": "cloudflare-workers-ai", "component_filter": ["Workers AI"]}]"#,
+        )
+        .unwrap();
+        let providers = resolve(&entries);
+        assert_eq!(providers.len(), 2);
+        assert_eq!(providers[0].id, "groq");
+        assert_eq!(providers[1].component_filter, vec!["Workers AI".to_string()]);
+    }
+}
@@ -0,0 +1,337 @@
+//! Google Cloud 状态数据源
+//!
+//! Vertex AI 上的 Gemini 等服务不在 AI Studio 状态页面中，而是由 Google Cloud 状态面板的
+//! 公开事件流 `incidents.json` 覆盖。该事件流包含全部产品的历史事件，按产品与区域过滤后
+//! 将未结束的事件映射为组件状态与进行中的事件。
+
+use super::{build_summary, ProviderConfig, ScrapedComponent};
+use crate::models::{
+    ComponentStatus, Incident, IncidentImpact, IncidentStatus, IncidentUpdate, IncidentUpdateStatus,
+    StatusPageSummary,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::error::Error;
+use tracing::info;
+
+/// Google Cloud 数据源配置
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct GoogleCloudConfig {
+    /// 事件流地址
+    #[serde(default = "default_url")]
+    pub url: String,
+    /// 关注的产品名称或产品ID，每个产品对应一个组件
+    pub products: Vec<String>,
+    /// 关注的区域（如 `us-central1`），为空时不按区域过滤
+    #[serde(default)]
+    pub locations: Vec<String>,
+}
+
+fn default_url() -> String {
+    "https://status.cloud.google.com/incidents.json".to_string()
+}
+
+#[derive(Debug, Deserialize)]
+struct GcpIncident {
+    id: String,
+    #[serde(default)]
+    begin: Option<String>,
+    #[serde(default)]
+    end: Option<String>,
+    #[serde(default)]
+    modified: Option<String>,
+    #[serde(default)]
+    external_desc: String,
+    #[serde(default)]
+    status_impact: String,
+    #[serde(default)]
+    uri: Option<String>,
+    #[serde(default)]
+    affected_products: Vec<Named>,
+    #[serde(default)]
+    currently_affected_locations: Vec<Named>,
+    #[serde(default)]
+    most_recent_update: Option<GcpUpdate>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Named {
+    #[serde(default)]
+    id: String,
+    #[serde(default)]
+    title: String,
+}
+
+impl Named {
+    fn matches(&self, wanted: &str) -> bool {
+        self.id == wanted || self.title.eq_ignore_ascii_case(wanted)
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct GcpUpdate {
+    #[serde(default)]
+    text: String,
+    #[serde(default)]
+    when: Option<String>,
+    #[serde(default)]
+    status: String,
+}
+
+/// 解析 `incidents.json`
+pub fn parse(
+    provider: &ProviderConfig,
+    config: &GoogleCloudConfig,
+    body: &str,
+) -> Result<StatusPageSummary, Box<dyn Error + Send + Sync>> {
+    if config.products.is_empty() {
+        return Err("未配置任何 Google Cloud 产品".into());
+    }
+    let feed: Vec<GcpIncident> = serde_json::from_str(body)?;
+
+    // 配置的可能是产品ID或大小写不同的名称，组件名称使用事件流中的产品名称；
+    // 事件流中从未出现的产品沿用配置的值
+    let titles: HashMap<&str, &str> = config
+        .products
+        .iter()
+        .filter_map(|wanted| {
+            feed.iter()
+                .flat_map(|incident| &incident.affected_products)
+                .find(|product| product.matches(wanted) && !product.title.is_empty())
+                .map(|product| (wanted.as_str(), product.title.as_str()))
+        })
+        .collect();
+    let title = |wanted: &str| titles.get(wanted).copied().unwrap_or(wanted).to_string();
+
+    let mut statuses: HashMap<&str, ComponentStatus> = HashMap::new();
+    let mut incidents = Vec::new();
+    for incident in feed.iter().filter(|i| i.end.is_none()) {
+        let products: Vec<&str> = config
+            .products
+            .iter()
+            .filter(|wanted| incident.affected_products.iter().any(|p| p.matches(wanted)))
+            .map(String::as_str)
+            .collect();
+        if products.is_empty() || !in_locations(incident, &config.locations) {
+            continue;
+        }
+
+        let status = component_status(&incident.status_impact);
+        for product in &products {
+            let current = statuses.entry(product).or_insert(ComponentStatus::Operational);
+            if status.severity() > current.severity() {
+                *current = status.clone();
+            }
+        }
+        let names: Vec<String> = products.iter().map(|product| title(product)).collect();
+        incidents.push(into_incident(incident, &names));
+    }
+
+    let components = config
+        .products
+        .iter()
+        .map(|product| ScrapedComponent {
+            name: title(product),
+            status: statuses.get(product.as_str()).cloned().unwrap_or(ComponentStatus::Operational),
+            description: None,
+        })
+        .collect();
+
+    info!("📊 {} Google Cloud 事件流解析完成 - 进行中事件: {}", provider.name, incidents.len());
+    Ok(build_summary(provider, &config.url, components, incidents, vec![], None))
+}
+
+/// 事件是否影响关注的区域，未列出受影响区域或影响 `global` 的事件视为影响所有区域
+fn in_locations(incident: &GcpIncident, locations: &[String]) -> bool {
+    locations.is_empty()
+        || incident.currently_affected_locations.is_empty()
+        || incident
+            .currently_affected_locations
+            .iter()
+            .any(|l| l.id == "global" || locations.iter().any(|wanted| l.matches(wanted)))
+}
+
+fn component_status(status_impact: &str) -> ComponentStatus {
+    match status_impact {
+        "SERVICE_OUTAGE" => ComponentStatus::MajorOutage,
+        "SERVICE_DISRUPTION" => ComponentStatus::PartialOutage,
+        "SERVICE_INFORMATION" => ComponentStatus::Operational,
+        _ => ComponentStatus::Unknown,
+    }
+}
+
+fn into_incident(incident: &GcpIncident, products: &[String]) -> Incident {
+    let impact = match incident.status_impact.as_str() {
+        "SERVICE_OUTAGE" => IncidentImpact::Critical,
+        "SERVICE_DISRUPTION" => IncidentImpact::Major,
+        "SERVICE_INFORMATION" => IncidentImpact::Minor,
+        _ => IncidentImpact::Unknown,
+    };
+    let created_at = incident.begin.clone().unwrap_or_default();
+    let updated_at = incident.modified.clone().unwrap_or_else(|| created_at.clone());
+    let update = incident.most_recent_update.as_ref();
+    let update_status = match update.map(|u| u.status.as_str()) {
+        Some("AVAILABLE") => IncidentUpdateStatus::Resolved,
+        _ => IncidentUpdateStatus::Investigating,
+    };
+
+    Incident {
+        id: incident.id.clone(),
+        name: incident.external_desc.trim().to_string(),
+        status: IncidentStatus::Investigating,
+        created_at,
+        updated_at: updated_at.clone(),
+        monitoring_at: None,
+        resolved_at: None,
+        impact,
+        shortlink: incident
+            .uri
+            .as_ref()
+            .map(|uri| format!("https://status.cloud.google.com/{}", uri.trim_start_matches('/'))),
+        page_id: None,
+        incident_updates: vec![IncidentUpdate {
+            id: format!("{}-latest", incident.id),
+            status: update_status,
+            body: update.map(|u| u.text.trim().to_string()).unwrap_or_default(),
+            display_at: update.and_then(|u| u.when.clone()).or(Some(updated_at)),
+            incident_id: Some(incident.id.clone()),
+            affected_components: Some(
+                products
+                    .iter()
+                    .map(|name| HashMap::from([("name".to_string(), name.clone())]))
+                    .collect(),
+            ),
+            delights_resolved: None,
+        }],
+        scheduled_for: None,
+        scheduled_until: None,
+        automated: Some(false),
+        classification: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 读取 `tests/fixtures/google_cloud` 下记录的事件流
+    fn fixture() -> String {
+        let path = format!("{}/tests/fixtures/google_cloud/incidents.json", env!("CARGO_MANIFEST_DIR"));
+        std::fs::read_to_string(&path).unwrap_or_else(|e| panic!("缺少测试数据 {}: {}", path, e))
+    }
+
+    fn parse_with(products: &[&str], locations: &[&str]) -> StatusPageSummary {
+        let config = GoogleCloudConfig {
+            url: default_url(),
+            products: products.iter().map(|p| p.to_string()).collect(),
+            locations: locations.iter().map(|l| l.to_string()).collect(),
+        };
+        let provider: ProviderConfig = serde_json::from_value(serde_json::json!({
+            "id": "vertex-ai", "name": "Vertex AI", "source": { "type": "google_cloud", "products": products }
+        }))
+        .unwrap();
+        parse(&provider, &config, &fixture()).unwrap()
+    }
+
+    fn statuses(summary: &StatusPageSummary) -> Vec<(&str, ComponentStatus)> {
+        summary.components.iter().map(|c| (c.name.as_str(), c.status.clone())).collect()
+    }
+
+    fn incident_ids(summary: &StatusPageSummary) -> Vec<&str> {
+        summary.incidents.iter().map(|i| i.id.as_str()).collect()
+    }
+
+    #[test]
+    fn products_take_the_worst_open_incident() {
+        let summary = parse_with(&["vertex gemini api", "Vertex AI Online Prediction"], &[]);
+        assert_eq!(
+            statuses(&summary),
+            vec![
+                ("Vertex Gemini API", ComponentStatus::MajorOutage),
+                ("Vertex AI Online Prediction", ComponentStatus::PartialOutage),
+            ]
+        );
+        // 已结束的事件与其他产品的事件不计入
+        assert_eq!(
+            incident_ids(&summary),
+            vec!["kQx7vW2pRmT9sLd4NbYe", "Hc3nA8uZq1FwJ6rEoVt5", "Pm2bG7yXc4KdR9wQaLs1"]
+        );
+    }
+
+    #[test]
+    fn locations_filter_regional_incidents_but_keep_global_ones() {
+        let summary = parse_with(&["Vertex Gemini API", "sdXM79fz1FS6ekNpu37K"], &["us-central1"]);
+        assert_eq!(
+            statuses(&summary),
+            vec![
+                ("Vertex Gemini API", ComponentStatus::MajorOutage),
+                ("Vertex AI Online Prediction", ComponentStatus::Operational),
+            ]
+        );
+        assert_eq!(incident_ids(&summary), vec!["kQx7vW2pRmT9sLd4NbYe", "Pm2bG7yXc4KdR9wQaLs1"]);
+
+        let summary = parse_with(&["Vertex Gemini API"], &["asia-east1"]);
+        assert_eq!(statuses(&summary), vec![("Vertex Gemini API", ComponentStatus::Operational)]);
+        assert!(summary.incidents.is_empty());
+    }
+
+    #[test]
+    fn component_names_come_from_the_feed() {
+        let summary = parse_with(&["sdXM79fz1FS6ekNpu37K", "vertex gemini api", "Vertex AI Search"], &[]);
+        let names: Vec<&str> = summary.components.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, ["Vertex AI Online Prediction", "Vertex Gemini API", "Vertex AI Search"]);
+        let affected: Vec<&str> = summary.incidents[1].incident_updates[0]
+            .affected_components
+            .as_ref()
+            .unwrap()
+            .iter()
+            .map(|c| c["name"].as_str())
+            .collect();
+        assert_eq!(affected, ["Vertex AI Online Prediction", "Vertex Gemini API"]);
+    }
+
+    #[test]
+    fn incidents_are_mapped() {
+        let summary = parse_with(&["Vertex Gemini API", "Vertex AI Online Prediction"], &[]);
+
+        let outage = &summary.incidents[0];
+        assert_eq!(outage.name, "Vertex Gemini API requests are failing in us-central1");
+        assert_eq!(outage.impact, IncidentImpact::Critical);
+        assert_eq!(outage.created_at, "2025-06-01T02:10:00+00:00");
+        assert_eq!(outage.updated_at, "2025-06-01T03:05:12+00:00");
+        assert_eq!(
+            outage.shortlink.as_deref(),
+            Some("https://status.cloud.google.com/incidents/kQx7vW2pRmT9sLd4NbYe")
+        );
+        let update = &outage.incident_updates[0];
+        assert_eq!(update.status, IncidentUpdateStatus::Investigating);
+        assert_eq!(update.body, "Our engineers are mitigating the issue.");
+        assert_eq!(update.display_at.as_deref(), Some("2025-06-01T03:05:12+00:00"));
+
+        // 影响多个关注产品的事件列出所有受影响组件
+        let disruption = &summary.incidents[1];
+        assert_eq!(disruption.impact, IncidentImpact::Major);
+        let affected: Vec<&str> = disruption.incident_updates[0]
+            .affected_components
+            .as_ref()
+            .unwrap()
+            .iter()
+            .map(|c| c["name"].as_str())
+            .collect();
+        assert_eq!(affected, vec!["Vertex Gemini API", "Vertex AI Online Prediction"]);
+
+        let information = &summary.incidents[2];
+        assert_eq!(information.impact, IncidentImpact::Minor);
+        assert_eq!(information.incident_updates[0].status, IncidentUpdateStatus::Resolved);
+    }
+
+    #[test]
+    fn products_are_required() {
+        let config = GoogleCloudConfig { url: default_url(), products: vec![], locations: vec![] };
+        let provider: ProviderConfig = serde_json::from_value(serde_json::json!({
+            "id": "vertex-ai", "name": "Vertex AI", "source": { "type": "google_cloud", "products": [] }
+        }))
+        .unwrap();
+        assert_eq!(parse(&provider, &config, "[]").unwrap_err().to_string(), "未配置任何 Google Cloud 产品");
+    }
+}
@@ -0,0 +1,198 @@
+//! 静态页面数据源：下载服务端渲染的状态页面并按CSS选择器提取，无需浏览器
+
+use super::{build_summary, map_value, PageSelectors, ProviderConfig, ScrapedComponent, StatusSource};
+use crate::models::{OverallStatus, StatusPageSummary};
+use scraper::{ElementRef, Html, Selector};
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+use tracing::{info, warn};
+
+/// 静态页面配置
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct StaticHtmlConfig {
+    pub url: String,
+    #[serde(flatten)]
+    pub selectors: PageSelectors,
+}
+
+/// 解析页面内容
+pub fn parse(
+    provider: &ProviderConfig,
+    config: &StaticHtmlConfig,
+    html: &str,
+) -> Result<StatusPageSummary, Box<dyn Error + Send + Sync>> {
+    let document = Html::parse_document(html);
+    let selectors = &config.selectors;
+
+    let status = match &selectors.overall_status {
+        Some(selector) => {
+            let text = document
+                .select(&compile(selector)?)
+                .next()
+                .map(element_text)
+                .unwrap_or_default();
+            match map_value(&selectors.overall_mappings, &text) {
+                Some(indicator) => Some(OverallStatus {
+                    indicator,
+                    description: text,
+                }),
+                None => {
+                    warn!("⚠️ {} 的整体状态文本未命中任何映射: {}", provider.name, text);
+                    None
+                }
+            }
+        }
+        None => None,
+    };
+
+    let name_selector = compile(&selectors.service_name)?;
+    let status_selector = selectors.service_status.as_deref().map(compile).transpose()?;
+
+    let mut components = Vec::new();
+    for row in document.select(&compile(&selectors.service_list)?) {
+        let Some(name) = row.select(&name_selector).next().map(element_text) else {
+            continue;
+        };
+        if name.is_empty() {
+            continue;
+        }
+
+        let status_element = match &status_selector {
+            Some(selector) => match row.select(selector).next() {
+                Some(element) => element,
+                None => continue,
+            },
+            None => row,
+        };
+
+        let raw = match &selectors.status_from {
+            StatusSource::Class => status_element.value().attr("class").unwrap_or_default().to_string(),
+            StatusSource::Text => element_text(status_element),
+            StatusSource::Attribute(attr) => status_element.value().attr(attr).unwrap_or_default().to_string(),
+        };
+        let status = map_value(&selectors.mappings, &raw).unwrap_or_else(|| {
+            warn!("⚠️ {} 服务 {} 的状态值未命中任何映射: {}", provider.name, name, raw.trim());
+            selectors.default_status.clone()
+        });
+
+        components.push(ScrapedComponent {
+            name,
+            status,
+            description: None,
+        });
+    }
+
+    if components.is_empty() {
+        return Err(format!("页面中未找到任何服务: {}", selectors.service_list).into());
+    }
+
+    info!("📊 {} 页面解析完成 - 服务数: {}", provider.name, components.len());
+    Ok(build_summary(provider, &config.url, components, vec![], vec![], status))
+}
+
+pub(super) fn compile(selector: &str) -> Result<Selector, Box<dyn Error + Send + Sync>> {
+    Selector::parse(selector).map_err(|e| format!("无效的选择器 {}: {}", selector, e).into())
+}
+
+/// 元素的文本内容，合并空白
+pub(super) fn element_text(element: ElementRef) -> String {
+    element.text().collect::<Vec<_>>().join(" ").split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{ComponentStatus, StatusIndicator};
+
+    const PAGE: &str = r#"<html><body>
+        <h2 class="page-status">All Systems Operational</h2>
+        <ul>
+            <li class="service"><span class="name">Chat API</span><span class="state ok" data-status="up">Operational</span></li>
+            <li class="service"><span class="name"> Embeddings
+                API </span><span class="state warn" data-status="slow">Degraded</span></li>
+            <li class="service"><span class="name">Files</span><span class="state" data-status="gone">Unknown</span></li>
+            <li class="service"><span class="name">Batch</span></li>
+            <li class="service"><span class="name"> </span><span class="state ok">Operational</span></li>
+        </ul>
+    </body></html>"#;
+
+    fn setup(selectors: serde_json::Value) -> (ProviderConfig, StaticHtmlConfig) {
+        let mut source = serde_json::json!({
+            "type": "static_html",
+            "url": "https://status.example.com",
+            "service_list": "li.service",
+            "service_name": ".name",
+            "service_status": ".state",
+            "mappings": [
+                { "contains": "ok", "status": "operational" },
+                { "contains": "warn", "status": "degraded_performance" }
+            ]
+        });
+        source.as_object_mut().unwrap().extend(selectors.as_object().unwrap().clone());
+        let provider: ProviderConfig =
+            serde_json::from_value(serde_json::json!({ "id": "example", "name": "Example", "source": source })).unwrap();
+        let crate::sources::SourceConfig::StaticHtml(config) = provider.source.clone() else {
+            panic!("不是静态页面数据源");
+        };
+        (provider, config)
+    }
+
+    fn statuses(summary: &StatusPageSummary) -> Vec<(&str, ComponentStatus)> {
+        summary.components.iter().map(|c| (c.name.as_str(), c.status.clone())).collect()
+    }
+
+    #[test]
+    fn class_mapping_with_default_status() {
+        let (provider, config) = setup(serde_json::json!({ "default_status": "major_outage" }));
+        let summary = parse(&provider, &config, PAGE).unwrap();
+        // 缺少状态元素或名称为空的行被跳过，名称中的空白被合并
+        assert_eq!(
+            statuses(&summary),
+            [
+                ("Chat API", ComponentStatus::Operational),
+                ("Embeddings API", ComponentStatus::DegradedPerformance),
+                ("Files", ComponentStatus::MajorOutage),
+            ]
+        );
+        // 未配置整体状态选择器时由组件状态推导
+        assert_eq!(summary.status.indicator, StatusIndicator::Major);
+    }
+
+    #[test]
+    fn text_and_attribute_sources() {
+        let (provider, config) = setup(serde_json::json!({
+            "overall_status": ".page-status",
+            "overall_mappings": [{ "contains": "all systems", "status": "none" }],
+            "status_from": { "attribute": "data-status" },
+            "mappings": [
+                { "regex": "^up$", "status": "operational" },
+                { "contains": "slow", "status": "degraded_performance" }
+            ]
+        }));
+        let summary = parse(&provider, &config, PAGE).unwrap();
+        assert_eq!(summary.status.indicator, StatusIndicator::None);
+        assert_eq!(summary.status.description, "All Systems Operational");
+        assert_eq!(summary.components[1].status, ComponentStatus::DegradedPerformance);
+        assert_eq!(summary.components[2].status, ComponentStatus::Unknown);
+
+        let (provider, config) = setup(serde_json::json!({
+            "status_from": "text",
+            "mappings": [{ "contains": "degraded", "status": "degraded_performance" }],
+            "default_status": "operational"
+        }));
+        let summary = parse(&provider, &config, PAGE).unwrap();
+        assert_eq!(summary.components[0].status, ComponentStatus::Operational);
+        assert_eq!(summary.components[1].status, ComponentStatus::DegradedPerformance);
+    }
+
+    #[test]
+    fn missing_rows_and_invalid_selectors_fail() {
+        let (provider, config) = setup(serde_json::json!({ "service_list": ".missing" }));
+        let error = parse(&provider, &config, PAGE).unwrap_err();
+        assert!(error.to_string().contains("页面中未找到任何服务"), "{}", error);
+
+        let (provider, config) = setup(serde_json::json!({ "service_name": "[[" }));
+        let error = parse(&provider, &config, PAGE).unwrap_err();
+        assert!(error.to_string().contains("无效的选择器 [["), "{}", error);
+    }
+}
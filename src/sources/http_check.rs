@@ -0,0 +1,289 @@
+//! 通用 HTTP 可用性检查：向向量数据库、向量化服务、自建网关等任意接口发请求，
+//! 按状态码与响应内容判断是否正常，每个检查对应一个组件。
+
+use super::json::{query, query_text};
+use super::{build_summary, expand_headers, ProviderConfig, ScrapedComponent};
+use crate::models::{ComponentStatus, StatusPageSummary};
+use reqwest::{Client, Method};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::error::Error;
+use std::sync::{LazyLock, Mutex};
+use std::time::{Duration, Instant};
+use tracing::info;
+
+/// 各检查（按供应商ID与检查名称）最近一次的结果，未到检查间隔时直接复用
+static LAST_RESULTS: LazyLock<Mutex<HashMap<(String, String), LastResult>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// HTTP 检查数据源配置
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct HttpCheckConfig {
+    pub checks: Vec<HttpCheck>,
+}
+
+/// 单个接口检查
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct HttpCheck {
+    /// 组件名称，如 `Qdrant`
+    pub name: String,
+    pub url: String,
+    #[serde(default = "default_method")]
+    pub method: String,
+    /// 额外请求头，值中的 `${VAR}` 会替换为环境变量
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub headers: HashMap<String, String>,
+    /// 请求体（POST 等）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body: Option<String>,
+    /// 视为正常的状态码，为空时接受任意 2xx
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub expected_status: Vec<u16>,
+    /// 响应体必须包含的文本
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body_contains: Option<String>,
+    /// 响应体必须存在的 JSONPath，如 `$.status`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub json_path: Option<String>,
+    /// `json_path` 取到的值必须等于该文本
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub json_equals: Option<String>,
+    /// 单次请求超时（秒）
+    #[serde(default = "default_timeout_secs")]
+    pub timeout_secs: u64,
+    /// 检查间隔（秒），未配置时每轮刷新都检查；短于全局刷新间隔时按刷新间隔执行
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub interval_secs: Option<u64>,
+}
+
+fn default_method() -> String {
+    "GET".to_string()
+}
+
+fn default_timeout_secs() -> u64 {
+    10
+}
+
+/// 单个检查的结果，失败时为原因
+pub type CheckResult = Result<CheckOutcome, String>;
+
+/// 检查时间与结果
+type LastResult = (Instant, CheckResult);
+
+/// 检查通过时的响应信息
+#[derive(Debug, Clone, PartialEq)]
+pub struct CheckOutcome {
+    pub status: u16,
+    pub latency_ms: u64,
+}
+
+/// 返回仍在检查间隔内的上一次结果
+pub fn cached(provider: &ProviderConfig, check: &HttpCheck) -> Option<CheckResult> {
+    let interval = Duration::from_secs(check.interval_secs?);
+    let last = LAST_RESULTS.lock().unwrap();
+    let (checked_at, result) = last.get(&(provider.id.clone(), check.name.clone()))?;
+    (checked_at.elapsed() < interval).then(|| result.clone())
+}
+
+/// 记录本次检查结果
+pub fn remember(provider: &ProviderConfig, check: &HttpCheck, result: &CheckResult) {
+    LAST_RESULTS
+        .lock()
+        .unwrap()
+        .insert((provider.id.clone(), check.name.clone()), (Instant::now(), result.clone()));
+}
+
+/// 发送一次请求并校验响应，不符合预期时返回错误以便重试
+pub async fn check_once(client: &Client, check: &HttpCheck) -> Result<CheckOutcome, Box<dyn Error + Send + Sync>> {
+    let method = Method::from_bytes(check.method.to_uppercase().as_bytes())
+        .map_err(|_| format!("无效的请求方法: {}", check.method))?;
+    let mut request = client.request(method, &check.url).timeout(Duration::from_secs(check.timeout_secs));
+    for (name, value) in expand_headers(&check.headers) {
+        request = request.header(name, value);
+    }
+    if let Some(body) = &check.body {
+        request = request.body(body.clone());
+    }
+
+    let started = Instant::now();
+    let response = request.send().await.map_err(|e| -> Box<dyn Error + Send + Sync> {
+        if e.is_timeout() { "请求超时".into() } else { e.into() }
+    })?;
+    let status = response.status().as_u16();
+    let body = response.text().await?;
+    let latency_ms = started.elapsed().as_millis() as u64;
+
+    verify(check, status, &body)?;
+    Ok(CheckOutcome { status, latency_ms })
+}
+
+/// 按状态码、关键字与 JSON 断言校验响应
+pub fn verify(check: &HttpCheck, status: u16, body: &str) -> Result<(), String> {
+    let status_ok = if check.expected_status.is_empty() {
+        (200..300).contains(&status)
+    } else {
+        check.expected_status.contains(&status)
+    };
+    if !status_ok {
+        return Err(format!("返回 HTTP {}", status));
+    }
+
+    if let Some(keyword) = &check.body_contains {
+        if !body.contains(keyword.as_str()) {
+            return Err(format!("响应中未找到 \"{}\"", keyword));
+        }
+    }
+
+    if let Some(path) = &check.json_path {
+        let json: Value = serde_json::from_str(body).map_err(|_| "响应不是有效的 JSON".to_string())?;
+        match &check.json_equals {
+            Some(expected) => {
+                let actual = query_text(path, &json).map_err(|e| e.to_string())?;
+                if actual.as_deref() != Some(expected.as_str()) {
+                    return Err(format!("{} 为 {}，期望 {}", path, actual.as_deref().unwrap_or("空"), expected));
+                }
+            }
+            None => {
+                let found = query(path, &json).map_err(|e| e.to_string())?;
+                if found.iter().all(Value::is_null) {
+                    return Err(format!("响应中未找到 {}", path));
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// 将各检查结果转换为组件
+pub fn parse(
+    provider: &ProviderConfig,
+    config: &HttpCheckConfig,
+    results: &[CheckResult],
+) -> StatusPageSummary {
+    let components: Vec<ScrapedComponent> = config
+        .checks
+        .iter()
+        .zip(results)
+        .map(|(check, result)| match result {
+            Ok(outcome) => ScrapedComponent {
+                name: check.name.clone(),
+                status: ComponentStatus::Operational,
+                description: Some(format!("HTTP {} · {}ms", outcome.status, outcome.latency_ms)),
+            },
+            Err(e) => ScrapedComponent {
+                name: check.name.clone(),
+                status: ComponentStatus::MajorOutage,
+                description: Some(e.clone()),
+            },
+        })
+        .collect();
+
+    info!(
+        "📊 {} HTTP 检查完成 - 检查数: {}, 正常: {}",
+        provider.name,
+        components.len(),
+        results.iter().filter(|r| r.is_ok()).count()
+    );
+    let url = config.checks.first().map(|c| c.url.as_str()).unwrap_or_default();
+    build_summary(provider, url, components, Vec::new(), Vec::new(), None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check(value: serde_json::Value) -> HttpCheck {
+        let mut base = serde_json::json!({ "name": "Qdrant", "url": "http://127.0.0.1:6333/readyz" });
+        base.as_object_mut().unwrap().extend(value.as_object().unwrap().clone());
+        serde_json::from_value(base).unwrap()
+    }
+
+    #[test]
+    fn status_defaults_to_any_2xx() {
+        let c = check(serde_json::json!({}));
+        assert_eq!(c.method, "GET");
+        assert!(verify(&c, 204, "").is_ok());
+        assert_eq!(verify(&c, 503, "").unwrap_err(), "返回 HTTP 503");
+
+        let c = check(serde_json::json!({ "expected_status": [401] }));
+        assert!(verify(&c, 401, "").is_ok());
+        assert!(verify(&c, 200, "").is_err());
+    }
+
+    #[test]
+    fn body_keyword_and_json_assertions() {
+        let c = check(serde_json::json!({ "body_contains": "all shards are ready" }));
+        assert!(verify(&c, 200, "all shards are ready").is_ok());
+        assert_eq!(verify(&c, 200, "starting").unwrap_err(), "响应中未找到 \"all shards are ready\"");
+
+        let c = check(serde_json::json!({ "json_path": "$.status", "json_equals": "ok" }));
+        assert!(verify(&c, 200, r#"{"status":"ok"}"#).is_ok());
+        assert_eq!(verify(&c, 200, r#"{"status":"degraded"}"#).unwrap_err(), "$.status 为 degraded，期望 ok");
+        assert_eq!(verify(&c, 200, "<html>").unwrap_err(), "响应不是有效的 JSON");
+
+        let c = check(serde_json::json!({ "json_path": "$.data.version" }));
+        assert!(verify(&c, 200, r#"{"data":{"version":"1.2"}}"#).is_ok());
+        assert!(verify(&c, 200, r#"{"data":{}}"#).is_err());
+    }
+
+    #[test]
+    fn results_become_components() {
+        let config = HttpCheckConfig {
+            checks: vec![check(serde_json::json!({})), check(serde_json::json!({ "name": "Gateway" }))],
+        };
+        let provider: ProviderConfig = serde_json::from_value(serde_json::json!({
+            "id": "infra", "name": "Infra", "source": { "type": "http_check", "checks": [] }
+        }))
+        .unwrap();
+        let summary = parse(
+            &provider,
+            &config,
+            &[Ok(CheckOutcome { status: 200, latency_ms: 42 }), Err("请求超时".to_string())],
+        );
+        assert_eq!(summary.components[0].status, ComponentStatus::Operational);
+        assert_eq!(summary.components[0].description.as_deref(), Some("HTTP 200 · 42ms"));
+        assert_eq!(summary.components[1].name, "Gateway");
+        assert_eq!(summary.components[1].status, ComponentStatus::MajorOutage);
+        assert_eq!(summary.components[1].description.as_deref(), Some("请求超时"));
+    }
+
+    #[tokio::test]
+    async fn check_once_sends_method_and_body() {
+        use axum::{routing::post, Router};
+        let app = Router::new().route("/embed", post(|body: String| async move { format!("{{\"echo\":\"{}\"}}", body) }));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        let c = check(serde_json::json!({
+            "url": format!("http://{}/embed", addr),
+            "method": "post",
+            "body": "ping",
+            "json_path": "$.echo",
+            "json_equals": "ping"
+        }));
+        let outcome = check_once(&Client::new(), &c).await.unwrap();
+        assert_eq!(outcome.status, 200);
+
+        let c = check(serde_json::json!({ "url": format!("http://{}/embed", addr) }));
+        assert_eq!(check_once(&Client::new(), &c).await.unwrap_err().to_string(), "返回 HTTP 405");
+    }
+
+    #[test]
+    fn results_are_cached_per_check() {
+        let provider: ProviderConfig = serde_json::from_value(serde_json::json!({
+            "id": "cache-test", "name": "Cache", "source": { "type": "http_check", "checks": [] }
+        }))
+        .unwrap();
+        let slow = check(serde_json::json!({ "name": "Slow", "interval_secs": 600 }));
+        let every = check(serde_json::json!({ "name": "Every" }));
+        assert!(cached(&provider, &slow).is_none());
+
+        remember(&provider, &slow, &Ok(CheckOutcome { status: 200, latency_ms: 5 }));
+        remember(&provider, &every, &Err("请求超时".to_string()));
+        assert_eq!(cached(&provider, &slow), Some(Ok(CheckOutcome { status: 200, latency_ms: 5 })));
+        assert!(cached(&provider, &every).is_none());
+    }
+}
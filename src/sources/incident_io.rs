@@ -0,0 +1,396 @@
+//! incident.io 状态页面数据源
+//!
+//! incident.io 托管的状态页面通过 `/proxy/<域名>` 提供页面摘要，结构与 statuspage 的
+//! `/api/v2/summary.json` 不同：组件按分组组织在 `structure` 中，只有受影响的组件才会
+//! 出现在 `affected_components` 里。
+
+use super::{build_summary, ProviderConfig, ScrapedComponent};
+use crate::models::{
+    ComponentStatus, Incident, IncidentImpact, IncidentStatus, IncidentUpdate, IncidentUpdateStatus,
+    MaintenanceStatus, ScheduledMaintenance, StatusPageSummary,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::error::Error;
+use tracing::info;
+
+/// incident.io 数据源配置
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct IncidentIoConfig {
+    /// 状态页面地址，如 `https://status.openai.com`
+    pub url: String,
+}
+
+impl IncidentIoConfig {
+    /// 页面摘要接口地址
+    pub fn api_url(&self) -> String {
+        let base = self.url.trim_end_matches('/');
+        let host = base
+            .split_once("://")
+            .map_or(base, |(_, rest)| rest)
+            .split('/')
+            .next()
+            .unwrap_or_default();
+        format!("{}/proxy/{}", base, host)
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct ProxyResponse {
+    summary: Summary,
+}
+
+#[derive(Debug, Deserialize)]
+struct Summary {
+    #[serde(default)]
+    structure: Structure,
+    #[serde(default)]
+    affected_components: Vec<AffectedComponent>,
+    #[serde(default)]
+    ongoing_incidents: Vec<ProxyIncident>,
+    #[serde(default)]
+    scheduled_maintenances: Vec<ProxyIncident>,
+    #[serde(default)]
+    in_progress_maintenances: Vec<ProxyIncident>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct Structure {
+    #[serde(default)]
+    items: Vec<StructureItem>,
+}
+
+/// 结构项：单个组件或组件分组
+#[derive(Debug, Deserialize)]
+struct StructureItem {
+    #[serde(default)]
+    component: Option<ProxyComponent>,
+    #[serde(default)]
+    group: Option<ProxyGroup>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ProxyComponent {
+    #[serde(alias = "id")]
+    component_id: String,
+    name: String,
+    #[serde(default)]
+    hidden: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct ProxyGroup {
+    #[serde(default)]
+    components: Vec<ProxyComponent>,
+    #[serde(default)]
+    hidden: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct AffectedComponent {
+    #[serde(alias = "id")]
+    component_id: String,
+    #[serde(default)]
+    status: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct ProxyIncident {
+    id: String,
+    name: String,
+    #[serde(default)]
+    status: String,
+    #[serde(default)]
+    current_worst_impact: Option<String>,
+    #[serde(default)]
+    published_at: Option<String>,
+    #[serde(default)]
+    last_update_at: Option<String>,
+    #[serde(default)]
+    last_update_message: Option<String>,
+    #[serde(default)]
+    resolved_at: Option<String>,
+    #[serde(default)]
+    starts_at: Option<String>,
+    #[serde(default)]
+    ends_at: Option<String>,
+    #[serde(default)]
+    url: Option<String>,
+    #[serde(default)]
+    affected_components: Vec<AffectedComponent>,
+}
+
+/// 解析 `/proxy/<域名>` 返回的页面摘要
+pub fn parse(
+    provider: &ProviderConfig,
+    config: &IncidentIoConfig,
+    body: &str,
+) -> Result<StatusPageSummary, Box<dyn Error + Send + Sync>> {
+    let summary = serde_json::from_str::<ProxyResponse>(body)?.summary;
+
+    let affected: HashMap<&str, ComponentStatus> = summary
+        .affected_components
+        .iter()
+        .map(|c| (c.component_id.as_str(), component_status(&c.status)))
+        .collect();
+
+    let mut names: HashMap<&str, &str> = HashMap::new();
+    let mut components = Vec::new();
+    for item in &summary.structure.items {
+        let members: Vec<&ProxyComponent> = match (&item.component, &item.group) {
+            (Some(component), _) => vec![component],
+            (None, Some(group)) if !group.hidden => group.components.iter().collect(),
+            _ => Vec::new(),
+        };
+        for component in members.into_iter().filter(|c| !c.hidden) {
+            names.insert(&component.component_id, &component.name);
+            components.push(ScrapedComponent {
+                name: component.name.clone(),
+                status: affected
+                    .get(component.component_id.as_str())
+                    .cloned()
+                    .unwrap_or(ComponentStatus::Operational),
+                description: None,
+            });
+        }
+    }
+
+    if components.is_empty() {
+        return Err("页面摘要中未找到任何组件".into());
+    }
+
+    let incidents: Vec<Incident> = summary
+        .ongoing_incidents
+        .iter()
+        .map(|incident| into_incident(incident, &names))
+        .collect();
+
+    let maintenances: Vec<ScheduledMaintenance> = summary
+        .in_progress_maintenances
+        .iter()
+        .map(|m| into_maintenance(m, MaintenanceStatus::InProgress, &names))
+        .chain(
+            summary
+                .scheduled_maintenances
+                .iter()
+                .map(|m| into_maintenance(m, MaintenanceStatus::Scheduled, &names)),
+        )
+        .collect();
+
+    info!(
+        "📊 {} incident.io 解析完成 - 组件数: {}, 进行中事件: {}, 维护: {}",
+        provider.name,
+        components.len(),
+        incidents.len(),
+        maintenances.len()
+    );
+    Ok(build_summary(provider, &config.url, components, incidents, maintenances, None))
+}
+
+/// incident.io 组件状态，`full_outage` 对应 statuspage 的 `major_outage`
+fn component_status(status: &str) -> ComponentStatus {
+    match status {
+        "operational" => ComponentStatus::Operational,
+        "degraded_performance" => ComponentStatus::DegradedPerformance,
+        "partial_outage" => ComponentStatus::PartialOutage,
+        "full_outage" | "major_outage" => ComponentStatus::MajorOutage,
+        "under_maintenance" => ComponentStatus::UnderMaintenance,
+        _ => ComponentStatus::Unknown,
+    }
+}
+
+fn incident_impact(impact: Option<&str>) -> IncidentImpact {
+    match impact {
+        Some("degraded_performance") => IncidentImpact::Minor,
+        Some("partial_outage") => IncidentImpact::Major,
+        Some("full_outage") => IncidentImpact::Critical,
+        _ => IncidentImpact::Unknown,
+    }
+}
+
+fn affected_list(
+    affected: &[AffectedComponent],
+    names: &HashMap<&str, &str>,
+) -> Option<Vec<HashMap<String, String>>> {
+    let list: Vec<HashMap<String, String>> = affected
+        .iter()
+        .filter_map(|c| names.get(c.component_id.as_str()))
+        .map(|name| HashMap::from([("name".to_string(), name.to_string())]))
+        .collect();
+    (!list.is_empty()).then_some(list)
+}
+
+fn into_incident(incident: &ProxyIncident, names: &HashMap<&str, &str>) -> Incident {
+    let (status, update_status) = match incident.status.as_str() {
+        "identified" => (IncidentStatus::Identified, IncidentUpdateStatus::Identified),
+        "monitoring" => (IncidentStatus::Monitoring, IncidentUpdateStatus::Monitoring),
+        "resolved" => (IncidentStatus::Resolved, IncidentUpdateStatus::Resolved),
+        _ => (IncidentStatus::Investigating, IncidentUpdateStatus::Investigating),
+    };
+    let created_at = incident.published_at.clone().unwrap_or_default();
+    let updated_at = incident.last_update_at.clone().unwrap_or_else(|| created_at.clone());
+    // 已解决但未给出解决时间时，以最后一次更新时间为准
+    let resolved_at = match status {
+        IncidentStatus::Resolved => incident.resolved_at.clone().or_else(|| Some(updated_at.clone())),
+        _ => None,
+    };
+
+    Incident {
+        id: incident.id.clone(),
+        name: incident.name.clone(),
+        status,
+        created_at: created_at.clone(),
+        updated_at: updated_at.clone(),
+        monitoring_at: None,
+        resolved_at,
+        impact: incident_impact(incident.current_worst_impact.as_deref()),
+        shortlink: incident.url.clone(),
+        page_id: None,
+        incident_updates: vec![IncidentUpdate {
+            id: format!("{}-latest", incident.id),
+            status: update_status,
+            body: incident.last_update_message.clone().unwrap_or_default(),
+            display_at: Some(updated_at),
+            incident_id: Some(incident.id.clone()),
+            affected_components: affected_list(&incident.affected_components, names),
+            delights_resolved: None,
+        }],
+        scheduled_for: None,
+        scheduled_until: None,
+        automated: Some(false),
+        classification: None,
+    }
+}
+
+fn into_maintenance(
+    maintenance: &ProxyIncident,
+    status: MaintenanceStatus,
+    names: &HashMap<&str, &str>,
+) -> ScheduledMaintenance {
+    let created_at = maintenance
+        .published_at
+        .clone()
+        .or_else(|| maintenance.starts_at.clone())
+        .unwrap_or_default();
+    let updated_at = maintenance.last_update_at.clone().unwrap_or_else(|| created_at.clone());
+
+    ScheduledMaintenance {
+        id: maintenance.id.clone(),
+        name: maintenance.name.clone(),
+        status,
+        created_at: created_at.clone(),
+        updated_at: updated_at.clone(),
+        monitoring_at: None,
+        resolved_at: maintenance.resolved_at.clone(),
+        shortlink: maintenance.url.clone(),
+        incident_updates: vec![IncidentUpdate {
+            id: format!("{}-latest", maintenance.id),
+            status: IncidentUpdateStatus::Unknown,
+            body: maintenance.last_update_message.clone().unwrap_or_default(),
+            display_at: Some(updated_at),
+            incident_id: Some(maintenance.id.clone()),
+            affected_components: affected_list(&maintenance.affected_components, names),
+            delights_resolved: None,
+        }],
+        scheduled_for: maintenance.starts_at.clone(),
+        scheduled_until: maintenance.ends_at.clone(),
+        automated: Some(false),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fetcher::LlmProvider;
+    use crate::models::StatusIndicator;
+    use crate::sources::SourceConfig;
+
+    fn fixture() -> String {
+        let path = format!("{}/tests/fixtures/incident_io/openai.json", env!("CARGO_MANIFEST_DIR"));
+        std::fs::read_to_string(&path).unwrap_or_else(|e| panic!("缺少测试数据 {}: {}", path, e))
+    }
+
+    fn openai() -> (ProviderConfig, IncidentIoConfig) {
+        let provider = LlmProvider::OpenAI.builtin_source().expect("OpenAI 应使用 incident.io 数据源");
+        let SourceConfig::IncidentIo(config) = provider.source.clone() else {
+            panic!("OpenAI 不是 incident.io 数据源");
+        };
+        (provider, config)
+    }
+
+    #[test]
+    fn openai_uses_incident_io() {
+        let (provider, config) = openai();
+        assert_eq!(provider.id, "openai");
+        assert_eq!(config.api_url(), "https://status.openai.com/proxy/status.openai.com");
+        assert!(LlmProvider::Anthropic.builtin_source().is_none());
+        assert!(LlmProvider::DeepSeek.builtin_source().is_none());
+
+        let nested = IncidentIoConfig { url: "https://status.example.com/".to_string() };
+        assert_eq!(nested.api_url(), "https://status.example.com/proxy/status.example.com");
+    }
+
+    #[test]
+    fn components_skip_hidden_entries() {
+        let (provider, config) = openai();
+        let summary = parse(&provider, &config, &fixture()).unwrap();
+        let components: Vec<(&str, ComponentStatus)> =
+            summary.components.iter().map(|c| (c.name.as_str(), c.status.clone())).collect();
+        assert_eq!(
+            components,
+            [
+                ("Responses", ComponentStatus::Operational),
+                ("Chat Completions", ComponentStatus::DegradedPerformance),
+                ("Realtime", ComponentStatus::Operational),
+                ("ChatGPT", ComponentStatus::MajorOutage),
+                ("Sora", ComponentStatus::UnderMaintenance),
+            ]
+        );
+        assert_eq!(summary.status.indicator, StatusIndicator::Major);
+    }
+
+    #[test]
+    fn incidents_and_maintenances() {
+        let (provider, config) = openai();
+        let summary = parse(&provider, &config, &fixture()).unwrap();
+
+        let ongoing = &summary.incidents[0];
+        assert_eq!(ongoing.status, IncidentStatus::Identified);
+        assert_eq!(ongoing.impact, IncidentImpact::Critical);
+        assert_eq!(ongoing.resolved_at, None);
+        assert_eq!(ongoing.updated_at, "2025-06-10T08:15:40.000Z");
+        let update = &ongoing.incident_updates[0];
+        assert_eq!(update.body, "We have identified the issue and are working on a fix.");
+        let affected: Vec<&str> = update.affected_components.as_ref().unwrap().iter().map(|c| c["name"].as_str()).collect();
+        assert_eq!(affected, ["ChatGPT", "Chat Completions"]);
+
+        let resolved = &summary.incidents[1];
+        assert_eq!(resolved.status, IncidentStatus::Resolved);
+        assert_eq!(resolved.impact, IncidentImpact::Minor);
+        assert_eq!(resolved.resolved_at.as_deref(), Some("2025-06-10T06:00:00.000Z"));
+        // 未给出解决时间时取最后更新时间
+        let without_time = &summary.incidents[2];
+        assert_eq!(without_time.resolved_at.as_deref(), Some("2025-06-10T04:30:00.000Z"));
+        assert_eq!(without_time.incident_updates[0].affected_components, None);
+
+        let maintenances: Vec<(&str, MaintenanceStatus)> =
+            summary.scheduled_maintenances.iter().map(|m| (m.name.as_str(), m.status.clone())).collect();
+        assert_eq!(
+            maintenances,
+            [
+                ("Sora database upgrade", MaintenanceStatus::InProgress),
+                ("Realtime API network maintenance", MaintenanceStatus::Scheduled),
+            ]
+        );
+        let scheduled = &summary.scheduled_maintenances[1];
+        assert_eq!(scheduled.created_at, "2025-06-12T02:00:00.000Z");
+        assert_eq!(scheduled.scheduled_until.as_deref(), Some("2025-06-12T03:00:00.000Z"));
+    }
+
+    #[test]
+    fn empty_structure_is_an_error() {
+        let (provider, config) = openai();
+        let error = parse(&provider, &config, r#"{"summary": {"structure": {"items": []}}}"#).unwrap_err();
+        assert!(error.to_string().contains("未找到任何组件"), "{}", error);
+    }
+}
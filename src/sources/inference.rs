@@ -0,0 +1,464 @@
+//! 自建推理服务数据源（vLLM、Text Generation Inference、Ollama、llama.cpp server）
+//!
+//! 轮询每个实例的健康检查、模型与 Prometheus 指标接口，每个实例对应一个组件，
+//! 描述中给出已加载的模型与排队情况，使自建集群与云端供应商的容量显示在同一看板上。
+//! 内网实例通常使用 http，因此使用 `StatusFetcher` 中不限制 https 的监控客户端。
+
+use super::{build_summary, expand_headers, ProviderConfig, ScrapedComponent};
+use crate::models::{ComponentStatus, StatusPageSummary};
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::error::Error;
+use std::time::Duration;
+use tokio::task::JoinSet;
+use tracing::info;
+
+/// 推理服务数据源配置，`vllm`、`tgi`、`ollama`、`llama_cpp` 共用
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct InferenceConfig {
+    pub instances: Vec<InferenceInstance>,
+    /// 额外请求头（如鉴权），值支持 `${VAR}`
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub headers: HashMap<String, String>,
+    /// 排队请求数达到该值时视为性能下降
+    #[serde(default = "default_queue_warn")]
+    pub queue_warn: u64,
+    /// 每个请求的超时时间（秒）
+    #[serde(default = "default_timeout_secs")]
+    pub timeout_secs: u64,
+}
+
+/// 单个推理服务实例
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct InferenceInstance {
+    /// 组件名称，如 `gpu-node-1`
+    pub name: String,
+    /// 实例地址，如 `http://10.0.0.12:8000`
+    pub url: String,
+    /// 应当提供的模型，配置后未找到该模型时视为部分中断
+    #[serde(default)]
+    pub model: Option<String>,
+}
+
+fn default_queue_warn() -> u64 {
+    10
+}
+
+fn default_timeout_secs() -> u64 {
+    5
+}
+
+/// 推理服务类型
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ServerKind {
+    Vllm,
+    Tgi,
+    Ollama,
+    LlamaCpp,
+}
+
+/// 各类型的接口路径
+struct Endpoints {
+    health: &'static str,
+    /// 可用模型列表
+    models: &'static str,
+    /// 已加载到显存的模型（仅 Ollama）
+    loaded: Option<&'static str>,
+    metrics: Option<&'static str>,
+}
+
+impl ServerKind {
+    fn endpoints(self) -> Endpoints {
+        match self {
+            ServerKind::Vllm => Endpoints { health: "/health", models: "/v1/models", loaded: None, metrics: Some("/metrics") },
+            ServerKind::Tgi => Endpoints { health: "/health", models: "/info", loaded: None, metrics: Some("/metrics") },
+            ServerKind::Ollama => Endpoints { health: "/", models: "/api/tags", loaded: Some("/api/ps"), metrics: None },
+            ServerKind::LlamaCpp => Endpoints { health: "/health", models: "/v1/models", loaded: None, metrics: Some("/metrics") },
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            ServerKind::Vllm => "vLLM",
+            ServerKind::Tgi => "TGI",
+            ServerKind::Ollama => "Ollama",
+            ServerKind::LlamaCpp => "llama.cpp",
+        }
+    }
+}
+
+/// 单个实例各接口的原始响应
+#[derive(Debug)]
+pub struct InstanceSnapshot {
+    /// 健康检查的 HTTP 状态码与响应体，连接失败时为错误信息
+    pub health: Result<(u16, String), String>,
+    pub models: Option<String>,
+    pub loaded: Option<String>,
+    pub metrics: Option<String>,
+}
+
+impl Default for InstanceSnapshot {
+    fn default() -> Self {
+        Self { health: Err(String::new()), models: None, loaded: None, metrics: None }
+    }
+}
+
+/// 获取全部实例的状态
+pub async fn fetch(
+    client: &Client,
+    provider: &ProviderConfig,
+    config: &InferenceConfig,
+    kind: ServerKind,
+) -> Result<StatusPageSummary, Box<dyn Error + Send + Sync>> {
+    if config.instances.is_empty() {
+        return Err("未配置任何实例".into());
+    }
+    let headers = expand_headers(&config.headers);
+    let timeout = Duration::from_secs(config.timeout_secs);
+
+    let mut tasks = JoinSet::new();
+    for (index, instance) in config.instances.iter().enumerate() {
+        let (client, headers, url) = (client.clone(), headers.clone(), instance.url.clone());
+        tasks.spawn(async move { (index, snapshot(&client, &headers, &url, kind, timeout).await) });
+    }
+    let mut snapshots: Vec<(usize, InstanceSnapshot)> = Vec::new();
+    while let Some(result) = tasks.join_next().await {
+        snapshots.push(result?);
+    }
+    snapshots.sort_by_key(|(index, _)| *index);
+
+    let snapshots: Vec<InstanceSnapshot> = snapshots.into_iter().map(|(_, s)| s).collect();
+    Ok(parse(provider, config, kind, &snapshots))
+}
+
+/// 依次请求实例的各个接口，健康检查失败时不再请求其他接口
+async fn snapshot(
+    client: &Client,
+    headers: &HashMap<String, String>,
+    base: &str,
+    kind: ServerKind,
+    timeout: Duration,
+) -> InstanceSnapshot {
+    let base = base.trim_end_matches('/');
+    let get = |path: &str| {
+        let mut request = client.get(format!("{}{}", base, path)).timeout(timeout);
+        for (name, value) in headers {
+            request = request.header(name.as_str(), value.as_str());
+        }
+        request.send()
+    };
+    let body = |path: Option<&'static str>| async move {
+        let response = get(path?).await.ok()?;
+        if !response.status().is_success() {
+            return None;
+        }
+        response.text().await.ok()
+    };
+
+    let endpoints = kind.endpoints();
+    let health = match get(endpoints.health).await {
+        Ok(response) => {
+            let code = response.status().as_u16();
+            Ok((code, response.text().await.unwrap_or_default()))
+        }
+        Err(e) => Err(if e.is_timeout() { "请求超时".to_string() } else { e.to_string() }),
+    };
+    if !matches!(health, Ok((code, _)) if (200..300).contains(&code)) {
+        return InstanceSnapshot { health, ..Default::default() };
+    }
+
+    InstanceSnapshot {
+        health,
+        models: body(Some(endpoints.models)).await,
+        loaded: body(endpoints.loaded).await,
+        metrics: body(endpoints.metrics).await,
+    }
+}
+
+/// 将各实例的响应转换为组件
+pub fn parse(
+    provider: &ProviderConfig,
+    config: &InferenceConfig,
+    kind: ServerKind,
+    snapshots: &[InstanceSnapshot],
+) -> StatusPageSummary {
+    let components: Vec<ScrapedComponent> = config
+        .instances
+        .iter()
+        .zip(snapshots)
+        .map(|(instance, snapshot)| {
+            let (status, description) = instance_status(config, kind, instance, snapshot);
+            ScrapedComponent { name: instance.name.clone(), status, description: Some(description) }
+        })
+        .collect();
+
+    info!(
+        "📊 {} {} 实例检查完成 - 实例数: {}, 正常: {}",
+        provider.name,
+        kind.label(),
+        components.len(),
+        components.iter().filter(|c| c.status == ComponentStatus::Operational).count()
+    );
+    let url = config.instances.first().map(|i| i.url.as_str()).unwrap_or_default();
+    build_summary(provider, url, components, Vec::new(), Vec::new(), None)
+}
+
+/// 推理服务的负载指标
+#[derive(Debug, Default, PartialEq)]
+struct Load {
+    running: Option<f64>,
+    waiting: Option<f64>,
+    /// KV 缓存占用（0~1，仅 vLLM）
+    cache_usage: Option<f64>,
+}
+
+fn instance_status(
+    config: &InferenceConfig,
+    kind: ServerKind,
+    instance: &InferenceInstance,
+    snapshot: &InstanceSnapshot,
+) -> (ComponentStatus, String) {
+    let (code, body) = match &snapshot.health {
+        Ok(health) => health,
+        Err(e) => return (ComponentStatus::MajorOutage, format!("无法连接: {}", e)),
+    };
+    if !(200..300).contains(code) {
+        // llama.cpp 加载模型期间健康检查返回 503
+        if kind == ServerKind::LlamaCpp && *code == 503 && body.to_lowercase().contains("loading") {
+            return (ComponentStatus::DegradedPerformance, "模型加载中".to_string());
+        }
+        return (ComponentStatus::MajorOutage, format!("健康检查返回 HTTP {}", code));
+    }
+
+    let models = snapshot.models.as_deref().map(|body| model_names(kind, body)).unwrap_or_default();
+    let loaded = snapshot.loaded.as_deref().map(|body| model_names(kind, body));
+    let load = snapshot.metrics.as_deref().map(|text| load(kind, text)).unwrap_or_default();
+
+    let mut details = Vec::new();
+    match &loaded {
+        Some(loaded) if loaded.is_empty() => details.push("未加载模型".to_string()),
+        Some(loaded) => details.push(format!("已加载: {}", loaded.join(", "))),
+        None if !models.is_empty() => details.push(format!("模型: {}", models.join(", "))),
+        None => {}
+    }
+    if let Some(running) = load.running {
+        details.push(format!("处理中 {}", running));
+    }
+    if let Some(waiting) = load.waiting {
+        details.push(format!("排队 {}", waiting));
+    }
+    if let Some(usage) = load.cache_usage {
+        details.push(format!("KV 缓存 {:.0}%", usage * 100.0));
+    }
+
+    let missing = instance.model.as_deref().filter(|expected| {
+        !models.is_empty() && !models.iter().any(|m| model_matches(m, expected))
+    });
+    // 模型接口不可用时无法确认应当提供的模型，不能视为正常
+    let unverified = instance.model.as_deref().filter(|_| models.is_empty());
+    let status = if let Some(expected) = missing {
+        details.insert(0, format!("未找到模型 {}", expected));
+        ComponentStatus::PartialOutage
+    } else if let Some(expected) = unverified {
+        details.insert(0, format!("模型接口不可用，无法确认模型 {}", expected));
+        ComponentStatus::DegradedPerformance
+    } else if load.waiting.is_some_and(|w| w >= config.queue_warn as f64) {
+        ComponentStatus::DegradedPerformance
+    } else {
+        ComponentStatus::Operational
+    };
+
+    let description = if details.is_empty() { format!("{} 运行中", kind.label()) } else { details.join(" · ") };
+    (status, description)
+}
+
+/// Ollama 的模型名带标签（如 `llama3:8b`），配置 `llama3` 时同样匹配
+fn model_matches(name: &str, expected: &str) -> bool {
+    name == expected || name.strip_prefix(expected).is_some_and(|rest| rest.starts_with(':'))
+}
+
+/// 从模型接口的响应中提取模型名称
+fn model_names(kind: ServerKind, body: &str) -> Vec<String> {
+    let Ok(value) = serde_json::from_str::<serde_json::Value>(body) else {
+        return Vec::new();
+    };
+    match kind {
+        // TGI 的 `/info` 只有一个模型
+        ServerKind::Tgi => value.get("model_id").and_then(|v| v.as_str()).map(str::to_string).into_iter().collect(),
+        ServerKind::Ollama => value
+            .get("models")
+            .and_then(|v| v.as_array())
+            .map(|models| {
+                models
+                    .iter()
+                    .filter_map(|m| m.get("name").or_else(|| m.get("model")).and_then(|v| v.as_str()))
+                    .map(str::to_string)
+                    .collect()
+            })
+            .unwrap_or_default(),
+        ServerKind::Vllm | ServerKind::LlamaCpp => value
+            .get("data")
+            .and_then(|v| v.as_array())
+            .map(|models| models.iter().filter_map(|m| m.get("id")?.as_str()).map(str::to_string).collect())
+            .unwrap_or_default(),
+    }
+}
+
+/// 从 Prometheus 指标中读取处理中与排队的请求数
+fn load(kind: ServerKind, text: &str) -> Load {
+    let metrics = prometheus_totals(text);
+    let get = |names: &[&str]| names.iter().find_map(|name| metrics.get(*name).copied());
+    match kind {
+        ServerKind::Vllm => Load {
+            running: get(&["vllm:num_requests_running"]),
+            waiting: get(&["vllm:num_requests_waiting"]),
+            cache_usage: get(&["vllm:gpu_cache_usage_perc", "vllm:kv_cache_usage_perc"]),
+        },
+        ServerKind::Tgi => Load {
+            running: get(&["tgi_batch_current_size"]),
+            waiting: get(&["tgi_queue_size"]),
+            cache_usage: None,
+        },
+        ServerKind::LlamaCpp => Load {
+            running: get(&["llamacpp:requests_processing"]),
+            waiting: get(&["llamacpp:requests_deferred"]),
+            cache_usage: None,
+        },
+        ServerKind::Ollama => Load::default(),
+    }
+}
+
+/// 按指标名汇总 Prometheus 文本格式中的样本值（忽略标签，多个模型的值相加）
+fn prometheus_totals(text: &str) -> HashMap<String, f64> {
+    let mut totals = HashMap::new();
+    for line in text.lines().map(str::trim).filter(|l| !l.is_empty() && !l.starts_with('#')) {
+        let name_end = line.find(['{', ' ']).unwrap_or(line.len());
+        let rest = match line[name_end..].strip_prefix('{') {
+            Some(labelled) => labelled.split_once('}').map_or("", |(_, rest)| rest),
+            None => &line[name_end..],
+        };
+        if let Some(value) = rest.split_whitespace().next().and_then(|v| v.parse::<f64>().ok()) {
+            *totals.entry(line[..name_end].to_string()).or_insert(0.0) += value;
+        }
+    }
+    totals
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn instance(model: Option<&str>) -> InferenceInstance {
+        InferenceInstance { name: "gpu-1".to_string(), url: "http://10.0.0.1:8000".to_string(), model: model.map(str::to_string) }
+    }
+
+    fn config() -> InferenceConfig {
+        InferenceConfig { instances: vec![instance(None)], headers: HashMap::new(), queue_warn: 10, timeout_secs: 5 }
+    }
+
+    fn healthy(models: &str, metrics: Option<&str>) -> InstanceSnapshot {
+        InstanceSnapshot {
+            health: Ok((200, String::new())),
+            models: Some(models.to_string()),
+            loaded: None,
+            metrics: metrics.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn vllm_queue_depth_and_cache_usage() {
+        let metrics = "# HELP vllm:num_requests_waiting Number of requests waiting\n\
+            vllm:num_requests_running{model_name=\"qwen\"} 4.0\n\
+            vllm:num_requests_waiting{model_name=\"qwen\"} 12.0\n\
+            vllm:gpu_cache_usage_perc{model_name=\"qwen\"} 0.85\n";
+        let snapshot = healthy(r#"{"data":[{"id":"Qwen/Qwen2.5-72B-Instruct"}]}"#, Some(metrics));
+        let (status, description) = instance_status(&config(), ServerKind::Vllm, &instance(None), &snapshot);
+        assert_eq!(status, ComponentStatus::DegradedPerformance);
+        assert_eq!(description, "模型: Qwen/Qwen2.5-72B-Instruct · 处理中 4 · 排队 12 · KV 缓存 85%");
+    }
+
+    #[test]
+    fn missing_expected_model_is_partial_outage() {
+        let snapshot = healthy(r#"{"model_id":"meta-llama/Llama-3.1-8B-Instruct"}"#, Some("tgi_queue_size 0\n"));
+        let (status, description) =
+            instance_status(&config(), ServerKind::Tgi, &instance(Some("mistralai/Mistral-7B")), &snapshot);
+        assert_eq!(status, ComponentStatus::PartialOutage);
+        assert!(description.starts_with("未找到模型 mistralai/Mistral-7B"));
+    }
+
+    #[test]
+    fn ollama_lists_loaded_models_and_matches_tags() {
+        let snapshot = InstanceSnapshot {
+            health: Ok((200, "Ollama is running".to_string())),
+            models: Some(r#"{"models":[{"name":"llama3:8b"},{"name":"qwen2.5:14b"}]}"#.to_string()),
+            loaded: Some(r#"{"models":[{"name":"llama3:8b","size_vram":5000000000}]}"#.to_string()),
+            metrics: None,
+        };
+        let (status, description) = instance_status(&config(), ServerKind::Ollama, &instance(Some("qwen2.5")), &snapshot);
+        assert_eq!(status, ComponentStatus::Operational);
+        assert_eq!(description, "已加载: llama3:8b");
+    }
+
+    #[test]
+    fn llama_cpp_loading_and_unreachable() {
+        let loading = InstanceSnapshot {
+            health: Ok((503, r#"{"error":{"code":503,"message":"Loading model"}}"#.to_string())),
+            ..Default::default()
+        };
+        assert_eq!(
+            instance_status(&config(), ServerKind::LlamaCpp, &instance(None), &loading).0,
+            ComponentStatus::DegradedPerformance
+        );
+
+        let down = InstanceSnapshot { health: Err("connection refused".to_string()), ..Default::default() };
+        let (status, description) = instance_status(&config(), ServerKind::LlamaCpp, &instance(None), &down);
+        assert_eq!(status, ComponentStatus::MajorOutage);
+        assert_eq!(description, "无法连接: connection refused");
+    }
+
+    #[tokio::test]
+    async fn fetch_polls_each_instance() {
+        use axum::{routing::get, Router};
+        let app = Router::new()
+            .route("/health", get(|| async { "" }))
+            .route("/v1/models", get(|| async { r#"{"data":[{"id":"qwen"}]}"# }))
+            .route("/metrics", get(|| async { "vllm:num_requests_running 1\nvllm:num_requests_waiting 0\n" }));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        let config = InferenceConfig {
+            instances: vec![
+                InferenceInstance { name: "gpu-1".to_string(), url: format!("http://{}", addr), model: Some("qwen".to_string()) },
+                InferenceInstance { name: "gpu-2".to_string(), url: "http://127.0.0.1:1".to_string(), model: None },
+            ],
+            ..config()
+        };
+        let provider: ProviderConfig = serde_json::from_value(serde_json::json!({
+            "id": "cluster", "name": "GPU Cluster", "source": { "type": "vllm", "instances": [] }
+        }))
+        .unwrap();
+
+        let summary = fetch(&Client::new(), &provider, &config, ServerKind::Vllm).await.unwrap();
+        assert_eq!(summary.components.len(), 2);
+        assert_eq!(summary.components[0].status, ComponentStatus::Operational);
+        assert_eq!(summary.components[0].description.as_deref(), Some("模型: qwen · 处理中 1 · 排队 0"));
+        assert_eq!(summary.components[1].status, ComponentStatus::MajorOutage);
+    }
+
+    #[test]
+    fn expected_model_without_model_listing_is_degraded() {
+        let snapshot = InstanceSnapshot { health: Ok((200, String::new())), ..Default::default() };
+        let (status, description) = instance_status(&config(), ServerKind::Vllm, &instance(Some("qwen")), &snapshot);
+        assert_eq!(status, ComponentStatus::DegradedPerformance);
+        assert_eq!(description, "模型接口不可用，无法确认模型 qwen");
+
+        let empty = healthy(r#"{"data":[]}"#, None);
+        assert_eq!(
+            instance_status(&config(), ServerKind::Vllm, &instance(Some("qwen")), &empty).0,
+            ComponentStatus::DegradedPerformance
+        );
+        assert_eq!(instance_status(&config(), ServerKind::Vllm, &instance(None), &snapshot).0, ComponentStatus::Operational);
+    }
+}
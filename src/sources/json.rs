@@ -0,0 +1,237 @@
+//! JSON 接口数据源：通过 JSONPath 从任意结构的健康检查接口中提取状态
+
+use super::{build_summary, ProviderConfig, ScrapedComponent};
+use crate::models::{ComponentStatus, OverallStatus, StatusIndicator, StatusPageSummary};
+use jsonpath_rust::JsonPath;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::error::Error;
+use std::str::FromStr;
+use tracing::{info, warn};
+
+/// JSON 接口配置
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct JsonEndpointConfig {
+    pub url: String,
+    /// 额外请求头，值中的 `${VAR}` 会替换为环境变量
+    #[serde(default)]
+    pub headers: HashMap<String, String>,
+    /// 整体状态值的路径，未配置时由组件状态推导
+    #[serde(default)]
+    pub overall_status: Option<String>,
+    /// 整体状态描述的路径
+    #[serde(default)]
+    pub overall_description: Option<String>,
+    /// 整体状态值到指示器的映射（不区分大小写）
+    #[serde(default)]
+    pub indicator_map: HashMap<String, StatusIndicator>,
+    /// 组件列表的路径，如 `$.services[*]`
+    pub components: String,
+    /// 组件名称的路径（相对于组件）
+    pub component_name: String,
+    /// 组件状态的路径（相对于组件）
+    pub component_status: String,
+    /// 组件描述的路径（相对于组件）
+    #[serde(default)]
+    pub component_description: Option<String>,
+    /// 组件状态值到组件状态的映射（不区分大小写）
+    #[serde(default)]
+    pub status_map: HashMap<String, ComponentStatus>,
+    /// 没有任何映射命中时使用的状态
+    #[serde(default = "default_status")]
+    pub default_status: ComponentStatus,
+}
+
+fn default_status() -> ComponentStatus {
+    ComponentStatus::Unknown
+}
+
+/// 解析接口返回内容
+pub fn parse(
+    provider: &ProviderConfig,
+    config: &JsonEndpointConfig,
+    body: &str,
+) -> Result<StatusPageSummary, Box<dyn Error + Send + Sync>> {
+    let json: Value = serde_json::from_str(body)?;
+
+    let mut nodes = query(&config.components, &json)?;
+    // 路径直接指向数组时展开其元素
+    if let [Value::Array(items)] = nodes.as_slice() {
+        nodes = items.clone();
+    }
+
+    let mut components = Vec::new();
+    for node in &nodes {
+        let Some(name) = query_text(&config.component_name, node)? else {
+            continue;
+        };
+        let raw = query_text(&config.component_status, node)?.unwrap_or_default();
+        let status = lookup(&config.status_map, &raw).unwrap_or_else(|| {
+            warn!("⚠️ {} 组件 {} 的状态值未命中任何映射: {}", provider.name, name, raw);
+            config.default_status.clone()
+        });
+        let description = match &config.component_description {
+            Some(path) => query_text(path, node)?,
+            None => None,
+        };
+        components.push(ScrapedComponent {
+            name,
+            status,
+            description,
+        });
+    }
+
+    let status = match &config.overall_status {
+        Some(path) => {
+            let raw = query_text(path, &json)?.unwrap_or_default();
+            let description = match &config.overall_description {
+                Some(path) => query_text(path, &json)?,
+                None => None,
+            };
+            match lookup(&config.indicator_map, &raw) {
+                Some(indicator) => Some(OverallStatus {
+                    indicator,
+                    description: description.unwrap_or(raw),
+                }),
+                None => {
+                    warn!("⚠️ {} 的整体状态值未命中任何映射: {}", provider.name, raw);
+                    None
+                }
+            }
+        }
+        None => None,
+    };
+
+    if components.is_empty() && status.is_none() {
+        return Err(format!("接口中未找到任何组件: {}", config.components).into());
+    }
+
+    info!("📊 {} 接口解析完成 - 组件数: {}", provider.name, components.len());
+    Ok(build_summary(provider, &config.url, components, vec![], vec![], status))
+}
+
+/// 执行 JSONPath 查询
+pub(super) fn query(path: &str, json: &Value) -> Result<Vec<Value>, Box<dyn Error + Send + Sync>> {
+    let path = JsonPath::<Value>::from_str(path).map_err(|e| format!("无效的JSONPath {}: {}", path, e))?;
+    Ok(path.find_slice_ptr(json).into_iter().map(|v| (*v).clone()).collect())
+}
+
+/// 查询第一个值并转为文本
+pub(super) fn query_text(path: &str, json: &Value) -> Result<Option<String>, Box<dyn Error + Send + Sync>> {
+    Ok(query(path, json)?.into_iter().next().and_then(|value| match value {
+        Value::String(s) => Some(s.trim().to_string()),
+        Value::Null => None,
+        other => Some(other.to_string()),
+    }))
+}
+
+/// 先查映射表，未配置映射时尝试按 statuspage 的取值直接解析
+fn lookup<T: Clone + DeserializeOwned>(map: &HashMap<String, T>, raw: &str) -> Option<T> {
+    if map.is_empty() {
+        return serde_json::from_value(Value::String(raw.to_lowercase())).ok();
+    }
+    map.iter()
+        .find(|(key, _)| key.eq_ignore_ascii_case(raw))
+        .map(|(_, value)| value.clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn provider(config: Value) -> (ProviderConfig, JsonEndpointConfig) {
+        let mut source = json!({ "type": "json_endpoint", "url": "https://health.example.com/api" });
+        source.as_object_mut().unwrap().extend(config.as_object().unwrap().clone());
+        let provider: ProviderConfig =
+            serde_json::from_value(json!({ "id": "acme", "name": "Acme", "source": source.clone() })).unwrap();
+        (provider, serde_json::from_value(source).unwrap())
+    }
+
+    const BODY: &str = r#"{
+        "health": { "state": "DEGRADED", "message": "Elevated latency" },
+        "services": [
+            { "name": "Chat API", "state": "UP", "detail": { "latency": 120 } },
+            { "name": "Embeddings", "state": "down", "detail": { "note": " Region failover " } },
+            { "name": "Batch", "state": "paused" },
+            { "state": "UP" }
+        ]
+    }"#;
+
+    #[test]
+    fn query_returns_every_match() {
+        let json: Value = serde_json::from_str(BODY).unwrap();
+        let names = query("$.services[*].name", &json).unwrap();
+        assert_eq!(names, vec![json!("Chat API"), json!("Embeddings"), json!("Batch")]);
+        assert!(query("$.missing", &json).unwrap().iter().all(Value::is_null));
+        assert!(query("services[", &json).unwrap_err().to_string().starts_with("无效的JSONPath services["));
+    }
+
+    #[test]
+    fn query_text_trims_strings_and_stringifies_scalars() {
+        let json: Value = serde_json::from_str(BODY).unwrap();
+        assert_eq!(query_text("$.services[1].detail.note", &json).unwrap().as_deref(), Some("Region failover"));
+        assert_eq!(query_text("$.services[0].detail.latency", &json).unwrap().as_deref(), Some("120"));
+        assert_eq!(query_text("$.services[0].nothing", &json).unwrap(), None);
+        assert_eq!(query_text("$.nothing", &json).unwrap(), None);
+    }
+
+    #[test]
+    fn components_are_mapped_case_insensitively() {
+        let (provider, config) = provider(json!({
+            "overall_status": "$.health.state",
+            "overall_description": "$.health.message",
+            "indicator_map": { "degraded": "minor" },
+            "components": "$.services[*]",
+            "component_name": "$.name",
+            "component_status": "$.state",
+            "component_description": "$.detail.note",
+            "status_map": { "up": "operational", "DOWN": "major_outage" },
+            "default_status": "under_maintenance"
+        }));
+        let summary = parse(&provider, &config, BODY).unwrap();
+
+        let components: Vec<_> = summary.components.iter().map(|c| (c.name.as_str(), c.status.clone())).collect();
+        // 缺少名称的组件被跳过，未命中映射的状态使用默认值
+        assert_eq!(
+            components,
+            vec![
+                ("Chat API", ComponentStatus::Operational),
+                ("Embeddings", ComponentStatus::MajorOutage),
+                ("Batch", ComponentStatus::UnderMaintenance),
+            ]
+        );
+        assert_eq!(summary.components[1].description.as_deref(), Some("Region failover"));
+        assert_eq!(summary.status.indicator, StatusIndicator::Minor);
+        assert_eq!(summary.status.description, "Elevated latency");
+    }
+
+    #[test]
+    fn array_path_and_statuspage_values_without_map() {
+        let (provider, config) = provider(json!({
+            "components": "$.items",
+            "component_name": "$.id",
+            "component_status": "$.status"
+        }));
+        let body = r#"{ "items": [
+            { "id": "gpt", "status": "Degraded_Performance" },
+            { "id": "tts", "status": "sleepy" }
+        ] }"#;
+        let summary = parse(&provider, &config, body).unwrap();
+        assert_eq!(summary.components[0].status, ComponentStatus::DegradedPerformance);
+        assert_eq!(summary.components[1].status, ComponentStatus::Unknown);
+    }
+
+    #[test]
+    fn empty_result_is_an_error() {
+        let (provider, config) = provider(json!({
+            "components": "$.nodes[*]",
+            "component_name": "$.name",
+            "component_status": "$.state"
+        }));
+        let error = parse(&provider, &config, BODY).unwrap_err();
+        assert_eq!(error.to_string(), "接口中未找到任何组件: $.nodes[*]");
+    }
+}
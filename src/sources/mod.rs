@@ -0,0 +1,436 @@
+//! 配置文件驱动的自定义数据源
+//!
+//! 内置的四个供应商之外，可以在 `config/providers.json` 中声明任意数量的供应商，
+//! 每个供应商通过 `source.type` 指定数据源类型；也可以通过 `enabled` 启用内置目录中的供应商。
+
+pub mod announcements;
+pub mod aws;
+pub mod azure;
+pub mod betterstack;
+pub mod browser;
+pub mod catalog;
+pub mod google_cloud;
+pub mod html;
+pub mod http_check;
+pub mod incident_io;
+pub mod inference;
+pub mod instatus;
+pub mod statuspage;
+pub mod json;
+
+use crate::component_ids;
+use crate::config::CONFIG;
+use crate::models::{
+    Component, ComponentStatus, Incident, OverallStatus, Page, ScheduledMaintenance, StatusIndicator,
+    StatusPageSummary,
+};
+use regex::{Regex, RegexBuilder};
+use std::collections::HashMap;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use tracing::{error, info};
+
+/// 配置文件结构
+#[derive(Debug, Deserialize)]
+struct ProvidersFile {
+    /// 启用的内置目录供应商
+    #[serde(default)]
+    enabled: Vec<catalog::CatalogEntry>,
+    /// 逐个解析，单个供应商配置无效时只跳过该供应商
+    #[serde(default)]
+    providers: Vec<serde_json::Value>,
+}
+
+/// 自定义供应商
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ProviderConfig {
+    /// 唯一标识，同时作为组件ID前缀
+    pub id: String,
+    /// 显示名称，也是 status.json 中的键
+    pub name: String,
+    /// 官方状态页面链接
+    #[serde(default)]
+    pub link: Option<String>,
+    /// 图标URL
+    #[serde(default)]
+    pub icon: Option<String>,
+    pub source: SourceConfig,
+    /// 只保留名称包含其中任一文本的组件（不区分大小写），为空时保留全部
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub component_filter: Vec<String>,
+}
+
+/// 数据源类型
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum SourceConfig {
+    /// Atlassian Statuspage 的 `/api/v2/summary.json`
+    Statuspage(statuspage::StatuspageConfig),
+    /// 无头浏览器渲染后按选择器提取
+    BrowserScrape(browser::BrowserScrapeConfig),
+    /// 直接下载服务端渲染的页面并按选择器提取
+    StaticHtml(html::StaticHtmlConfig),
+    /// 非 statuspage 格式的 JSON 健康检查接口
+    JsonEndpoint(json::JsonEndpointConfig),
+    /// incident.io 托管的状态页面
+    IncidentIo(incident_io::IncidentIoConfig),
+    /// Instatus 托管的状态页面
+    Instatus(instatus::InstatusConfig),
+    /// Better Stack 托管的状态页面
+    BetterStack(betterstack::BetterStackConfig),
+    /// Google Cloud 状态面板事件流（Vertex AI 等）
+    GoogleCloud(google_cloud::GoogleCloudConfig),
+    /// Azure 状态 RSS 订阅（Azure OpenAI 等）
+    AzureStatus(azure::AzureStatusConfig),
+    /// AWS Health Dashboard 公开事件（Amazon Bedrock 等）
+    AwsHealth(aws::AwsHealthConfig),
+    /// 只发布故障/维护公告的供应商
+    Announcements(announcements::AnnouncementsConfig),
+    /// 自建 vLLM 推理服务
+    Vllm(inference::InferenceConfig),
+    /// 自建 Text Generation Inference 推理服务
+    Tgi(inference::InferenceConfig),
+    /// 自建 Ollama 推理服务
+    Ollama(inference::InferenceConfig),
+    /// 自建 llama.cpp server 推理服务
+    LlamaCpp(inference::InferenceConfig),
+    /// 通用 HTTP 可用性检查（向量数据库、自建网关等）
+    HttpCheck(http_check::HttpCheckConfig),
+}
+
+impl SourceConfig {
+    /// 检查当前构建是否支持该数据源
+    pub fn is_supported(&self) -> bool {
+        !matches!(self, SourceConfig::BrowserScrape(_)) || cfg!(feature = "browser")
+    }
+}
+
+/// 页面元素选择器与状态映射，浏览器爬虫与静态页面共用
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct PageSelectors {
+    /// 整体状态文本的选择器，未配置时由组件状态推导
+    #[serde(default)]
+    pub overall_status: Option<String>,
+    /// 整体状态文本到指示器的映射
+    #[serde(default)]
+    pub overall_mappings: Vec<MappingRule<StatusIndicator>>,
+    /// 匹配每个服务行的选择器
+    pub service_list: String,
+    /// 服务名称选择器（相对于服务行）
+    pub service_name: String,
+    /// 服务状态元素选择器（相对于服务行），未配置时使用服务行本身
+    #[serde(default)]
+    pub service_status: Option<String>,
+    /// 从状态元素的哪一部分读取状态
+    #[serde(default)]
+    pub status_from: StatusSource,
+    /// 状态文本/class 到组件状态的映射
+    pub mappings: Vec<MappingRule<ComponentStatus>>,
+    /// 没有任何映射命中时使用的状态
+    #[serde(default = "default_status")]
+    pub default_status: ComponentStatus,
+}
+
+/// 状态读取位置
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StatusSource {
+    /// 元素的 class 属性
+    #[default]
+    Class,
+    /// 元素文本
+    Text,
+    /// 指定属性，如 `{"attribute": "data-status"}`
+    Attribute(String),
+}
+
+fn default_status() -> ComponentStatus {
+    ComponentStatus::Unknown
+}
+
+/// 按顺序匹配的映射规则，命中第一条即返回对应值
+///
+/// `contains` 与 `regex` 至少配置一个，同时配置时需全部满足，均不区分大小写。
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct MappingRule<T> {
+    /// 输入包含该文本时命中
+    #[serde(default)]
+    pub contains: Option<String>,
+    /// 输入匹配该正则时命中
+    #[serde(default)]
+    pub regex: Option<Pattern>,
+    pub status: T,
+}
+
+impl<T> MappingRule<T> {
+    fn matches(&self, input: &str) -> bool {
+        if self.contains.is_none() && self.regex.is_none() {
+            return false;
+        }
+        let contains = self
+            .contains
+            .as_ref()
+            .is_none_or(|text| input.to_lowercase().contains(&text.to_lowercase()));
+        let regex = self.regex.as_ref().is_none_or(|pattern| pattern.0.is_match(input));
+        contains && regex
+    }
+}
+
+/// 不区分大小写的正则，在加载配置时编译，无效的正则直接导致配置解析失败
+#[derive(Debug, Clone)]
+pub struct Pattern(Regex);
+
+impl Serialize for Pattern {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.0.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for Pattern {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let pattern = String::deserialize(deserializer)?;
+        RegexBuilder::new(&pattern)
+            .case_insensitive(true)
+            .build()
+            .map(Pattern)
+            .map_err(|e| serde::de::Error::custom(format!("无效的映射正则 {}: {}", pattern, e)))
+    }
+}
+
+/// 在映射表中查找输入对应的值
+pub fn map_value<T: Clone>(rules: &[MappingRule<T>], input: &str) -> Option<T> {
+    rules
+        .iter()
+        .find(|rule| rule.matches(input))
+        .map(|rule| rule.status.clone())
+}
+
+/// 将 `${VAR}` 替换为环境变量的值，未设置的变量替换为空串
+pub fn expand_env(value: &str) -> String {
+    static PATTERN: std::sync::LazyLock<Regex> =
+        std::sync::LazyLock::new(|| Regex::new(r"\$\{([A-Za-z_][A-Za-z0-9_]*)\}").unwrap());
+    PATTERN
+        .replace_all(value, |caps: &regex::Captures| std::env::var(&caps[1]).unwrap_or_default())
+        .into_owned()
+}
+
+/// 展开请求头中的环境变量
+pub fn expand_headers(headers: &HashMap<String, String>) -> HashMap<String, String> {
+    headers
+        .iter()
+        .map(|(name, value)| (name.clone(), expand_env(value)))
+        .collect()
+}
+
+/// 按词边界查找模型名、服务名等术语，避免 `gpt-4o` 命中 `gpt-4o-mini`
+pub fn mentions(text: &str, term: &str) -> bool {
+    if term.is_empty() {
+        return false;
+    }
+    // 只把 ASCII 字母数字视为词的一部分，中文紧跟模型名时仍能命中
+    let is_word = |c: char| c.is_ascii_alphanumeric() || c == '-' || c == '.' || c == '_';
+    text.match_indices(term).any(|(start, _)| {
+        let before = text[..start].chars().next_back();
+        let after = text[start + term.len()..].chars().next();
+        // 句末的点不算作词的一部分
+        let after_ok = match after {
+            Some('.') => !text[start + term.len() + 1..].chars().next().is_some_and(|c| c.is_ascii_alphanumeric()),
+            Some(c) => !is_word(c),
+            None => true,
+        };
+        !before.is_some_and(is_word) && after_ok
+    })
+}
+
+/// 数据源解析出的组件，ID在生成摘要时由名称派生
+#[derive(Debug, Clone)]
+pub struct ScrapedComponent {
+    pub name: String,
+    pub status: ComponentStatus,
+    pub description: Option<String>,
+}
+
+/// 由数据源结果组装状态摘要
+///
+/// 未提供整体状态时，根据最严重的组件状态推导。
+pub fn build_summary(
+    provider: &ProviderConfig,
+    url: &str,
+    components: Vec<ScrapedComponent>,
+    incidents: Vec<Incident>,
+    scheduled_maintenances: Vec<ScheduledMaintenance>,
+    status: Option<OverallStatus>,
+) -> StatusPageSummary {
+    let now = chrono::Utc::now().to_rfc3339();
+    let ids = component_ids::assign_ids(&provider.id, components.iter().map(|c| c.name.as_str()));
+
+    let components: Vec<Component> = components
+        .into_iter()
+        .zip(ids)
+        .enumerate()
+        .map(|(i, (component, id))| Component {
+            id,
+            name: component.name,
+            status: component.status,
+            created_at: now.clone(),
+            updated_at: now.clone(),
+            position: i as u32,
+            description: component.description,
+            group_id: None,
+            group: Some(false),
+            only_show_if_degraded: false,
+        })
+        .collect();
+
+    let status = status.unwrap_or_else(|| OverallStatus::from_components(&components));
+
+    StatusPageSummary {
+        page: Page {
+            id: provider.id.clone(),
+            name: provider.name.clone(),
+            url: provider.link.clone().unwrap_or_else(|| url.to_string()),
+            updated_at: now,
+            time_zone: Some("UTC".to_string()),
+        },
+        components,
+        incidents,
+        scheduled_maintenances,
+        status,
+    }
+}
+
+/// 按组件过滤条件裁剪摘要，并根据保留的组件重新推导整体状态
+///
+/// 事件只保留影响了保留组件或标题包含过滤文本的条目。
+pub fn apply_component_filter(summary: &mut StatusPageSummary, filter: &[String]) {
+    if filter.is_empty() {
+        return;
+    }
+    let filter: Vec<String> = filter.iter().map(|f| f.to_lowercase()).collect();
+    let matches = |name: &str| {
+        let name = name.to_lowercase();
+        filter.iter().any(|f| name.contains(f.as_str()))
+    };
+
+    summary.components.retain(|c| matches(&c.name));
+    summary.incidents.retain(|incident| {
+        matches(&incident.name)
+            || incident
+                .incident_updates
+                .iter()
+                .filter_map(|u| u.affected_components.as_ref())
+                .flatten()
+                .filter_map(|c| c.get("name"))
+                .any(|name| matches(name))
+    });
+    summary.status = OverallStatus::from_components(&summary.components);
+}
+
+/// 供前端展示的链接与图标，按供应商名称索引
+pub fn provider_links(providers: &[ProviderConfig]) -> serde_json::Value {
+    providers
+        .iter()
+        .map(|p| {
+            (p.name.clone(), serde_json::json!({ "link": p.link, "icon": p.icon }))
+        })
+        .collect::<serde_json::Map<_, _>>()
+        .into()
+}
+
+/// 加载自定义供应商配置，每轮刷新时调用，修改文件后无需重启
+pub fn load_providers() -> Vec<ProviderConfig> {
+    let content = match fs::read_to_string(CONFIG.providers_file) {
+        Ok(content) => content,
+        Err(_) => return Vec::new(),
+    };
+
+    match serde_json::from_str::<ProvidersFile>(&content) {
+        Ok(file) => {
+            let mut providers = catalog::resolve(&file.enabled);
+            if !providers.is_empty() {
+                info!("📋 已启用 {} 个内置目录供应商", providers.len());
+            }
+            let custom: Vec<ProviderConfig> = file
+                .providers
+                .into_iter()
+                .filter_map(|value| {
+                    parse_provider(value)
+                        .map_err(|e| error!("❌ {} 中的{}，已跳过", CONFIG.providers_file, e))
+                        .ok()
+                })
+                .collect();
+            if !custom.is_empty() {
+                info!("📋 已加载 {} 个自定义供应商", custom.len());
+            }
+            providers.extend(custom);
+            providers
+        }
+        Err(e) => {
+            error!("❌ 解析供应商配置 {} 失败: {}", CONFIG.providers_file, e);
+            Vec::new()
+        }
+    }
+}
+
+/// 解析单个供应商配置，错误信息中带上供应商ID
+fn parse_provider(value: serde_json::Value) -> Result<ProviderConfig, String> {
+    let id = value.get("id").and_then(|id| id.as_str()).unwrap_or("(未命名)").to_string();
+    serde_json::from_value(value).map_err(|e| format!("供应商 {} 配置无效: {}", id, e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rules(value: serde_json::Value) -> Vec<MappingRule<ComponentStatus>> {
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[test]
+    fn first_matching_rule_wins() {
+        let rules = rules(serde_json::json!([
+            { "contains": "outage", "regex": "^major", "status": "major_outage" },
+            { "contains": "outage", "status": "partial_outage" },
+            { "regex": "degrad(ed|ation)", "status": "degraded_performance" },
+            { "status": "operational" }
+        ]));
+        assert_eq!(map_value(&rules, "Major Outage"), Some(ComponentStatus::MajorOutage));
+        assert_eq!(map_value(&rules, "Partial outage"), Some(ComponentStatus::PartialOutage));
+        assert_eq!(map_value(&rules, "status-DEGRADED"), Some(ComponentStatus::DegradedPerformance));
+        // 既没有 contains 也没有 regex 的规则不会命中
+        assert_eq!(map_value(&rules, "Operational"), None);
+    }
+
+    #[test]
+    fn patterns_round_trip() {
+        let rules = rules(serde_json::json!([{ "regex": "^ok$", "status": "operational" }]));
+        assert_eq!(serde_json::to_value(&rules[0]).unwrap()["regex"], "^ok$");
+    }
+
+    #[test]
+    fn invalid_pattern_rejects_only_that_provider() {
+        let error = parse_provider(serde_json::json!({
+            "id": "acme",
+            "name": "Acme",
+            "source": {
+                "type": "static_html",
+                "url": "https://status.acme.dev",
+                "service_list": ".component",
+                "service_name": ".name",
+                "mappings": [{ "regex": "(unclosed", "status": "major_outage" }]
+            }
+        }))
+        .unwrap_err();
+        assert!(error.starts_with("供应商 acme 配置无效: 无效的映射正则 (unclosed"), "{}", error);
+    }
+
+    #[test]
+    fn mentions_respects_word_boundaries() {
+        assert!(mentions("errors on gpt-4o.", "gpt-4o"));
+        assert!(mentions("gpt-4o and o1 affected", "gpt-4o"));
+        assert!(!mentions("gpt-4o-mini affected", "gpt-4o"));
+        assert!(!mentions("claude-3.5-sonnet", "claude-3"));
+        assert!(mentions("deepseek-v3 接口异常", "deepseek-v3"));
+        assert!(mentions("deepseek-v3接口异常", "deepseek-v3"));
+    }
+}
@@ -0,0 +1,83 @@
+//! Atlassian Statuspage 数据源：与内置的 OpenAI/Anthropic/DeepSeek 相同的 `/api/v2/summary.json`
+
+use super::ProviderConfig;
+use crate::models::StatusPageSummary;
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+use tracing::info;
+
+/// Statuspage 数据源配置
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct StatuspageConfig {
+    /// 状态页面地址，如 `https://status.example.com`
+    pub url: String,
+}
+
+impl StatuspageConfig {
+    /// 页面摘要接口地址
+    pub fn api_url(&self) -> String {
+        format!("{}/api/v2/summary.json", self.url.trim_end_matches('/'))
+    }
+}
+
+/// 解析 `/api/v2/summary.json`
+pub fn parse(
+    provider: &ProviderConfig,
+    _config: &StatuspageConfig,
+    body: &str,
+) -> Result<StatusPageSummary, Box<dyn Error + Send + Sync>> {
+    let summary: StatusPageSummary = serde_json::from_str(body)?;
+    info!("📊 {} Statuspage 解析完成 - 组件数: {}", provider.name, summary.components.len());
+    Ok(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{ComponentStatus, StatusIndicator};
+    use crate::sources::apply_component_filter;
+
+    const SUMMARY: &str = r#"{
+        "page": { "id": "y2j98763l56x", "name": "Example", "url": "https://status.example.com", "updated_at": "2025-06-01T08:00:00.000Z" },
+        "components": [
+            { "id": "c1", "name": "Workers AI", "status": "degraded_performance", "created_at": "2023-01-01T00:00:00Z",
+              "updated_at": "2025-06-01T08:00:00Z", "position": 1, "description": null, "group_id": null, "group": false,
+              "only_show_if_degraded": false },
+            { "id": "c2", "name": "CDN", "status": "major_outage", "created_at": "2023-01-01T00:00:00Z",
+              "updated_at": "2025-06-01T08:00:00Z", "position": 2, "description": null, "group_id": null, "group": false,
+              "only_show_if_degraded": false },
+            { "id": "c3", "name": "Labs", "status": "in_preview", "created_at": "2023-01-01T00:00:00Z",
+              "updated_at": "2025-06-01T08:00:00Z", "position": 3, "only_show_if_degraded": false }
+        ],
+        "incidents": [],
+        "status": { "indicator": "major", "description": "Partial System Outage" }
+    }"#;
+
+    fn setup() -> (ProviderConfig, StatuspageConfig) {
+        let provider: ProviderConfig = serde_json::from_value(serde_json::json!({
+            "id": "example", "name": "Example", "source": { "type": "statuspage", "url": "https://status.example.com" }
+        }))
+        .unwrap();
+        (provider, StatuspageConfig { url: "https://status.example.com/".to_string() })
+    }
+
+    #[test]
+    fn tolerates_unknown_status_and_missing_fields() {
+        let (provider, config) = setup();
+        assert_eq!(config.api_url(), "https://status.example.com/api/v2/summary.json");
+        let summary = parse(&provider, &config, SUMMARY).unwrap();
+        assert_eq!(summary.components[2].status, ComponentStatus::Unknown);
+        assert!(summary.scheduled_maintenances.is_empty());
+        assert_eq!(summary.status.indicator, StatusIndicator::Major);
+    }
+
+    #[test]
+    fn component_filter_rederives_status() {
+        let (provider, config) = setup();
+        let mut summary = parse(&provider, &config, SUMMARY).unwrap();
+        apply_component_filter(&mut summary, &["workers ai".to_string()]);
+        let names: Vec<&str> = summary.components.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, ["Workers AI"]);
+        assert_eq!(summary.status.indicator, StatusIndicator::Minor);
+    }
+}
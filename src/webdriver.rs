@@ -0,0 +1,77 @@
+use std::error::Error;
+use thirtyfour::prelude::*;
+use thirtyfour::PageLoadStrategy;
+use tokio::time::{sleep, Duration};
+use tracing::{debug, info, warn};
+
+/// ChromeDriver 监听地址
+pub const CHROME_DRIVER_URL: &str = "http://localhost:9515";
+
+/// 创建 WebDriver 实例
+pub async fn create_webdriver() -> Result<WebDriver, Box<dyn Error + Send + Sync>> {
+    let mut caps = DesiredCapabilities::chrome();
+    
+    // Chrome 启动参数
+    let chrome_args = [
+        "--headless=new",
+        "--no-sandbox",
+        "--disable-dev-shm-usage",
+        "--disable-gpu",
+        "--disable-web-security",
+        "--disable-features=VizDisplayCompositor",
+        "--window-size=1920,1080",
+        "--user-agent=Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/137.0.0.0 Safari/537.36",
+    ];
+    
+    for arg in chrome_args {
+        caps.add_arg(arg)?;
+    }
+    
+    caps.set_page_load_strategy(PageLoadStrategy::Normal)?;
+    
+    let driver = WebDriver::new(CHROME_DRIVER_URL, caps).await
+        .map_err(|e| -> Box<dyn Error + Send + Sync> { 
+            format!("WebDriver初始化失败: {}。请确保ChromeDriver在{}运行", e, CHROME_DRIVER_URL).into() 
+        })?;
+    
+    info!("✅ WebDriver 初始化完成");
+    Ok(driver)
+}
+
+/// 等待页面完全加载，并额外等待前端应用渲染
+pub async fn wait_for_page_ready(driver: &WebDriver, settle: Duration) -> Result<(), Box<dyn Error + Send + Sync>> {
+    // 等待页面加载完成
+    for i in 0..10 {
+        let ready_state = driver.execute("return document.readyState", vec![]).await?;
+        
+        match ready_state.convert::<String>() {
+            Ok(state) if state == "complete" => {
+                debug!("✅ 页面加载完成");
+                break;
+            }
+            Ok(state) => {
+                debug!("📊 页面状态: {}", state);
+            }
+            Err(_) => {
+                debug!("⚠️ 无法获取页面状态");
+            }
+        }
+        
+        if i == 9 {
+            warn!("⚠️ 页面可能未完全加载，但继续执行");
+        }
+        
+        sleep(Duration::from_millis(500)).await;
+    }
+    
+    // 额外等待前端应用加载
+    sleep(settle).await;
+    Ok(())
+}
+
+/// 获取页面源码和截图，用于诊断快照
+pub async fn capture_page(driver: &WebDriver) -> (Option<String>, Option<Vec<u8>>) {
+    let page_source = driver.source().await.ok();
+    let screenshot = driver.screenshot_as_png().await.ok();
+    (page_source, screenshot)
+}